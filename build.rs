@@ -2,10 +2,14 @@ use std::{path::Path, process::Command};
 
 const WASI_ADAPTER_VERSION: &str = "aec4b25";
 
+// This should be kept in sync with the `wit-bindgen` version in `Cargo.toml`.
+const WIT_BINDGEN_VERSION: &str = "0.12.0";
+
 fn main() {
     println!("cargo:rerun-if-changed=build.rs");
 
     println!("cargo:rustc-env=WASI_ADAPTER_VERSION={WASI_ADAPTER_VERSION}");
+    println!("cargo:rustc-env=WIT_BINDGEN_VERSION={WIT_BINDGEN_VERSION}");
     if !commit_info() {
         println!(
             "cargo:rustc-env=CARGO_VERSION_INFO={} (wasi:{WASI_ADAPTER_VERSION})",
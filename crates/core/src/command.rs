@@ -35,7 +35,7 @@ impl CommonOptions {
                     _ => Verbosity::Verbose,
                 }
             },
-            self.color.unwrap_or_default(),
+            self.color.or_else(Color::from_env).unwrap_or_default(),
         )
     }
 }
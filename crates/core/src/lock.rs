@@ -63,14 +63,48 @@ impl LockedPackageVersion {
     }
 }
 
+/// Represents a locked local path dependency in a lock file.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LockedPath {
+    /// The id of the dependency.
+    pub id: PackageId,
+    /// The path of the dependency.
+    ///
+    /// The path is relative to the directory of the lock file when the
+    /// dependency is within that directory.
+    pub path: String,
+    /// The digest of the contents at the path.
+    pub digest: AnyHash,
+}
+
+impl LockedPath {
+    /// Gets the key used in sorting and searching the path list.
+    pub fn key(&self) -> (&PackageId, &str) {
+        (&self.id, &self.path)
+    }
+}
+
 /// Represents a resolver for a lock file.
 #[derive(Clone, Copy, Debug)]
-pub struct LockFileResolver<'a>(&'a LockFile);
+pub struct LockFileResolver<'a> {
+    lock_file: &'a LockFile,
+    fallback: Option<&'a LockFile>,
+}
 
 impl<'a> LockFileResolver<'a> {
     /// Creates a new lock file resolver for the given workspace and lock file.
     pub fn new(lock_file: &'a LockFile) -> Self {
-        Self(lock_file)
+        Self {
+            lock_file,
+            fallback: None,
+        }
+    }
+
+    /// Sets a lock file, such as one of another project, to resolve packages
+    /// from when they are not in the lock file.
+    pub fn with_fallback(mut self, fallback: &'a LockFile) -> Self {
+        self.fallback = Some(fallback);
+        self
     }
 
     /// Resolves a package from the lock file.
@@ -84,20 +118,22 @@ impl<'a> LockFileResolver<'a> {
         id: &PackageId,
         requirement: &VersionReq,
     ) -> Result<Option<&'a LockedPackageVersion>> {
-        if let Some(pkg) = self
-            .0
-            .packages
-            .binary_search_by_key(&(id, registry), LockedPackage::key)
-            .ok()
-            .map(|i| &self.0.packages[i])
-        {
-            if let Ok(index) = pkg
-                .versions
-                .binary_search_by_key(&requirement.to_string().as_str(), LockedPackageVersion::key)
+        // A package may be locked as either a WIT or a component dependency
+        let lock_files = std::iter::once(self.lock_file).chain(self.fallback);
+        for packages in lock_files.flat_map(|l| [&l.packages, &l.components]) {
+            if let Some(pkg) = packages
+                .binary_search_by_key(&(id, registry), LockedPackage::key)
+                .ok()
+                .map(|i| &packages[i])
             {
-                let locked = &pkg.versions[index];
-                log::info!("dependency package `{id}` from registry `{registry}` with requirement `{requirement}` was resolved by the lock file to version {version}", version = locked.version);
-                return Ok(Some(locked));
+                if let Ok(index) = pkg.versions.binary_search_by_key(
+                    &requirement.to_string().as_str(),
+                    LockedPackageVersion::key,
+                ) {
+                    let locked = &pkg.versions[index];
+                    log::info!("dependency package `{id}` from registry `{registry}` with requirement `{requirement}` was resolved by the lock file to version {version}", version = locked.version);
+                    return Ok(Some(locked));
+                }
             }
         }
 
@@ -117,11 +153,21 @@ pub struct LockFile {
     ///
     /// Currently this is always `1`.
     pub version: i64,
-    /// The locked dependencies in the lock file.
+    /// The locked WIT dependencies in the lock file.
     ///
     /// This list is sorted by the key of the locked package.
     #[serde(rename = "package", default, skip_serializing_if = "Vec::is_empty")]
     pub packages: Vec<LockedPackage>,
+    /// The locked component dependencies in the lock file.
+    ///
+    /// This list is sorted by the key of the locked package.
+    #[serde(rename = "component", default, skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<LockedPackage>,
+    /// The locked local path dependencies in the lock file.
+    ///
+    /// This list is sorted by the key of the locked path.
+    #[serde(rename = "path", default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<LockedPath>,
 }
 
 impl LockFile {
@@ -132,9 +178,40 @@ impl LockFile {
         Self {
             version: LOCK_FILE_VERSION,
             packages: packages.into(),
+            components: Vec::new(),
+            paths: Vec::new(),
         }
     }
 
+    /// Sets the locked component dependencies of the lock file.
+    ///
+    /// It is expected that the packages will be already sorted.
+    pub fn with_components(mut self, components: impl Into<Vec<LockedPackage>>) -> Self {
+        self.components = components.into();
+        self
+    }
+
+    /// Sets the locked local path dependencies of the lock file.
+    ///
+    /// It is expected that the paths will be already sorted.
+    pub fn with_paths(mut self, paths: impl Into<Vec<LockedPath>>) -> Self {
+        self.paths = paths.into();
+        self
+    }
+
+    /// Determines if the lock file has no locked dependencies.
+    pub fn is_empty(&self) -> bool {
+        self.packages.is_empty() && self.components.is_empty() && self.paths.is_empty()
+    }
+
+    /// Finds the locked path dependency with the given id and path.
+    pub fn find_path(&self, id: &PackageId, path: &str) -> Option<&LockedPath> {
+        self.paths
+            .binary_search_by_key(&(id, path), LockedPath::key)
+            .ok()
+            .map(|i| &self.paths[i])
+    }
+
     /// Reads the lock file from the given file object.
     pub fn read(mut file: &File) -> Result<Self> {
         let mut contents = String::new();
@@ -179,6 +256,8 @@ impl Default for LockFile {
         Self {
             version: LOCK_FILE_VERSION,
             packages: Vec::new(),
+            components: Vec::new(),
+            paths: Vec::new(),
         }
     }
 }
@@ -432,7 +511,7 @@ mod sys {
     }
 
     pub(super) fn error_contended(err: &Error) -> bool {
-        err.raw_os_error().map_or(false, |x| x == libc::EWOULDBLOCK)
+        err.raw_os_error() == Some(libc::EWOULDBLOCK)
     }
 
     pub(super) fn error_unsupported(err: &Error) -> bool {
@@ -328,10 +328,7 @@ impl Format {
             ProgressStyle::Indeterminate => String::new(),
         };
         let extra_len = stats.len() + 2 /* [ and ] */ + 15 /* status header */;
-        let display_width = match self.width().checked_sub(extra_len) {
-            Some(n) => n,
-            None => return None,
-        };
+        let display_width = self.width().checked_sub(extra_len)?;
 
         let mut string = String::with_capacity(self.max_width);
         string.push('[');
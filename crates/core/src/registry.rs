@@ -76,7 +76,7 @@ pub enum Dependency {
     Package(RegistryPackage),
 
     /// The dependency is a path to a local directory or file.
-    Local(PathBuf),
+    Local(LocalPackage),
 }
 
 impl Serialize for Dependency {
@@ -105,13 +105,19 @@ impl Serialize for Dependency {
                     .serialize(serializer)
                 }
             }
-            Self::Local(path) => {
+            Self::Local(local) => {
                 #[derive(Serialize)]
                 struct Entry<'a> {
                     path: &'a PathBuf,
+                    #[serde(skip_serializing_if = "Option::is_none")]
+                    package: Option<&'a PackageId>,
                 }
 
-                Entry { path }.serialize(serializer)
+                Entry {
+                    path: &local.path,
+                    package: local.package.as_ref(),
+                }
+                .serialize(serializer)
             }
         }
     }
@@ -154,7 +160,9 @@ impl<'de> Deserialize<'de> for Dependency {
                 let entry = Entry::deserialize(MapAccessDeserializer::new(map))?;
 
                 match (entry.path, entry.package, entry.version, entry.registry) {
-                    (Some(path), None, None, None) => Ok(Self::Value::Local(path)),
+                    (Some(path), package, None, None) => {
+                        Ok(Self::Value::Local(LocalPackage { path, package }))
+                    }
                     (None, id, Some(version), registry) => {
                         Ok(Self::Value::Package(RegistryPackage {
                             id,
@@ -162,15 +170,12 @@ impl<'de> Deserialize<'de> for Dependency {
                             registry,
                         }))
                     }
-                    (Some(_), None, Some(_), _) => Err(de::Error::custom(
+                    (Some(_), _, Some(_), _) => Err(de::Error::custom(
                         "cannot specify both `path` and `version` fields in a dependency entry",
                     )),
-                    (Some(_), None, None, Some(_)) => Err(de::Error::custom(
+                    (Some(_), _, None, Some(_)) => Err(de::Error::custom(
                         "cannot specify both `path` and `registry` fields in a dependency entry",
                     )),
-                    (Some(_), Some(_), _, _) => Err(de::Error::custom(
-                        "cannot specify both `path` and `package` fields in a dependency entry",
-                    )),
                     (None, None, _, _) => Err(de::Error::missing_field("package")),
                     (None, Some(_), None, _) => Err(de::Error::missing_field("version")),
                 }
@@ -219,6 +224,27 @@ impl FromStr for RegistryPackage {
     }
 }
 
+/// Represents a reference to a local WIT package or component.
+#[derive(Debug, Clone)]
+pub struct LocalPackage {
+    /// The path to the local directory or file.
+    pub path: PathBuf,
+
+    /// The name of the package defined at the path.
+    ///
+    /// If specified, the package is renamed to the id of the dependency.
+    pub package: Option<PackageId>,
+}
+
+impl From<PathBuf> for LocalPackage {
+    fn from(path: PathBuf) -> Self {
+        Self {
+            path,
+            package: None,
+        }
+    }
+}
+
 /// Represents information about a resolution of a registry package.
 #[derive(Clone, Debug)]
 pub struct RegistryResolution {
@@ -249,6 +275,8 @@ pub struct LocalResolution {
     pub id: PackageId,
     /// The path to the resolved dependency.
     pub path: PathBuf,
+    /// The name of the package defined at the path, if it differs from the id.
+    pub package: Option<PackageId>,
 }
 
 /// Represents a resolution of a dependency.
@@ -289,7 +317,24 @@ impl DependencyResolution {
     }
 
     /// Decodes the resolved dependency.
-    pub fn decode(&self) -> Result<DecodedDependency> {
+    ///
+    /// If the dependency is a local package with a `package` override, the
+    /// decoded package is renamed to the id of the dependency.
+    pub fn decode(&self) -> Result<DecodedDependency<'_>> {
+        let mut decoded = self.decode_contents()?;
+        if let Self::Local(LocalResolution {
+            id,
+            package: Some(package),
+            ..
+        }) = self
+        {
+            decoded.rename(package, id)?;
+        }
+
+        Ok(decoded)
+    }
+
+    fn decode_contents(&self) -> Result<DecodedDependency<'_>> {
         // If the dependency path is a directory, assume it contains wit to parse as a package.
         if self.path().is_dir() {
             return Ok(DecodedDependency::Wit {
@@ -340,6 +385,7 @@ impl DependencyResolution {
 }
 
 /// Represents a decoded dependency.
+#[allow(clippy::large_enum_variant)]
 pub enum DecodedDependency<'a> {
     /// The dependency decoded from an unresolved WIT package.
     Wit {
@@ -388,6 +434,42 @@ impl<'a> DecodedDependency<'a> {
         }
     }
 
+    /// Renames the decoded package to the given dependency id.
+    ///
+    /// Returns an error if the decoded package is not named `expected`.
+    fn rename(&mut self, expected: &PackageId, id: &PackageId) -> Result<()> {
+        let name = self.package_name();
+        if name.namespace != expected.namespace() || name.name != expected.name() {
+            bail!(
+                "expected dependency `{id}` to define package `{expected}`, but it defines package `{name}`"
+            );
+        }
+
+        let new_name = PackageName {
+            namespace: id.namespace().to_string(),
+            name: id.name().to_string(),
+            version: name.version.clone(),
+        };
+
+        match self {
+            Self::Wit { package, .. } => package.name = new_name,
+            Self::Wasm { decoded, .. } => {
+                let pkg = decoded.package();
+                let resolve = match decoded {
+                    DecodedWasm::WitPackage(resolve, _) | DecodedWasm::Component(resolve, _) => {
+                        resolve
+                    }
+                };
+
+                let old_name = std::mem::replace(&mut resolve.packages[pkg].name, new_name.clone());
+                resolve.package_names.remove(&old_name);
+                resolve.package_names.insert(new_name, pkg);
+            }
+        }
+
+        Ok(())
+    }
+
     /// Converts the decoded dependency into a component world.
     ///
     /// Returns an error if the dependency is not a decoded component.
@@ -402,6 +484,13 @@ impl<'a> DecodedDependency<'a> {
     }
 }
 
+/// Determines if the path of a local dependency ends in a glob pattern.
+fn is_glob(path: &Path) -> bool {
+    path.file_name()
+        .map(|name| name.to_string_lossy().contains(['*', '?', '[']))
+        .unwrap_or(false)
+}
+
 /// Used to resolve dependencies for a WIT package.
 pub struct DependencyResolver<'a> {
     terminal: &'a Terminal,
@@ -410,6 +499,7 @@ pub struct DependencyResolver<'a> {
     lock_file: Option<LockFileResolver<'a>>,
     registries: IndexMap<&'a str, Registry<'a>>,
     resolutions: HashMap<PackageId, DependencyResolution>,
+    glob_matches: HashMap<PackageId, (&'a PackageId, PathBuf)>,
     network_allowed: bool,
 }
 
@@ -429,6 +519,7 @@ impl<'a> DependencyResolver<'a> {
             lock_file,
             registries: Default::default(),
             resolutions: Default::default(),
+            glob_matches: Default::default(),
             network_allowed,
         })
     }
@@ -477,21 +568,139 @@ impl<'a> DependencyResolver<'a> {
                     .add_dependency(id, package_id, &package.version, registry_name, locked)
                     .await?;
             }
+            Dependency::Local(p) if is_glob(&p.path) => {
+                self.add_glob_dependency(id, p)?;
+            }
             Dependency::Local(p) => {
                 // A local path dependency, insert a resolution immediately
                 let res = DependencyResolution::Local(LocalResolution {
                     id: id.clone(),
-                    path: p.clone(),
+                    path: p.path.clone(),
+                    package: p.package.clone(),
                 });
 
+                // An explicit dependency takes precedence over a glob match
                 let prev = self.resolutions.insert(id.clone(), res);
-                assert!(prev.is_none());
+                assert!(prev.is_none() || self.glob_matches.contains_key(id));
             }
         }
 
         Ok(())
     }
 
+    /// Adds a local dependency whose path ends in a glob pattern.
+    ///
+    /// Each matching directory, `.wit` file, or `.wasm` file is resolved as a
+    /// dependency named after the WIT package it defines.
+    fn add_glob_dependency(&mut self, id: &'a PackageId, local: &LocalPackage) -> Result<()> {
+        if local.package.is_some() {
+            bail!("dependency `{id}` cannot specify a `package` name for a glob path");
+        }
+
+        // Only the last component may be a pattern; escape the rest so that
+        // the directories containing it are matched literally
+        let pattern = match (local.path.parent(), local.path.file_name()) {
+            (Some(dir), Some(pattern)) => Path::new(&glob::Pattern::escape(&dir.to_string_lossy()))
+                .join(pattern)
+                .to_string_lossy()
+                .into_owned(),
+            _ => bail!(
+                "invalid glob path `{path}` for dependency `{id}`",
+                path = local.path.display()
+            ),
+        };
+
+        let paths = glob::glob_with(
+            &pattern,
+            glob::MatchOptions {
+                require_literal_leading_dot: true,
+                ..Default::default()
+            },
+        )
+        .with_context(|| {
+            format!(
+                "invalid glob path `{path}` for dependency `{id}`",
+                path = local.path.display()
+            )
+        })?;
+
+        let mut matched = false;
+        for path in paths {
+            let path = path.with_context(|| {
+                format!("failed to read a path matched by the glob path of dependency `{id}`")
+            })?;
+
+            if !path.is_dir()
+                && !matches!(
+                    path.extension().and_then(|e| e.to_str()),
+                    Some("wit") | Some("wasm")
+                )
+            {
+                log::debug!(
+                    "skipping `{path}` matched by the glob path of dependency `{id}` as it is not a WIT package",
+                    path = path.display()
+                );
+                continue;
+            }
+
+            let name = DependencyResolution::Local(LocalResolution {
+                id: id.clone(),
+                path: path.clone(),
+                package: None,
+            })
+            .decode()?
+            .package_name()
+            .clone();
+
+            let package: PackageId = format!("{ns}:{name}", ns = name.namespace, name = name.name)
+                .parse()
+                .with_context(|| format!("invalid package name `{name}`"))?;
+
+            if let Some((other, prev)) = self.glob_matches.get(&package) {
+                bail!(
+                    "package `{package}` is defined by both `{prev}` (matched by dependency `{other}`) and `{path}` (matched by dependency `{id}`)",
+                    prev = prev.display(),
+                    path = path.display()
+                );
+            }
+
+            matched = true;
+            self.glob_matches
+                .insert(package.clone(), (id, path.clone()));
+
+            // An explicit dependency takes precedence over a glob match
+            if self.resolutions.contains_key(&package) {
+                log::debug!(
+                    "skipping package `{package}` matched by the glob path of dependency `{id}` as it is an explicit dependency"
+                );
+                continue;
+            }
+
+            log::info!(
+                "dependency `{id}` matched package `{package}` at `{path}`",
+                path = path.display()
+            );
+
+            self.resolutions.insert(
+                package.clone(),
+                DependencyResolution::Local(LocalResolution {
+                    id: package,
+                    path,
+                    package: None,
+                }),
+            );
+        }
+
+        if !matched {
+            self.terminal.warn(format!(
+                "glob path `{path}` of dependency `{id}` did not match any WIT packages",
+                path = local.path.display()
+            ))?;
+        }
+
+        Ok(())
+    }
+
     /// Resolve all dependencies.
     ///
     /// This will download all dependencies that are not already present in client storage.
@@ -501,6 +710,7 @@ impl<'a> DependencyResolver<'a> {
         let Self {
             mut registries,
             mut resolutions,
+            glob_matches,
             terminal,
             network_allowed,
             ..
@@ -514,8 +724,10 @@ impl<'a> DependencyResolver<'a> {
         for resolution in
             Self::download_and_resolve(registries, downloads, terminal, network_allowed).await?
         {
-            let prev = resolutions.insert(resolution.id().clone(), resolution);
-            assert!(prev.is_none());
+            // An explicit dependency takes precedence over a glob match
+            let id = resolution.id().clone();
+            let prev = resolutions.insert(id.clone(), resolution);
+            assert!(prev.is_none() || glob_matches.contains_key(&id));
         }
 
         Ok(resolutions)
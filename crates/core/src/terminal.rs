@@ -39,6 +39,17 @@ impl FromStr for Color {
     }
 }
 
+impl Color {
+    /// Gets the color preference from the `CARGO_TERM_COLOR` environment variable.
+    ///
+    /// Returns `None` if the variable is not set or is not a valid color option.
+    pub fn from_env() -> Option<Self> {
+        std::env::var("CARGO_TERM_COLOR")
+            .ok()
+            .and_then(|v| v.parse().ok())
+    }
+}
+
 impl fmt::Display for Color {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -207,6 +218,31 @@ impl Terminal {
         state.output.print(status, Some(&message), false)
     }
 
+    /// Prints a red 'error' message prefixed with a source location.
+    ///
+    /// This matches the `file: error: message` output of cargo's short message format.
+    pub fn error_at<T: fmt::Display, U: fmt::Display>(
+        &self,
+        location: T,
+        message: U,
+    ) -> Result<()> {
+        let status = "error";
+        let status_red = status.red();
+
+        let status = if self.0.borrow().output.supports_color() {
+            &status_red as &dyn fmt::Display
+        } else {
+            &status
+        };
+
+        let status = format!("{location}: {status}");
+
+        // This doesn't call print as errors are always printed even when quiet
+        let mut state = self.0.borrow_mut();
+        state.clear_stderr();
+        state.output.print(&status, Some(&message), false)
+    }
+
     /// Write a styled fragment to stdout.
     ///
     /// Caller is responsible for deciding whether [`Shell::verbosity`] is affects output.
@@ -266,7 +302,13 @@ impl Output {
         match self {
             Output::Write(_) => false,
             Output::Stream { is_terminal, color } => match color {
-                Color::Auto => *is_terminal,
+                // Respect `NO_COLOR` (see https://no-color.org/) when not forced
+                Color::Auto => {
+                    *is_terminal
+                        && !std::env::var_os("NO_COLOR")
+                            .map(|v| !v.is_empty())
+                            .unwrap_or(false)
+                }
                 Color::Never => false,
                 Color::Always => true,
             },
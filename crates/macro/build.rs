@@ -13,7 +13,7 @@ pub(crate) fn find_target_dir(out_dir: impl AsRef<Path>) -> Option<PathBuf> {
             || dir.file_name() == Some(OsStr::new("target"))
                 && dir
                     .parent()
-                    .map_or(false, |parent| parent.join("Cargo.toml").exists())
+                    .is_some_and(|parent| parent.join("Cargo.toml").exists())
         {
             return Some(dir);
         }
@@ -19,11 +19,14 @@ use syn::{
     token, Error, Result, Token,
 };
 use wit_bindgen_core::{
-    wit_parser::{Resolve, TypeDefKind, WorldId, WorldItem, WorldKey},
+    wit_parser::{
+        Function, FunctionKind, InterfaceId, Resolve, Type, TypeDefKind, TypeId, TypeOwner,
+        WorldId, WorldItem, WorldKey,
+    },
     Files,
 };
 use wit_bindgen_rust::{ExportKey, Opts};
-use wit_bindgen_rust_lib::Ownership;
+use wit_bindgen_rust_lib::{to_rust_ident, Ownership};
 use wit_component::DecodedWasm;
 
 fn implementor_path_str(path: &syn::Path) -> String {
@@ -54,13 +57,133 @@ fn implementor_path_str(path: &syn::Path) -> String {
 /// on a type named `File` in the same scope as the `generate!`
 /// macro invocation.
 ///
+/// # Empty results
+///
+/// Functions with empty results map to Rust signatures as follows:
+///
+/// - `func()` maps to a function returning `()`.
+/// - `func() -> result` maps to a function returning `Result<(), ()>`.
+/// - `func() -> result<t>` maps to a function returning `Result<T, ()>`.
+/// - `func() -> result<_, e>` maps to a function returning `Result<(), E>`.
+///
+/// The same mapping is used for the exports generated by `cargo component new`.
+///
+/// # Module layout
+///
+/// The bindings are generated in a `bindings` module, or in the module named
+/// by the `path` option:
+///
+/// - `bindings::imports` re-exports the bindings of every world import:
+///   interfaces (e.g. `bindings::imports::my::pkg::iface`), functions, and
+///   types.
+/// - `bindings::exports` contains the traits to implement for exported
+///   interfaces (e.g. `bindings::exports::my::pkg::iface::Guest`).
+/// - `bindings::Guest` is the trait to implement for functions exported
+///   directly from the world.
+///
+/// Imports are also available directly from the `bindings` module.
+///
+/// # Resources
+///
+/// Exported resources are implemented with a `Guest<Resource>` trait in the
+/// module of the exporting interface. The trait is documented with the WIT
+/// resource it implements (e.g. `my:pkg/iface/res`) and lists the method
+/// for each function of the resource, and each method of the trait is
+/// documented with the WIT function it implements (e.g. `[method]res.get`).
+/// An implementation rejected by rustc for a missing method or an
+/// incompatible signature can then be compared against the WIT definition.
+///
+/// The keys of the `resources` option name exported resources by the key of
+/// their interface in the world followed by the resource name (e.g.
+/// `my:pkg/iface/res`, or `iface/res` for an interface defined inline in the
+/// world). Naming a resource the world does not export is an error that
+/// lists the exported resources, as is specifying an `implementor` for a
+/// world that exports no functions or interfaces.
+///
+/// # Shared types
+///
+/// A type `use`d by multiple interfaces is generated once, in the module of
+/// the interface that defines it; the other interfaces refer to it with type
+/// aliases, so values of the type can be passed between interfaces.
+///
+/// # Ownership
+///
+/// By default (the `owning` model), the types of parameters to imported
+/// functions own their data, so a guest passing a string or list it already
+/// has must clone it into the argument.
+///
+/// With the `borrowing` model, types used as parameters to imported functions
+/// carry a lifetime parameter and borrow their data instead (e.g. a record
+/// with a `string` field is generated as `Entry<'a>` with a `&'a str` field),
+/// as the data only needs to be valid for the duration of the call. Types that
+/// are also returned from imports or used by exports remain owned.
+///
+/// The `borrowing-duplicate-if-necessary` model instead generates both a
+/// borrowing `...Param<'a>` type and an owned `...Result` type for types
+/// used in both positions.
+///
+/// # Filtering interfaces
+///
+/// The `include` and `exclude` options name interfaces by their key in the
+/// world: `<namespace>:<package>/<interface>` (the namespace may be omitted)
+/// for interfaces of a package, or the name of an interface defined inline
+/// in the world. Interfaces whose types are used by a retained interface are
+/// kept as well. Naming an interface the world does not import or export is
+/// an error.
+///
+/// Only the bindings are filtered: the component built from them imports and
+/// exports only the retained interfaces, so a component that leaves out an
+/// export of its target world no longer implements the whole world.
+///
 /// # Options
 ///
 /// The macro accepts the following options:
 ///
 /// - `implementor`: The name of the type to implement world exports on.
 /// - `resources`: A map of resource names to resource implementor types.
-/// - `ownership`: The ownership model to use for resources.
+/// - `ownership`: The ownership model of the types of imported function
+///   parameters: `owning` (the default), `borrowing`, or
+///   `borrowing-duplicate-if-necessary`.
+/// - `thread_safe_resources`: Whether imported resource handle types are
+///   `Send` and `Sync` when the `atomics` target feature is enabled, so that
+///   they can be shared between threads. Without the option, or without the
+///   target feature, the handle types are neither `Send` nor `Sync`.
+/// - `raw_strings`: Whether to represent strings as `Vec<u8>` instead of
+///   `String`, skipping UTF-8 validation of strings received from the host.
+/// - `allow`: A list of lints to allow in the generated bindings module.
+/// - `non_exhaustive`: Whether to mark the Rust enums generated for WIT
+///   `enum` and `variant` types as `#[non_exhaustive]`.
+/// - `additional_derives`: A list of paths of derive macros to apply to the
+///   types generated for WIT `record`, `variant`, and `enum` types, in
+///   addition to the ones the bindings derive themselves.
+/// - `include`: A list of the imported and exported interfaces of the world
+///   to generate bindings for; other interfaces are left out of the bindings.
+/// - `exclude`: A list of the imported and exported interfaces of the world
+///   to leave out of the bindings.
+/// - `path`: The name of the module to generate the bindings in, instead of
+///   `bindings` (e.g. `path: my_wit` for `my_wit::Guest` and
+///   `my_wit::exports::...`). The name must be a valid Rust identifier that is
+///   not a keyword.
+/// - `async`: Whether to generate `async` trait methods for the exported
+///   functions the world marks as async. The WIT syntax supported by this
+///   version of the bindings has no async functions, so this option
+///   currently has no effect.
+///
+/// # Manifest defaults
+///
+/// The `implementor`, `ownership`, `thread_safe_resources`, `raw_strings`,
+/// `allow`, `non_exhaustive`, and `additional_derives` options may be given
+/// defaults for every invocation of the macro in a package with the
+/// `package.metadata.component.bindings` table of `Cargo.toml`, using
+/// kebab-case keys:
+///
+/// ```toml
+/// [package.metadata.component.bindings]
+/// ownership = "borrowing"
+/// additional-derives = ["serde::Serialize"]
+/// ```
+///
+/// An option given to the macro overrides the default from the manifest.
 ///
 /// # Examples
 ///
@@ -78,6 +201,14 @@ fn implementor_path_str(path: &syn::Path) -> String {
 /// })
 /// ```
 ///
+/// Generating the bindings in a module named `my_wit`:
+///
+/// ```ignore
+/// cargo_component_bindings::generate!({
+///     path: my_wit,
+/// })
+/// ```
+///
 /// Specifying a custom resource implementor type named `MyResource`:
 ///
 /// ```ignore
@@ -88,13 +219,62 @@ fn implementor_path_str(path: &syn::Path) -> String {
 /// })
 /// ```
 ///
-/// Specifying the `borrowing-duplicate-if-necessary` ownership model
-/// for resources:
+/// Specifying the `borrowing-duplicate-if-necessary` ownership model:
 ///
 /// ```ignore
 /// cargo_component_bindings::generate!({
 ///      ownership: "borrowing-duplicate-if-necessary"
 /// })
+/// ```
+///
+/// Making imported resource handles `Send` and `Sync` for threaded
+/// targets:
+///
+/// ```ignore
+/// cargo_component_bindings::generate!({
+///      thread_safe_resources: true
+/// })
+/// ```
+///
+/// Using raw byte strings to skip UTF-8 validation:
+///
+/// ```ignore
+/// cargo_component_bindings::generate!({
+///      raw_strings: true
+/// })
+/// ```
+///
+/// Allowing lints that fire on the generated bindings:
+///
+/// ```ignore
+/// cargo_component_bindings::generate!({
+///      allow: ["clippy::all", "dead_code"]
+/// })
+/// ```
+///
+/// Marking generated enums as `#[non_exhaustive]`:
+///
+/// ```ignore
+/// cargo_component_bindings::generate!({
+///      non_exhaustive: true
+/// })
+/// ```
+///
+/// Deriving additional traits for generated types:
+///
+/// ```ignore
+/// cargo_component_bindings::generate!({
+///      additional_derives: [serde::Serialize, serde::Deserialize]
+/// })
+/// ```
+///
+/// Generating bindings for only some interfaces of a large world:
+///
+/// ```ignore
+/// cargo_component_bindings::generate!({
+///      include: ["my:package/keyed-integer"]
+/// })
+/// ```
 #[proc_macro]
 pub fn generate(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     syn::parse_macro_input!(input as Config)
@@ -103,6 +283,364 @@ pub fn generate(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
         .into()
 }
 
+/// Creates the `imports` module that re-exports the bindings of the
+/// world's imports.
+fn imports_module(resolve: &Resolve, world: WorldId) -> TokenStream {
+    let mut names = Vec::new();
+    for (key, item) in &resolve.worlds[world].imports {
+        // Interfaces with an id are nested in the module of their namespace
+        let name = match (key, item) {
+            (WorldKey::Interface(id), _) => {
+                let interface = &resolve.interfaces[*id];
+                let package =
+                    &resolve.packages[interface.package.expect("interface must have a package")];
+                to_rust_ident(&package.name.namespace)
+            }
+            (WorldKey::Name(name), WorldItem::Type(_)) => name.to_upper_camel_case(),
+            (WorldKey::Name(name), _) => to_rust_ident(name),
+        };
+
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    // An import named `imports` would conflict with the module
+    if names.iter().any(|name| name == "imports") {
+        return TokenStream::new();
+    }
+
+    let names = names
+        .iter()
+        .map(|name| syn::parse_str::<syn::Ident>(name).expect("import name should parse"));
+
+    quote! {
+        pub mod imports {
+            #(
+                #[allow(unused_imports)]
+                pub use super::#names;
+            )*
+        }
+    }
+}
+
+/// Marks the enums of the given items, including those in nested
+/// modules, as `#[non_exhaustive]`.
+fn mark_non_exhaustive(items: &mut [syn::Item]) {
+    for item in items {
+        match item {
+            syn::Item::Enum(item) => item.attrs.push(parse_quote!(#[non_exhaustive])),
+            syn::Item::Mod(syn::ItemMod {
+                content: Some((_, items)),
+                ..
+            }) => mark_non_exhaustive(items),
+            _ => continue,
+        }
+    }
+}
+
+/// Adds the given derives to the generated types that derive `Clone`.
+///
+/// The generated bindings derive `Clone` for the types of WIT records,
+/// variants, and enums, but not for resources, whose handles cannot be
+/// duplicated.
+fn add_derives(items: &mut [syn::Item], derives: &[syn::Path]) {
+    fn derives_clone(attrs: &[syn::Attribute]) -> bool {
+        attrs
+            .iter()
+            .filter(|a| a.path().is_ident("derive"))
+            .any(|a| {
+                a.parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+                    .map(|paths| paths.iter().any(|p| p.is_ident("Clone")))
+                    .unwrap_or(false)
+            })
+    }
+
+    for item in items {
+        let attrs = match item {
+            syn::Item::Struct(item) => &mut item.attrs,
+            syn::Item::Enum(item) => &mut item.attrs,
+            syn::Item::Mod(syn::ItemMod {
+                content: Some((_, items)),
+                ..
+            }) => {
+                add_derives(items, derives);
+                continue;
+            }
+            _ => continue,
+        };
+
+        if derives_clone(attrs) {
+            attrs.push(parse_quote!(#[derive(#(#derives),*)]));
+        }
+    }
+}
+
+/// Adds a marker field to the handle types of imported resources so that
+/// they are neither `Send` nor `Sync`.
+///
+/// With `thread_safe` set, the marker is only present when the `atomics`
+/// target feature is disabled, leaving the handles `Send` and `Sync` for
+/// targets with threads.
+fn mark_thread_affine_resources(items: &mut [syn::Item], thread_safe: bool) {
+    // Imported resources are the only generated structs wrapping a
+    // `Resource<T>` handle; exported resources are generated as traits
+    fn is_imported_resource(item: &syn::ItemStruct) -> bool {
+        match &item.fields {
+            syn::Fields::Named(fields) => fields.named.iter().any(|f| {
+                f.ident.as_ref().is_some_and(|i| i == "handle")
+                    && matches!(&f.ty, syn::Type::Path(ty)
+                        if ty.path.segments.last().is_some_and(|s| s.ident == "Resource"))
+            }),
+            _ => false,
+        }
+    }
+
+    let cfg = thread_safe.then(|| quote!(#[cfg(not(target_feature = "atomics"))]));
+    let mut resources = Vec::new();
+    for item in items.iter_mut() {
+        match item {
+            syn::Item::Struct(item) if is_imported_resource(item) => {
+                if let syn::Fields::Named(fields) = &mut item.fields {
+                    fields.named.push(parse_quote! {
+                        #cfg
+                        _thread_affine: ::core::marker::PhantomData<*const ()>
+                    });
+                }
+                resources.push(item.ident.clone());
+            }
+            syn::Item::Mod(syn::ItemMod {
+                content: Some((_, items)),
+                ..
+            }) => mark_thread_affine_resources(items, thread_safe),
+            _ => continue,
+        }
+    }
+
+    // Initialize the marker where the handle types are constructed
+    for item in items {
+        let item = match item {
+            syn::Item::Impl(item) if item.trait_.is_none() => item,
+            _ => continue,
+        };
+
+        match &*item.self_ty {
+            syn::Type::Path(ty) if resources.iter().any(|r| ty.path.is_ident(r)) => {}
+            _ => continue,
+        }
+
+        for item in &mut item.items {
+            let body = match item {
+                syn::ImplItem::Fn(f) if f.sig.ident == "from_handle" => &mut f.block,
+                _ => continue,
+            };
+
+            if let Some(syn::Stmt::Expr(syn::Expr::Struct(expr), None)) = body.stmts.last_mut() {
+                expr.fields.push(parse_quote! {
+                    #cfg
+                    _thread_affine: ::core::marker::PhantomData
+                });
+            }
+        }
+    }
+}
+
+/// Documents the traits of exported resources with the WIT resource they
+/// implement, and their methods with the WIT function that each one
+/// implements.
+///
+/// The generated code cannot check the implementations; a missing method or
+/// a mismatched signature is reported by rustc against the trait, whose
+/// documentation then names the WIT resource and functions to compare with.
+fn document_resource_methods(resolve: &Resolve, world: WorldId, items: &mut [syn::Item]) {
+    fn method_name(func: &Function) -> Option<(TypeId, String)> {
+        match func.kind {
+            FunctionKind::Constructor(ty) => Some((ty, "new".to_string())),
+            FunctionKind::Method(ty) | FunctionKind::Static(ty) => Some((
+                ty,
+                to_rust_ident(func.name.rsplit('.').next().unwrap_or(&func.name)),
+            )),
+            FunctionKind::Freestanding => None,
+        }
+    }
+
+    fn add_docs(attrs: &mut Vec<syn::Attribute>, docs: &[String]) {
+        if attrs.iter().any(|a| a.path().is_ident("doc")) {
+            attrs.push(parse_quote!(#[doc = ""]));
+        }
+
+        for doc in docs {
+            attrs.push(parse_quote!(#[doc = #doc]));
+        }
+    }
+
+    for (key, item) in &resolve.worlds[world].exports {
+        let id = match item {
+            WorldItem::Interface(id) => *id,
+            _ => continue,
+        };
+
+        let interface = &resolve.interfaces[id];
+        let (module, name, prefix) = match key {
+            WorldKey::Name(name) => (vec![to_rust_ident(name)], format!("`{name}`"), name.clone()),
+            WorldKey::Interface(_) => {
+                let package =
+                    &resolve.packages[interface.package.expect("interface must have a package")];
+                let iface = interface.name.as_ref().expect("interface must have a name");
+                (
+                    vec![
+                        to_rust_ident(&package.name.namespace),
+                        to_rust_ident(&package.name.name),
+                        to_rust_ident(iface),
+                    ],
+                    format!("`{}`", package.name.interface_id(iface)),
+                    format!(
+                        "{ns}:{pkg}/{iface}",
+                        ns = package.name.namespace,
+                        pkg = package.name.name
+                    ),
+                )
+            }
+        };
+
+        let items = match module_items(items, "exports").and_then(|items| {
+            module
+                .iter()
+                .try_fold(items, |items, m| module_items(items, m))
+        }) {
+            Some(items) => items,
+            None => continue,
+        };
+
+        for (resource, ty) in &interface.types {
+            if !matches!(resolve.types[*ty].kind, TypeDefKind::Resource) {
+                continue;
+            }
+
+            let key = format!("{prefix}/{resource}");
+            let implementor = resource.to_upper_camel_case();
+            let mut docs = vec![
+                format!(" Implements the WIT resource `{key}`."),
+                String::new(),
+                format!(
+                    " The implementing type is given for `{key}` in the `resources` option of \
+                     `generate!` and defaults to `{implementor}`. It must implement a method \
+                     for each function of the resource:"
+                ),
+                String::new(),
+            ];
+            docs.extend(interface.functions.values().filter_map(|func| {
+                let (owner, method) = method_name(func)?;
+                (owner == *ty).then(|| format!(" - `{method}`: `{func}`", func = func.name))
+            }));
+
+            for item in items.iter_mut() {
+                match item {
+                    syn::Item::Trait(item) if item.ident == format!("Guest{implementor}") => {
+                        add_docs(&mut item.attrs, &docs)
+                    }
+                    _ => continue,
+                }
+            }
+        }
+
+        for func in interface.functions.values() {
+            let (resource, method) = match method_name(func) {
+                Some(name) => name,
+                None => continue,
+            };
+
+            let resource = resolve.types[resource]
+                .name
+                .as_deref()
+                .expect("resource must have a name");
+            let doc = format!(
+                " Implements the `{func}` function of resource `{resource}` exported by interface {name}.",
+                func = func.name,
+            );
+
+            let traits = items.iter_mut().filter_map(|item| match item {
+                syn::Item::Trait(item)
+                    if item.ident == format!("Guest{}", resource.to_upper_camel_case()) =>
+                {
+                    Some(item)
+                }
+                _ => None,
+            });
+
+            for item in traits {
+                for item in &mut item.items {
+                    match item {
+                        syn::TraitItem::Fn(f) if f.sig.ident == method => {
+                            add_docs(&mut f.attrs, std::slice::from_ref(&doc))
+                        }
+                        _ => continue,
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Gets the items of the inline module with the given name.
+fn module_items<'a>(items: &'a mut [syn::Item], name: &str) -> Option<&'a mut [syn::Item]> {
+    items.iter_mut().find_map(|item| match item {
+        syn::Item::Mod(syn::ItemMod {
+            ident,
+            content: Some((_, items)),
+            ..
+        }) if ident == name => Some(items.as_mut_slice()),
+        _ => None,
+    })
+}
+
+/// Gets the name of a world import or export as used for the keys of the
+/// exports implemented by the bindings (e.g. `my:pkg/iface`).
+fn export_name(resolve: &Resolve, key: &WorldKey) -> String {
+    match key {
+        WorldKey::Name(name) => name.clone(),
+        WorldKey::Interface(id) => {
+            let interface = &resolve.interfaces[*id];
+            let package =
+                &resolve.packages[interface.package.expect("interface must have a package")];
+            format!(
+                "{ns}:{pkg}/{iface}",
+                ns = package.name.namespace,
+                pkg = package.name.name,
+                iface = interface.name.as_ref().expect("interface must have a name")
+            )
+        }
+    }
+}
+
+/// Gets the names of the resources exported by the given world, as used for
+/// the keys of the `resources` option.
+fn exported_resources(resolve: &Resolve, world: WorldId) -> Vec<String> {
+    let mut names = Vec::new();
+    for (key, item) in &resolve.worlds[world].exports {
+        match item {
+            WorldItem::Interface(id) => {
+                let prefix = export_name(resolve, key);
+                names.extend(
+                    resolve.interfaces[*id]
+                        .types
+                        .iter()
+                        .filter(|(_, ty)| matches!(resolve.types[**ty].kind, TypeDefKind::Resource))
+                        .map(|(name, _)| format!("{prefix}/{name}")),
+                );
+            }
+            WorldItem::Type(ty) => {
+                if let TypeDefKind::Resource = resolve.types[*ty].kind {
+                    names.push(export_name(resolve, key));
+                }
+            }
+            WorldItem::Function(_) => continue,
+        }
+    }
+
+    names
+}
+
 fn target_path() -> Result<PathBuf> {
     Ok(Path::new(env!("CARGO_TARGET_DIR"))
         .join("bindings")
@@ -113,6 +651,99 @@ fn target_path() -> Result<PathBuf> {
         .join("target.wasm"))
 }
 
+/// Gets the world configured for the cargo target being compiled, if any.
+fn cargo_target_world(path: &Path, span: Span) -> Result<Option<String>> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+
+    let worlds = fs::read_to_string(path).map_err(|e| {
+        Error::new(
+            span,
+            format!(
+                "failed to read target worlds file `{path}`: {e}",
+                path = path.display()
+            ),
+        )
+    })?;
+
+    // Binaries have their own name; otherwise this is the library crate name
+    let target =
+        match std::env::var("CARGO_BIN_NAME").or_else(|_| std::env::var("CARGO_CRATE_NAME")) {
+            Ok(target) => target.replace('-', "_"),
+            Err(_) => return Ok(None),
+        };
+
+    Ok(worlds.lines().find_map(|line| {
+        let (name, world) = line.split_once('=')?;
+        (name.replace('-', "_") == target).then(|| world.to_string())
+    }))
+}
+
+/// Gets the path of the `wit-bindgen` runtime module configured for the
+/// package, if any.
+fn runtime_path(path: &Path, span: Span) -> Result<Option<String>> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+
+    let runtime = fs::read_to_string(path).map_err(|e| {
+        Error::new(
+            span,
+            format!(
+                "failed to read runtime path file `{path}`: {e}",
+                path = path.display()
+            ),
+        )
+    })?;
+
+    let runtime = runtime.trim();
+    Ok((!runtime.is_empty()).then(|| runtime.to_string()))
+}
+
+/// The default options of the macro set in the manifest of the package.
+struct ManifestOptions(Vec<Opt>);
+
+impl Parse for ManifestOptions {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        Ok(Self(
+            Punctuated::<Opt, Token![,]>::parse_terminated(input)?
+                .into_iter()
+                .collect(),
+        ))
+    }
+}
+
+/// Gets the default options of the macro set in the
+/// `package.metadata.component.bindings` table of the manifest, if any.
+fn manifest_options(path: &Path, span: Span) -> Result<Vec<Opt>> {
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+
+    let options = fs::read_to_string(path).map_err(|e| {
+        Error::new(
+            span,
+            format!(
+                "failed to read bindings options file `{path}`: {e}",
+                path = path.display()
+            ),
+        )
+    })?;
+
+    syn::parse_str::<ManifestOptions>(&options)
+        .map(|options| options.0)
+        .map_err(|e| {
+            Error::new(
+                span,
+                format!(
+                    "failed to parse bindings options file `{path}`: {e}",
+                    path = path.display()
+                ),
+            )
+        })
+}
+
 fn decode_resolve(path: &Path, span: Span) -> Result<(Resolve, WorldId)> {
     let bytes = std::fs::read(path).map_err(|e| {
         Error::new(
@@ -146,6 +777,8 @@ fn decode_resolve(path: &Path, span: Span) -> Result<(Resolve, WorldId)> {
         )
     })?;
 
+    let world = cargo_target_world(&path.with_file_name("worlds"), span)?.unwrap_or(world);
+
     match decoded {
         DecodedWasm::WitPackage(resolve, pkg) => {
             let world = resolve
@@ -167,6 +800,14 @@ mod kw {
     syn::custom_keyword!(implementor);
     syn::custom_keyword!(resources);
     syn::custom_keyword!(ownership);
+    syn::custom_keyword!(thread_safe_resources);
+    syn::custom_keyword!(raw_strings);
+    syn::custom_keyword!(allow);
+    syn::custom_keyword!(non_exhaustive);
+    syn::custom_keyword!(additional_derives);
+    syn::custom_keyword!(include);
+    syn::custom_keyword!(exclude);
+    syn::custom_keyword!(path);
 }
 
 #[derive(Clone)]
@@ -188,6 +829,15 @@ enum Opt {
     Implementor(Span, syn::Path),
     Resources(Span, Vec<Resource>),
     Ownership(Span, Ownership),
+    ThreadSafeResources(Span, bool),
+    RawStrings(Span, bool),
+    Allow(Span, Vec<syn::Path>),
+    NonExhaustive(Span, bool),
+    AdditionalDerives(Span, Vec<syn::Path>),
+    Include(Span, Vec<syn::LitStr>),
+    Exclude(Span, Vec<syn::LitStr>),
+    Async(Span, bool),
+    Path(Span, syn::Ident),
 }
 
 impl Parse for Opt {
@@ -220,23 +870,268 @@ impl Parse for Opt {
                     .parse()
                     .map_err(|e| Error::new(ownership.span(), e))?,
             ))
+        } else if l.peek(kw::thread_safe_resources) {
+            let span = input.parse::<kw::thread_safe_resources>()?.span;
+            input.parse::<Token![:]>()?;
+            Ok(Opt::ThreadSafeResources(
+                span,
+                input.parse::<syn::LitBool>()?.value,
+            ))
+        } else if l.peek(kw::raw_strings) {
+            let span = input.parse::<kw::raw_strings>()?.span;
+            input.parse::<Token![:]>()?;
+            Ok(Opt::RawStrings(span, input.parse::<syn::LitBool>()?.value))
+        } else if l.peek(kw::allow) {
+            let span = input.parse::<kw::allow>()?.span;
+            input.parse::<Token![:]>()?;
+            let contents;
+            syn::bracketed!(contents in input);
+            Ok(Opt::Allow(
+                span,
+                Punctuated::<syn::LitStr, Token![,]>::parse_terminated(&contents)?
+                    .iter()
+                    .map(|lint| lint.parse())
+                    .collect::<Result<_>>()?,
+            ))
+        } else if l.peek(kw::non_exhaustive) {
+            let span = input.parse::<kw::non_exhaustive>()?.span;
+            input.parse::<Token![:]>()?;
+            Ok(Opt::NonExhaustive(
+                span,
+                input.parse::<syn::LitBool>()?.value,
+            ))
+        } else if l.peek(kw::additional_derives) {
+            let span = input.parse::<kw::additional_derives>()?.span;
+            input.parse::<Token![:]>()?;
+            let contents;
+            syn::bracketed!(contents in input);
+            Ok(Opt::AdditionalDerives(
+                span,
+                Punctuated::<syn::Path, Token![,]>::parse_terminated(&contents)?
+                    .into_iter()
+                    .collect(),
+            ))
+        } else if l.peek(kw::include) {
+            let span = input.parse::<kw::include>()?.span;
+            input.parse::<Token![:]>()?;
+            let contents;
+            syn::bracketed!(contents in input);
+            Ok(Opt::Include(
+                span,
+                Punctuated::<syn::LitStr, Token![,]>::parse_terminated(&contents)?
+                    .into_iter()
+                    .collect(),
+            ))
+        } else if l.peek(kw::exclude) {
+            let span = input.parse::<kw::exclude>()?.span;
+            input.parse::<Token![:]>()?;
+            let contents;
+            syn::bracketed!(contents in input);
+            Ok(Opt::Exclude(
+                span,
+                Punctuated::<syn::LitStr, Token![,]>::parse_terminated(&contents)?
+                    .into_iter()
+                    .collect(),
+            ))
+        } else if l.peek(kw::path) {
+            let span = input.parse::<kw::path>()?.span;
+            input.parse::<Token![:]>()?;
+            let module = input.parse::<syn::Ident>().map_err(|e| {
+                Error::new(
+                    e.span(),
+                    "expected the name of the bindings module to be a valid module identifier",
+                )
+            })?;
+            Ok(Opt::Path(span, module))
+        } else if l.peek(Token![async]) {
+            let span = input.parse::<Token![async]>()?.span;
+            input.parse::<Token![:]>()?;
+            Ok(Opt::Async(span, input.parse::<syn::LitBool>()?.value))
         } else {
             Err(l.error())
         }
     }
 }
 
+/// The interfaces to include in or exclude from the bindings.
+struct Filter {
+    include: bool,
+    names: Vec<syn::LitStr>,
+}
+
+impl Filter {
+    /// Removes the filtered interfaces from the imports and exports of the
+    /// given world.
+    fn apply(&self, resolve: &mut Resolve, world: WorldId) -> Result<()> {
+        let key_names = |key: &WorldKey| -> Vec<String> {
+            match key {
+                WorldKey::Name(name) => vec![name.clone()],
+                WorldKey::Interface(id) => {
+                    let interface = &resolve.interfaces[*id];
+                    let package = &resolve.packages
+                        [interface.package.expect("interface must have a package")];
+                    let name = interface.name.as_ref().expect("interface must have a name");
+                    let mut names = vec![
+                        format!(
+                            "{ns}:{pkg}/{name}",
+                            ns = package.name.namespace,
+                            pkg = package.name.name
+                        ),
+                        format!("{pkg}/{name}", pkg = package.name.name),
+                    ];
+                    if let Some(version) = &package.name.version {
+                        names.push(format!("{names}@{version}", names = names[0]));
+                    }
+                    names
+                }
+            }
+        };
+
+        let w = &resolve.worlds[world];
+        let items: Vec<_> = w
+            .imports
+            .iter()
+            .chain(&w.exports)
+            .filter_map(|(key, item)| match item {
+                WorldItem::Interface(id) => Some((key_names(key), *id)),
+                _ => None,
+            })
+            .collect();
+
+        for name in &self.names {
+            let value = name.value();
+            if !items.iter().any(|(names, _)| names.contains(&value)) {
+                return Err(Error::new(
+                    name.span(),
+                    format!("interface `{value}` is not imported or exported by the target world"),
+                ));
+            }
+        }
+
+        let mut retained: Vec<InterfaceId> = items
+            .iter()
+            .filter(|(names, _)| {
+                self.include == self.names.iter().any(|n| names.contains(&n.value()))
+            })
+            .map(|(_, id)| *id)
+            .collect();
+
+        // Keep the interfaces whose types are used by retained items
+        fn retain_owner(
+            resolve: &Resolve,
+            ty: TypeId,
+            retained: &mut Vec<InterfaceId>,
+            pending: &mut Vec<InterfaceId>,
+        ) {
+            if let TypeDefKind::Type(Type::Id(used)) = resolve.types[ty].kind {
+                if let TypeOwner::Interface(owner) = resolve.types[used].owner {
+                    if !retained.contains(&owner) {
+                        retained.push(owner);
+                        pending.push(owner);
+                    }
+                }
+            }
+        }
+
+        let mut pending = retained.clone();
+        for item in w.imports.values().chain(w.exports.values()) {
+            if let WorldItem::Type(ty) = item {
+                retain_owner(resolve, *ty, &mut retained, &mut pending);
+            }
+        }
+
+        while let Some(id) = pending.pop() {
+            for ty in resolve.interfaces[id].types.values() {
+                retain_owner(resolve, *ty, &mut retained, &mut pending);
+            }
+        }
+
+        let keep = |item: &WorldItem| match item {
+            WorldItem::Interface(id) => retained.contains(id),
+            _ => true,
+        };
+
+        let w = &mut resolve.worlds[world];
+        w.imports.retain(|_, item| keep(item));
+        w.exports.retain(|_, item| keep(item));
+        Ok(())
+    }
+}
+
 struct Config {
     input: PathBuf,
     resolve: Resolve,
     world: WorldId,
     implementor: Option<syn::Path>,
-    resources: HashMap<String, syn::Path>,
+    resources: Vec<Resource>,
     ownership: Ownership,
+    thread_safe_resources: bool,
+    raw_strings: bool,
+    allow: Vec<syn::Path>,
+    non_exhaustive: bool,
+    additional_derives: Vec<syn::Path>,
+    filter: Option<Filter>,
+    runtime_path: Option<String>,
+    module: syn::Ident,
 }
 
 impl Config {
-    fn expand(self) -> Result<TokenStream> {
+    /// Validates the `implementor` and `resources` options against the
+    /// exports of the target world.
+    fn validate_exports(&self) -> Result<()> {
+        let world = &self.resolve.worlds[self.world];
+        if let Some(implementor) = &self.implementor {
+            if !world
+                .exports
+                .values()
+                .any(|item| matches!(item, WorldItem::Interface(_) | WorldItem::Function(_)))
+            {
+                return Err(Error::new_spanned(
+                    implementor,
+                    format!(
+                        "implementor `{path}` is not used as the target world `{world}` does not \
+                         export any functions or interfaces",
+                        path = quote!(#implementor).to_string().replace(' ', ""),
+                        world = world.name,
+                    ),
+                ));
+            }
+        }
+
+        if self.resources.is_empty() {
+            return Ok(());
+        }
+
+        let exported = exported_resources(&self.resolve, self.world);
+        for resource in &self.resources {
+            let key = resource.key.value();
+            if exported.contains(&key) {
+                continue;
+            }
+
+            let mut message = format!(
+                "resource `{key}` is not exported by the target world `{world}`",
+                world = world.name
+            );
+            if exported.is_empty() {
+                message.push_str("; the world does not export any resources");
+            } else {
+                message.push_str("; the exported resources are: ");
+                for (i, name) in exported.iter().enumerate() {
+                    if i > 0 {
+                        message.push_str(", ");
+                    }
+                    write!(&mut message, "`{name}`").unwrap();
+                }
+            }
+
+            return Err(Error::new(resource.key.span(), message));
+        }
+
+        Ok(())
+    }
+
+    fn expand(mut self) -> Result<TokenStream> {
         fn resource_implementor(
             key: &str,
             name: &str,
@@ -253,6 +1148,18 @@ impl Config {
             }))
         }
 
+        if let Some(filter) = &self.filter {
+            filter.apply(&mut self.resolve, self.world)?;
+        }
+
+        self.validate_exports()?;
+
+        let resources: HashMap<_, _> = self
+            .resources
+            .iter()
+            .map(|r| (r.key.value(), r.value.clone()))
+            .collect();
+
         let implementor =
             implementor_path_str(&self.implementor.unwrap_or_else(|| parse_quote!(Component)));
 
@@ -261,22 +1168,7 @@ impl Config {
         exports.insert(ExportKey::World, implementor.clone());
 
         for (name, item) in &world.exports {
-            let key = match name {
-                WorldKey::Name(name) => name.clone(),
-                WorldKey::Interface(id) => {
-                    let interface = &self.resolve.interfaces[*id];
-                    let package = &self.resolve.packages
-                        [interface.package.expect("interface must have a package")];
-
-                    let mut key = String::new();
-                    key.push_str(&package.name.namespace);
-                    key.push(':');
-                    key.push_str(&package.name.name);
-                    key.push('/');
-                    key.push_str(interface.name.as_ref().expect("interface must have a name"));
-                    key
-                }
-            };
+            let key = export_name(&self.resolve, name);
 
             let implementor = match item {
                 WorldItem::Interface(id) => {
@@ -285,7 +1177,7 @@ impl Config {
                         match self.resolve.types[*ty].kind {
                             TypeDefKind::Resource => {
                                 let key = format!("{key}/{name}");
-                                let implementor = resource_implementor(&key, name, &self.resources);
+                                let implementor = resource_implementor(&key, name, &resources);
                                 exports.insert(ExportKey::Name(key), implementor);
                             }
                             _ => continue,
@@ -295,7 +1187,7 @@ impl Config {
                     implementor.clone()
                 }
                 WorldItem::Type(id) => match self.resolve.types[*id].kind {
-                    TypeDefKind::Resource => resource_implementor(&key, &key, &self.resources),
+                    TypeDefKind::Resource => resource_implementor(&key, &key, &resources),
                     _ => continue,
                 },
                 WorldItem::Function(_) => implementor.clone(),
@@ -307,7 +1199,11 @@ impl Config {
         let opts = Opts {
             exports,
             ownership: self.ownership,
-            runtime_path: Some("::cargo_component_bindings::rt".to_string()),
+            raw_strings: self.raw_strings,
+            runtime_path: Some(
+                self.runtime_path
+                    .unwrap_or_else(|| "::cargo_component_bindings::rt".to_string()),
+            ),
             bitflags_path: Some("::cargo_component_bindings::bitflags".to_string()),
             ..Default::default()
         };
@@ -334,13 +1230,33 @@ impl Config {
             "expected exactly one source file to be generated"
         );
 
-        let source = sources[0].parse::<TokenStream>()?;
+        let mut file = syn::parse2::<syn::File>(sources[0].parse::<TokenStream>()?)?;
+        document_resource_methods(&self.resolve, self.world, &mut file.items);
+        if self.non_exhaustive {
+            mark_non_exhaustive(&mut file.items);
+        }
+        if !self.additional_derives.is_empty() {
+            add_derives(&mut file.items, &self.additional_derives);
+        }
+        mark_thread_affine_resources(&mut file.items, self.thread_safe_resources);
+
+        let source = quote!(#file);
         let input = self.input.display().to_string();
 
+        let allow = &self.allow;
+        let allow = (!allow.is_empty()).then(|| quote!(#![allow(#(#allow),*)]));
+
+        let imports = imports_module(&self.resolve, self.world);
+        let module = &self.module;
+
         Ok(quote! {
-            pub(crate) mod bindings {
+            pub(crate) mod #module {
+                #allow
+
                 #source
 
+                #imports
+
                 const _: &[u8] = include_bytes!(#input);
             }
         })
@@ -352,6 +1268,14 @@ impl Parse for Config {
         let mut implementor: Option<syn::Path> = None;
         let mut resources: Option<Vec<Resource>> = None;
         let mut ownership: Option<Ownership> = None;
+        let mut thread_safe_resources: Option<bool> = None;
+        let mut raw_strings: Option<bool> = None;
+        let mut allow: Option<Vec<syn::Path>> = None;
+        let mut non_exhaustive: Option<bool> = None;
+        let mut additional_derives: Option<Vec<syn::Path>> = None;
+        let mut filter: Option<Filter> = None;
+        let mut async_exports: Option<bool> = None;
+        let mut module: Option<syn::Ident> = None;
 
         if input.peek(token::Brace) {
             let content;
@@ -398,22 +1322,165 @@ impl Parse for Config {
 
                         ownership = Some(value);
                     }
+                    Opt::ThreadSafeResources(span, value) => {
+                        if thread_safe_resources.is_some() {
+                            return Err(Error::new(
+                                span,
+                                "cannot specify `thread_safe_resources` more than once",
+                            ));
+                        }
+
+                        thread_safe_resources = Some(value);
+                    }
+                    Opt::RawStrings(span, value) => {
+                        if raw_strings.is_some() {
+                            return Err(Error::new(
+                                span,
+                                "cannot specify `raw_strings` more than once",
+                            ));
+                        }
+
+                        raw_strings = Some(value);
+                    }
+                    Opt::Allow(span, value) => {
+                        if allow.is_some() {
+                            return Err(Error::new(span, "cannot specify `allow` more than once"));
+                        }
+
+                        allow = Some(value);
+                    }
+                    Opt::NonExhaustive(span, value) => {
+                        if non_exhaustive.is_some() {
+                            return Err(Error::new(
+                                span,
+                                "cannot specify `non_exhaustive` more than once",
+                            ));
+                        }
+
+                        non_exhaustive = Some(value);
+                    }
+                    Opt::AdditionalDerives(span, value) => {
+                        if additional_derives.is_some() {
+                            return Err(Error::new(
+                                span,
+                                "cannot specify `additional_derives` more than once",
+                            ));
+                        }
+
+                        additional_derives = Some(value);
+                    }
+                    Opt::Include(span, names) => {
+                        if filter.is_some() {
+                            return Err(Error::new(
+                                span,
+                                "cannot specify `include` or `exclude` more than once",
+                            ));
+                        }
+
+                        filter = Some(Filter {
+                            include: true,
+                            names,
+                        });
+                    }
+                    Opt::Exclude(span, names) => {
+                        if filter.is_some() {
+                            return Err(Error::new(
+                                span,
+                                "cannot specify `include` or `exclude` more than once",
+                            ));
+                        }
+
+                        filter = Some(Filter {
+                            include: false,
+                            names,
+                        });
+                    }
+                    Opt::Async(span, value) => {
+                        if async_exports.is_some() {
+                            return Err(Error::new(span, "cannot specify `async` more than once"));
+                        }
+
+                        // The supported WIT version has no async functions,
+                        // so there are no exports to generate async methods
+                        // for and the option has no effect
+                        async_exports = Some(value);
+                    }
+                    Opt::Path(span, value) => {
+                        if module.is_some() {
+                            return Err(Error::new(span, "cannot specify `path` more than once"));
+                        }
+
+                        module = Some(value);
+                    }
                 }
             }
         }
 
         let input = target_path()?;
         let (resolve, world) = decode_resolve(&input, Span::call_site())?;
+        let runtime_path = runtime_path(&input.with_file_name("runtime"), Span::call_site())?;
+
+        // Options not given to the macro default to those of the manifest
+        for option in manifest_options(&input.with_file_name("options"), Span::call_site())? {
+            match option {
+                Opt::Implementor(_, value) => {
+                    implementor.get_or_insert(value);
+                }
+                Opt::Resources(_, value) => {
+                    resources.get_or_insert(value);
+                }
+                Opt::Ownership(_, value) => {
+                    ownership.get_or_insert(value);
+                }
+                Opt::ThreadSafeResources(_, value) => {
+                    thread_safe_resources.get_or_insert(value);
+                }
+                Opt::RawStrings(_, value) => {
+                    raw_strings.get_or_insert(value);
+                }
+                Opt::Allow(_, value) => {
+                    allow.get_or_insert(value);
+                }
+                Opt::NonExhaustive(_, value) => {
+                    non_exhaustive.get_or_insert(value);
+                }
+                Opt::AdditionalDerives(_, value) => {
+                    additional_derives.get_or_insert(value);
+                }
+                Opt::Include(_, names) => {
+                    filter.get_or_insert(Filter {
+                        include: true,
+                        names,
+                    });
+                }
+                Opt::Exclude(_, names) => {
+                    filter.get_or_insert(Filter {
+                        include: false,
+                        names,
+                    });
+                }
+                Opt::Async(..) => {}
+                Opt::Path(_, value) => {
+                    module.get_or_insert(value);
+                }
+            }
+        }
 
         Ok(Config {
             input,
             resolve,
             world,
             implementor,
-            resources: resources
-                .map(|r| r.into_iter().map(|r| (r.key.value(), r.value)).collect())
-                .unwrap_or_default(),
+            resources: resources.unwrap_or_default(),
             ownership: ownership.unwrap_or_default(),
+            thread_safe_resources: thread_safe_resources.unwrap_or(false),
+            raw_strings: raw_strings.unwrap_or(false),
+            allow: allow.unwrap_or_default(),
+            non_exhaustive: non_exhaustive.unwrap_or(false),
+            additional_derives: additional_derives.unwrap_or_default(),
+            filter,
+            runtime_path,
+            module: module.unwrap_or_else(|| syn::Ident::new("bindings", Span::call_site())),
         })
     }
 }
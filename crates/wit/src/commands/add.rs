@@ -93,7 +93,7 @@ impl AddCommand {
             Some(path) => {
                 config
                     .dependencies
-                    .insert(id.clone(), Dependency::Local(path.to_path_buf()));
+                    .insert(id.clone(), Dependency::Local(path.to_path_buf().into()));
 
                 format!(
                     "dependency `{id}` from path `{path}`{dry_run}",
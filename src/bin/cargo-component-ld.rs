@@ -0,0 +1,127 @@
+//! A linker wrapper that produces WebAssembly components.
+//!
+//! Configure it as the linker for a WebAssembly target so that a plain
+//! `cargo build` produces a component:
+//!
+//! ```toml
+//! [target.wasm32-wasi]
+//! linker = "cargo-component-ld"
+//! ```
+//!
+//! The module is linked with `rust-lld` and then componentized based on the
+//! component metadata of the package being built.
+
+use anyhow::{bail, Context, Result};
+use cargo_component::{componentize_linked_module, config::Config, target::find_rust_lld};
+use cargo_component_core::terminal::{Color, Terminal, Verbosity};
+use std::{
+    env,
+    path::{Component, Path, PathBuf},
+    process::Command,
+};
+
+fn output_path(args: &[String]) -> Option<PathBuf> {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "-o" {
+            return iter.next().map(PathBuf::from);
+        }
+
+        if let Some(path) = arg.strip_prefix("-o") {
+            return Some(PathBuf::from(path));
+        }
+    }
+
+    None
+}
+
+/// Gets the target triple and cargo profile of a build from the path of its
+/// output, which cargo places in `<target-dir>/<triple>/<profile-dir>/`.
+///
+/// Returns `None` for either if the path does not follow that layout.
+fn target_and_profile(path: &Path) -> (Option<String>, Option<String>) {
+    let components: Vec<_> = path
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .collect();
+
+    match components
+        .iter()
+        .rposition(|name| name.starts_with("wasm32-"))
+    {
+        Some(index) => {
+            let profile = components
+                .get(index + 1)
+                .filter(|_| index + 2 < components.len())
+                .map(|dir| match *dir {
+                    "debug" => "dev".to_string(),
+                    dir => dir.to_string(),
+                });
+            (Some(components[index].to_string()), profile)
+        }
+        None => (None, None),
+    }
+}
+
+fn link(args: &[String]) -> Result<()> {
+    let lld = find_rust_lld()?;
+
+    log::debug!(
+        "spawning linker `{lld}` with arguments `{args:?}`",
+        lld = lld.display()
+    );
+
+    let status = Command::new(&lld)
+        .arg("-flavor")
+        .arg("wasm")
+        .args(args)
+        .status()
+        .with_context(|| format!("failed to spawn `{lld}`", lld = lld.display()))?;
+
+    if !status.success() {
+        std::process::exit(status.code().unwrap_or(1));
+    }
+
+    let path = output_path(args).context("linker arguments did not contain an output path")?;
+
+    // Cargo sets these variables for rustc, which are inherited by the linker
+    let manifest_dir = match env::var_os("CARGO_MANIFEST_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => bail!("`CARGO_MANIFEST_DIR` is not set; the linker must be invoked by cargo"),
+    };
+
+    let package_name = env::var("CARGO_PKG_NAME")
+        .context("failed to get `CARGO_PKG_NAME` environment variable")?;
+
+    // The linker is not told the target or profile, so infer them from the
+    // output path for adapters configured by target or profile
+    let (target, profile) = target_and_profile(&path);
+    log::debug!("linking for target `{target:?}` with profile `{profile:?}`");
+
+    let config = Config::new(Terminal::new(Verbosity::Normal, Color::Auto))?;
+    componentize_linked_module(
+        &config,
+        &manifest_dir.join("Cargo.toml"),
+        &package_name,
+        &path,
+        target.as_deref(),
+        profile.as_deref(),
+        env::var_os("CARGO_BIN_NAME").is_some(),
+    )
+}
+
+fn main() -> Result<()> {
+    pretty_env_logger::init_custom_env("CARGO_COMPONENT_LOG");
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Err(e) = link(&args) {
+        let terminal = Terminal::new(Verbosity::Normal, Color::Auto);
+        terminal.error(format!("{e:?}"))?;
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
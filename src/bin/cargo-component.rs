@@ -1,8 +1,13 @@
 use anyhow::Result;
 use cargo_component::{
-    commands::{AddCommand, KeyCommand, NewCommand, PublishCommand, UpdateCommand},
+    commands::{
+        AddCommand, BindingsCommand, DoctorCommand, ExpandCommand, FmtCommand, KeyCommand,
+        MigrateCommand, NewCommand, PublishCommand, TreeCommand, UpdateCommand, VendorWitCommand,
+        VerifyCommand, WitCommand,
+    },
     config::{CargoArguments, Config},
-    load_component_metadata, load_metadata, run_cargo_command,
+    load_component_metadata, load_metadata, run_cargo_command, short_error_message,
+    test_components,
 };
 use cargo_component_core::terminal::{Color, Terminal, Verbosity};
 use clap::{CommandFactory, Parser};
@@ -14,16 +19,25 @@ fn version() -> &'static str {
 /// The list of commands that are built-in to `cargo-component`.
 const BUILTIN_COMMANDS: &[&str] = &[
     "add",
+    "bindings",
     "component", // for indirection via `cargo component`
+    "doctor",
+    "expand",
+    "fmt",
     "help",
     "init",
     "key",
+    "migrate",
     "new",
     "publish",
     "remove",
     "rm",
+    "tree",
     "update",
     "vendor",
+    "vendor-wit",
+    "verify",
+    "wit",
     "yank",
 ];
 
@@ -67,22 +81,47 @@ enum CargoComponent {
 #[derive(Parser)]
 enum Command {
     Add(AddCommand),
+    Bindings(BindingsCommand),
+    Doctor(DoctorCommand),
+    Expand(ExpandCommand),
+    Fmt(FmtCommand),
     // TODO: Init(InitCommand),
     Key(KeyCommand),
+    Migrate(MigrateCommand),
     New(NewCommand),
     // TODO: Remove(RemoveCommand),
+    Tree(TreeCommand),
     Update(UpdateCommand),
     Publish(PublishCommand),
+    VendorWit(VendorWitCommand),
+    Verify(VerifyCommand),
+    Wit(WitCommand),
     // TODO: Yank(YankCommand),
     // TODO: Vendor(VendorCommand),
 }
 
-fn detect_subcommand() -> Option<String> {
-    let mut iter = std::env::args().skip(1).peekable();
+/// Removes a `+toolchain` argument (e.g. `cargo component +nightly build`)
+/// from the given arguments.
+///
+/// As with cargo, the toolchain must come before the subcommand.
+fn take_toolchain(args: &mut Vec<String>) -> Option<String> {
+    let index = match args.get(1).map(String::as_str) {
+        Some("component") => 2,
+        _ => 1,
+    };
+
+    match args.get(index) {
+        Some(arg) if arg.starts_with('+') => Some(args.remove(index)[1..].to_string()),
+        _ => None,
+    }
+}
+
+fn detect_subcommand(args: &[String]) -> Option<String> {
+    let mut iter = args.iter().skip(1).peekable();
 
     // Skip the first argument if it is `component` (i.e. `cargo component`)
     if let Some(arg) = iter.peek() {
-        if arg == "component" {
+        if arg.as_str() == "component" {
             iter.next().unwrap();
         }
     }
@@ -94,7 +133,7 @@ fn detect_subcommand() -> Option<String> {
         }
 
         if !arg.starts_with('-') {
-            return Some(arg);
+            return Some(arg.clone());
         }
     }
 
@@ -105,17 +144,35 @@ fn detect_subcommand() -> Option<String> {
 async fn main() -> Result<()> {
     pretty_env_logger::init_custom_env("CARGO_COMPONENT_LOG");
 
-    let subcommand = detect_subcommand();
+    let mut args: Vec<String> = std::env::args().collect();
+    if let Some(toolchain) = take_toolchain(&mut args) {
+        // Select the toolchain for all spawned `rustup`, `rustc`, and `cargo`
+        // processes; `CARGO` refers to the current toolchain's cargo, so it
+        // is removed to spawn cargo through the rustup proxy instead
+        std::env::set_var("RUSTUP_TOOLCHAIN", toolchain);
+        std::env::remove_var("CARGO");
+    }
+
+    let subcommand = detect_subcommand(&args);
     match subcommand.as_deref() {
         // Check for built-in command or no command (shows help)
         Some(cmd) if BUILTIN_COMMANDS.contains(&cmd) => {
-            if let Err(e) = match CargoComponent::parse() {
+            if let Err(e) = match CargoComponent::parse_from(&args) {
                 CargoComponent::Component(cmd) | CargoComponent::Command(cmd) => match cmd {
                     Command::Add(cmd) => cmd.exec().await,
+                    Command::Bindings(cmd) => cmd.exec().await,
+                    Command::Doctor(cmd) => cmd.exec().await,
+                    Command::Expand(cmd) => cmd.exec().await,
+                    Command::Fmt(cmd) => cmd.exec().await,
                     Command::Key(cmd) => cmd.exec().await,
+                    Command::Migrate(cmd) => cmd.exec().await,
                     Command::New(cmd) => cmd.exec().await,
+                    Command::Tree(cmd) => cmd.exec().await,
                     Command::Update(cmd) => cmd.exec().await,
                     Command::Publish(cmd) => cmd.exec().await,
+                    Command::VendorWit(cmd) => cmd.exec().await,
+                    Command::Verify(cmd) => cmd.exec().await,
+                    Command::Wit(cmd) => cmd.exec().await,
                 },
             } {
                 let terminal = Terminal::new(Verbosity::Normal, Color::Auto);
@@ -137,7 +194,7 @@ async fn main() -> Result<()> {
         // If no subcommand was detected,
         None => {
             // Attempt to parse the supported CLI (expected to fail)
-            CargoComponent::parse();
+            CargoComponent::parse_from(&args);
 
             // If somehow the CLI parsed correctly despite no subcommand,
             // print the help instead
@@ -146,7 +203,12 @@ async fn main() -> Result<()> {
 
         _ => {
             // Not a built-in command, run the cargo command
-            let cargo_args = CargoArguments::parse()?;
+            let cargo_args = CargoArguments::parse_from(args.iter().skip(1))?;
+            if let Some(cargo) = &cargo_args.cargo {
+                // Also load the workspace metadata with the given cargo
+                std::env::set_var("CARGO", cargo);
+            }
+
             let config = Config::new(Terminal::new(
                 if cargo_args.quiet {
                     Verbosity::Quiet
@@ -156,7 +218,10 @@ async fn main() -> Result<()> {
                         _ => Verbosity::Verbose,
                     }
                 },
-                cargo_args.color.unwrap_or_default(),
+                cargo_args
+                    .color
+                    .or_else(Color::from_env)
+                    .unwrap_or_default(),
             ))?;
 
             let metadata = load_metadata(cargo_args.manifest_path.as_deref())?;
@@ -167,7 +232,14 @@ async fn main() -> Result<()> {
             )?;
             assert!(!packages.is_empty());
 
-            let spawn_args: Vec<_> = std::env::args().skip(1).collect();
+            if subcommand.as_deref() == Some("test") && cargo_args.test_components {
+                if let Err(e) = test_components(&config, &metadata, &packages, &cargo_args).await {
+                    config.terminal().error(format!("{e:?}"))?;
+                    std::process::exit(1);
+                }
+            }
+
+            let spawn_args: Vec<_> = args.iter().skip(1).cloned().collect();
             if let Err(e) = run_cargo_command(
                 &config,
                 &metadata,
@@ -178,7 +250,16 @@ async fn main() -> Result<()> {
             )
             .await
             {
-                config.terminal().error(format!("{e:?}"))?;
+                if cargo_args.short_message_format() {
+                    match short_error_message(&e) {
+                        (Some(location), message) => {
+                            config.terminal().error_at(location, message)?
+                        }
+                        (None, message) => config.terminal().error(message)?,
+                    }
+                } else {
+                    config.terminal().error(format!("{e:?}"))?;
+                }
                 std::process::exit(1);
             }
         }
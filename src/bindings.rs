@@ -2,22 +2,29 @@
 
 use crate::{
     last_modified_time,
-    metadata::{ComponentMetadata, Target},
+    metadata::{split_qualified_world, ComponentMetadata, Target, STDIN_TARGET_PATH},
     registry::PackageDependencyResolution,
 };
 use anyhow::{bail, Context, Result};
-use cargo_component_core::registry::DecodedDependency;
+use cargo_component_core::registry::{DecodedDependency, DependencyResolution};
+use heck::{ToSnakeCase, ToUpperCamelCase};
 use indexmap::{IndexMap, IndexSet};
+use serde_json::json;
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
+    io::Read,
     path::{Path, PathBuf},
     time::SystemTime,
 };
 use warg_protocol::registry::PackageId;
-use wit_component::DecodedWasm;
+use wit_bindgen_core::Files;
+use wit_bindgen_rust::{ExportKey, Opts};
+use wit_bindgen_rust_lib::to_rust_ident;
+use wit_component::{DecodedWasm, WitPrinter};
 use wit_parser::{
-    Interface, Package, PackageName, Resolve, Type, TypeDefKind, TypeOwner, UnresolvedPackage,
-    World, WorldId, WorldItem, WorldKey,
+    Docs, Function, FunctionKind, Handle, Interface, Package, PackageName, Resolve, Results, Type,
+    TypeDef, TypeDefKind, TypeId, TypeOwner, UnresolvedPackage, World, WorldId, WorldItem,
+    WorldKey,
 };
 
 fn named_world_key<'a>(resolve: &'a Resolve, orig: &'a WorldKey, prefix: &str) -> WorldKey {
@@ -32,6 +39,418 @@ fn named_world_key<'a>(resolve: &'a Resolve, orig: &'a WorldKey, prefix: &str) -
     WorldKey::Name(format!("{prefix}-{name}"))
 }
 
+/// Represents a local WIT package printed in canonical form.
+pub struct FormattedPackage {
+    /// The name of the package.
+    pub name: PackageName,
+    /// The WIT files that define the package.
+    pub files: Vec<PathBuf>,
+    /// The canonical WIT source of the package.
+    pub source: String,
+}
+
+/// Prints the local target of a package and its local WIT dependencies in
+/// canonical form.
+///
+/// Returns an empty list if the package does not have a local target.
+pub fn format_local_target(
+    resolution: &PackageDependencyResolution,
+) -> Result<Vec<FormattedPackage>> {
+    let metadata = resolution.metadata;
+    let dependency_world = BindingsEncoder::dependency_world(resolution)?;
+    let path = match metadata.target_path() {
+        Some(path) if !metadata.target_from_stdin() && dependency_world.is_none() => path,
+        _ => return Ok(Vec::new()),
+    };
+
+    let (resolve, world, _) =
+        BindingsEncoder::target_local_path(resolution, &path, metadata.section.target.world())?;
+
+    let mut packages = vec![(
+        resolve.worlds[world]
+            .package
+            .context("world has no package")?,
+        wit_files(&path)?,
+    )];
+
+    for resolution in resolution.target_resolutions.values() {
+        if let DependencyResolution::Local(local) = resolution {
+            if let DecodedDependency::Wit { package, .. } = resolution.decode()? {
+                let id = resolve.package_names[&package.name];
+                packages.push((id, wit_files(&local.path)?));
+            }
+        }
+    }
+
+    packages
+        .into_iter()
+        .map(|(id, files)| {
+            let mut source = WitPrinter::default().print(&resolve, id)?;
+            if !source.ends_with('\n') {
+                source.push('\n');
+            }
+
+            Ok(FormattedPackage {
+                name: resolve.packages[id].name.clone(),
+                files,
+                source,
+            })
+        })
+        .collect()
+}
+
+/// Encodes the local target of a package as a WIT package if the target
+/// defines no worlds.
+///
+/// Such a package only provides types and interfaces for other components
+/// to use, so the encoded WIT package is the component that is built.
+///
+/// Returns `Ok(None)` if the package does not have a local target or if the
+/// local target defines a world.
+pub fn encode_type_package(resolution: &PackageDependencyResolution) -> Result<Option<Vec<u8>>> {
+    let metadata = resolution.metadata;
+    if metadata.section.target.world().is_some() || !metadata.section.target_worlds.is_empty() {
+        return Ok(None);
+    }
+
+    let path = match metadata.target_path() {
+        Some(path) if !metadata.target_from_stdin() => path,
+        _ => return Ok(None),
+    };
+
+    let (resolve, package, _) = BindingsEncoder::target_local_package(resolution, &path)?;
+    check_package_name(metadata, &resolve.packages[package].name)?;
+    if !resolve.packages[package].worlds.is_empty() {
+        return Ok(None);
+    }
+
+    wit_component::encode(&resolve, package)
+        .map(Some)
+        .with_context(|| {
+            format!(
+                "failed to encode WIT package `{name}`",
+                name = resolve.packages[package].name
+            )
+        })
+}
+
+/// Represents a WIT package printed for vendoring.
+pub struct VendoredPackage {
+    /// The name of the package.
+    pub name: PackageName,
+    /// The canonical WIT source of the package.
+    pub source: String,
+}
+
+/// Represents the vendored contents of a target dependency.
+pub enum VendoredContents {
+    /// The dependency is vendored as WIT source.
+    Wit {
+        /// The package of the dependency.
+        package: VendoredPackage,
+        /// The packages the dependency depends on, including transitive ones.
+        deps: Vec<VendoredPackage>,
+    },
+    /// The dependency is vendored as an encoded WebAssembly file.
+    Wasm {
+        /// The contents of the file.
+        bytes: Vec<u8>,
+        /// The name of the package defined by the file, if it differs from
+        /// the id of the dependency.
+        package: Option<PackageId>,
+    },
+}
+
+/// Resolves the target dependencies of a package with a local target for
+/// vendoring.
+///
+/// Dependencies defined in WIT are printed in canonical form along with the
+/// packages they depend on, so the vendored copy does not rely on the `deps`
+/// directory layout of the original; dependencies encoded as WebAssembly are
+/// self-contained and are vendored as is.
+pub fn vendor_target_dependencies(
+    resolution: &PackageDependencyResolution,
+) -> Result<Vec<(PackageId, VendoredContents)>> {
+    let metadata = resolution.metadata;
+    let path = match metadata.target_path() {
+        Some(path) if !metadata.target_from_stdin() => path,
+        _ => bail!(
+            "package `{name}` does not have a local target to vendor the dependencies of",
+            name = metadata.name
+        ),
+    };
+
+    let (resolve, _, _) = BindingsEncoder::target_local_package(resolution, &path)?;
+    let print = |id: wit_parser::PackageId| -> Result<VendoredPackage> {
+        let mut source = WitPrinter::default().print(&resolve, id)?;
+        if !source.ends_with('\n') {
+            source.push('\n');
+        }
+
+        Ok(VendoredPackage {
+            name: resolve.packages[id].name.clone(),
+            source,
+        })
+    };
+
+    let mut ids: Vec<_> = resolution.target_resolutions.keys().collect();
+    ids.sort_by_key(|id| id.to_string());
+
+    let mut vendored = Vec::new();
+    for id in ids {
+        let dependency = &resolution.target_resolutions[id];
+        let contents = match dependency.decode()? {
+            DecodedDependency::Wit { package, .. } => {
+                let pkg = resolve.package_names[&package.name];
+                VendoredContents::Wit {
+                    package: print(pkg)?,
+                    deps: package_dependencies(&resolve, pkg)
+                        .into_iter()
+                        .map(print)
+                        .collect::<Result<_>>()?,
+                }
+            }
+            DecodedDependency::Wasm { .. } => VendoredContents::Wasm {
+                bytes: std::fs::read(dependency.path()).with_context(|| {
+                    format!(
+                        "failed to read content of dependency `{id}` at path `{path}`",
+                        path = dependency.path().display()
+                    )
+                })?,
+                package: match dependency {
+                    DependencyResolution::Local(local) => local.package.clone(),
+                    DependencyResolution::Registry(registry) => {
+                        (registry.package != registry.id).then(|| registry.package.clone())
+                    }
+                },
+            },
+        };
+
+        vendored.push((id.clone(), contents));
+    }
+
+    Ok(vendored)
+}
+
+/// Gets the packages that the given package depends on, including transitive
+/// dependencies.
+fn package_dependencies(
+    resolve: &Resolve,
+    package: wit_parser::PackageId,
+) -> Vec<wit_parser::PackageId> {
+    let mut deps = IndexSet::new();
+    let mut pending = vec![package];
+    while let Some(id) = pending.pop() {
+        for used in direct_package_dependencies(resolve, id) {
+            if used != package && deps.insert(used) {
+                pending.push(used);
+            }
+        }
+    }
+
+    deps.into_iter().collect()
+}
+
+/// Gets the packages directly used by the interfaces and worlds of a package.
+fn direct_package_dependencies(
+    resolve: &Resolve,
+    package: wit_parser::PackageId,
+) -> IndexSet<wit_parser::PackageId> {
+    let type_package = |ty: TypeId| match resolve.types[ty].kind {
+        TypeDefKind::Type(Type::Id(used)) => match resolve.types[used].owner {
+            TypeOwner::Interface(owner) => resolve.interfaces[owner].package,
+            _ => None,
+        },
+        _ => None,
+    };
+
+    let pkg = &resolve.packages[package];
+    let mut used = IndexSet::new();
+    for interface in pkg.interfaces.values() {
+        used.extend(
+            resolve.interfaces[*interface]
+                .types
+                .values()
+                .filter_map(|ty| type_package(*ty)),
+        );
+    }
+
+    for world in pkg.worlds.values() {
+        let world = &resolve.worlds[*world];
+        for item in world.imports.values().chain(world.exports.values()) {
+            used.extend(match item {
+                WorldItem::Interface(interface) => resolve.interfaces[*interface].package,
+                WorldItem::Type(ty) => type_package(*ty),
+                WorldItem::Function(_) => None,
+            });
+        }
+    }
+
+    used.shift_remove(&package);
+    used
+}
+
+/// Represents the WIT package dependency graph of a component package's
+/// target.
+pub struct PackageGraph {
+    /// The name of the target package, if the target has one.
+    pub root: Option<PackageName>,
+    /// The direct dependencies of each WIT package in the graph.
+    pub dependencies: IndexMap<PackageName, Vec<PackageName>>,
+}
+
+/// Resolves the WIT package dependency graph of the target of a package.
+///
+/// The graph is rooted at the local target package or at the package of
+/// the targeted world.
+pub fn target_package_graph(resolution: &PackageDependencyResolution) -> Result<PackageGraph> {
+    let metadata = resolution.metadata;
+    let dependency_world = BindingsEncoder::dependency_world(resolution)?;
+    let (resolve, root) = match metadata.target_path() {
+        Some(path) if !metadata.target_from_stdin() && dependency_world.is_none() => {
+            let (resolve, package, _) = BindingsEncoder::target_local_package(resolution, &path)?;
+            (resolve, Some(package))
+        }
+        _ => {
+            let encoder = BindingsEncoder::new(resolution)?;
+            let root = encoder.resolve.worlds[encoder.world].package;
+            (encoder.resolve, root)
+        }
+    };
+
+    let dependencies = resolve
+        .packages
+        .iter()
+        .map(|(id, package)| {
+            let mut deps: Vec<_> = direct_package_dependencies(&resolve, id)
+                .into_iter()
+                .map(|dep| resolve.packages[dep].name.clone())
+                .collect();
+            deps.sort_by_key(ToString::to_string);
+            (package.name.clone(), deps)
+        })
+        .collect();
+
+    Ok(PackageGraph {
+        root: root.map(|id| resolve.packages[id].name.clone()),
+        dependencies,
+    })
+}
+
+/// Checks that the package name of a local target matches the component
+/// package name given in the manifest, if any.
+///
+/// The versions of the packages are not compared.
+fn check_package_name(metadata: &ComponentMetadata, name: &PackageName) -> Result<()> {
+    let id = match &metadata.section.package {
+        Some(id) => id,
+        None => return Ok(()),
+    };
+
+    if id.namespace() != name.namespace || id.name() != name.name {
+        bail!(
+            "component package name `{id}` does not match WIT package `{ns}:{name}`",
+            ns = name.namespace,
+            name = name.name
+        );
+    }
+
+    Ok(())
+}
+
+fn wit_files(path: &Path) -> Result<Vec<PathBuf>> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(path)
+        .with_context(|| format!("failed to read directory `{path}`", path = path.display()))?
+    {
+        let path = entry?.path();
+        if path.is_file() && path.extension().and_then(|e| e.to_str()) == Some("wit") {
+            files.push(path);
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Parses the WIT packages in the `deps` directory of a directory
+/// dependency, including those in the `deps` directories of the packages.
+///
+/// Each entry of a `deps` directory is either a directory containing a
+/// package or a single WIT file.
+fn nested_wit_packages(path: &Path) -> Result<Vec<UnresolvedPackage>> {
+    let mut packages = Vec::new();
+    let deps = path.join("deps");
+    if !deps.is_dir() {
+        return Ok(packages);
+    }
+
+    let mut entries = Vec::new();
+    for entry in std::fs::read_dir(&deps)
+        .with_context(|| format!("failed to read directory `{path}`", path = deps.display()))?
+    {
+        entries.push(entry?.path());
+    }
+
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            packages.push(UnresolvedPackage::parse_dir(&path).with_context(|| {
+                format!("failed to parse dependency `{path}`", path = path.display())
+            })?);
+            packages.extend(nested_wit_packages(&path)?);
+        } else if path.extension().and_then(|e| e.to_str()) == Some("wit") {
+            packages.push(UnresolvedPackage::parse_file(&path).with_context(|| {
+                format!("failed to parse dependency `{path}`", path = path.display())
+            })?);
+        }
+    }
+
+    Ok(packages)
+}
+
+/// Represents a function exported by a target world.
+pub struct ExportedFunction {
+    /// The name of the generated trait that declares the function (e.g.
+    /// `Guest` or `GuestResource`).
+    pub trait_name: String,
+    /// The snake-cased name of the exported interface of the function, if
+    /// the function is exported from an interface.
+    pub module: Option<String>,
+    /// The name of the generated trait method for the function.
+    pub method: String,
+    /// The WIT export name of the function (e.g. `foo:bar/baz#qux`).
+    pub export: String,
+}
+
+impl ExportedFunction {
+    fn new(resolve: &Resolve, func: &Function, module: Option<String>, export: String) -> Self {
+        let resource = match func.kind {
+            FunctionKind::Freestanding => None,
+            FunctionKind::Method(id) | FunctionKind::Static(id) | FunctionKind::Constructor(id) => {
+                resolve.types[id].name.as_deref()
+            }
+        };
+
+        Self {
+            trait_name: format!(
+                "Guest{resource}",
+                resource = resource.unwrap_or_default().to_upper_camel_case()
+            ),
+            module,
+            method: match func.kind {
+                FunctionKind::Constructor(_) => "new".to_string(),
+                _ => to_rust_ident(func.item_name()),
+            },
+            export,
+        }
+    }
+}
+
 /// An encoder for bindings information.
 ///
 /// This type is responsible for encoding the target world
@@ -65,6 +484,31 @@ impl<'a> BindingsEncoder<'a> {
         })
     }
 
+    /// Gets the name of the target world.
+    pub fn world_name(&self) -> &str {
+        &self.resolve.worlds[self.world].name
+    }
+
+    /// Gets the fully-qualified name of the target world (e.g.
+    /// `component:foo/example`).
+    pub fn world_id(&self) -> String {
+        let world = &self.resolve.worlds[self.world];
+        match world.package {
+            Some(pkg) => format!(
+                "{pkg}/{world}",
+                pkg = self.resolve.packages[pkg].name,
+                world = world.name
+            ),
+            None => world.name.clone(),
+        }
+    }
+
+    /// Gets the paths of the local WIT files that the target world was
+    /// resolved from, including those of local target dependencies.
+    pub fn source_files(&self) -> &[PathBuf] {
+        &self.source_files
+    }
+
     /// Gets the cargo metadata for the package that the bindings are for.
     pub fn metadata(&self) -> &ComponentMetadata {
         self.resolution.metadata
@@ -74,29 +518,49 @@ impl<'a> BindingsEncoder<'a> {
     ///
     /// If this returns `Ok(None)`, then the bindings are up-to-date and
     /// do not need to be regenerated.
-    pub fn reason(&self, last_modified_output: SystemTime) -> Result<Option<&'static str>> {
+    ///
+    /// The `last_world` is the fully-qualified name of the world the
+    /// bindings were last generated for, if known.
+    pub fn reason(
+        &self,
+        last_modified_output: SystemTime,
+        last_world: Option<&str>,
+    ) -> Result<Option<String>> {
         let metadata = self.metadata();
-        let manifest_modified = metadata.modified_at > last_modified_output;
-        let target_modified = if let Some(path) = metadata.target_path() {
-            last_modified_time(&path)? > last_modified_output
-        } else {
-            false
-        };
+        if metadata.target_from_stdin() {
+            return Ok(Some("the target WIT was read from stdin".to_string()));
+        }
 
-        if manifest_modified
-            || target_modified
-            || self.dependencies_are_newer(last_modified_output)?
-        {
-            Ok(Some(if manifest_modified {
-                "the manifest was modified"
-            } else if target_modified {
-                "the target WIT file was modified"
-            } else {
-                "a dependency was modified"
-            }))
-        } else {
-            Ok(None)
+        if last_modified_output == SystemTime::UNIX_EPOCH {
+            return Ok(Some("the target has not been encoded yet".to_string()));
+        }
+
+        let world = self.world_id();
+        if let Some(last) = last_world {
+            if last != world {
+                return Ok(Some(format!(
+                    "the target world changed from `{last}` to `{world}`"
+                )));
+            }
+        }
+
+        if metadata.modified_at > last_modified_output {
+            return Ok(Some(format!(
+                "the manifest `{path}` was modified",
+                path = metadata.manifest_path.display()
+            )));
         }
+
+        if let Some(path) = metadata.target_path() {
+            if last_modified_time(&path)? > last_modified_output {
+                return Ok(Some(format!(
+                    "the target WIT `{path}` was modified",
+                    path = path.display()
+                )));
+            }
+        }
+
+        self.modified_dependency(last_modified_output)
     }
 
     /// Encodes the target world to a binary format.
@@ -127,69 +591,361 @@ impl<'a> BindingsEncoder<'a> {
         )
     }
 
-    fn dependencies_are_newer(&self, last_modified_output: SystemTime) -> Result<bool> {
+    /// Prints the package of the target world as WIT.
+    pub fn to_wit(&self) -> Result<String> {
+        let pkg_id = self.resolve.worlds[self.world]
+            .package
+            .context("world has no package")?;
+
+        WitPrinter::default().print(&self.resolve, pkg_id)
+    }
+
+    /// Creates a JSON representation of the target world.
+    ///
+    /// The representation describes the imports and exports of the world,
+    /// including their functions, types, and resources.
+    pub fn to_json(&self) -> serde_json::Value {
+        let resolve = &self.resolve;
+        let world = &resolve.worlds[self.world];
+        json!({
+            "package": world
+                .package
+                .map(|id| resolve.packages[id].name.to_string()),
+            "world": world.name,
+            "imports": world
+                .imports
+                .iter()
+                .map(|(key, item)| world_item_json(resolve, key, item))
+                .collect::<Vec<_>>(),
+            "exports": world
+                .exports
+                .iter()
+                .map(|(key, item)| world_item_json(resolve, key, item))
+                .collect::<Vec<_>>(),
+        })
+    }
+
+    /// Gets the target dependencies that the target package does not use.
+    ///
+    /// A dependency is used if the target package refers to its package,
+    /// either directly or through other dependencies.
+    pub fn unused_dependencies(&self) -> Result<Vec<&PackageId>> {
+        let resolve = &self.resolve;
+        let root = match resolve.worlds[self.world].package {
+            Some(package) => package,
+            None => return Ok(Vec::new()),
+        };
+
+        let owner_package = |owner: TypeOwner| match owner {
+            TypeOwner::Interface(id) => resolve.interfaces[id].package,
+            TypeOwner::World(id) => resolve.worlds[id].package,
+            TypeOwner::None => None,
+        };
+
+        // Find the packages that each package refers to
+        let mut references: HashMap<_, HashSet<_>> = HashMap::new();
+        for (_, ty) in resolve.types.iter() {
+            if let TypeDefKind::Type(Type::Id(other)) = &ty.kind {
+                if let (Some(from), Some(to)) = (
+                    owner_package(ty.owner),
+                    owner_package(resolve.types[*other].owner),
+                ) {
+                    references.entry(from).or_default().insert(to);
+                }
+            }
+        }
+
+        for (_, world) in resolve.worlds.iter() {
+            let from = match world.package {
+                Some(package) => package,
+                None => continue,
+            };
+
+            for item in world.imports.values().chain(world.exports.values()) {
+                if let WorldItem::Interface(id) = item {
+                    if let Some(to) = resolve.interfaces[*id].package {
+                        references.entry(from).or_default().insert(to);
+                    }
+                }
+            }
+        }
+
+        let mut used = HashSet::new();
+        let mut stack = vec![root];
+        while let Some(package) = stack.pop() {
+            if used.insert(package) {
+                stack.extend(references.get(&package).into_iter().flatten().copied());
+            }
+        }
+
+        let mut unused = Vec::new();
+        for (id, dependency) in &self.resolution.target_resolutions {
+            let decoded = dependency.decode()?;
+            match resolve.package_names.get(decoded.package_name()) {
+                Some(package) if used.contains(package) => {}
+                _ => unused.push(id),
+            }
+        }
+
+        Ok(unused)
+    }
+
+    /// Gets warnings for the deprecated items of the target world.
+    ///
+    /// An item is deprecated when a line of its doc comment starts with
+    /// `@deprecated`, optionally followed by a note (e.g.
+    /// `@deprecated use bar instead`).
+    pub fn deprecations(&self) -> Vec<String> {
+        let resolve = &self.resolve;
+        let world = &resolve.worlds[self.world];
+        let mut warnings = Vec::new();
+
+        let mut check = |docs: &Docs, item: String| {
+            if let Some(note) = deprecation_note(docs) {
+                warnings.push(if note.is_empty() {
+                    format!("{item} is deprecated")
+                } else {
+                    format!("{item} is deprecated: {note}")
+                });
+            }
+        };
+
+        for (key, item) in world.imports.iter().chain(world.exports.iter()) {
+            let name = world_key_name(resolve, key);
+            match item {
+                WorldItem::Interface(id) => {
+                    let interface = &resolve.interfaces[*id];
+                    check(&interface.docs, format!("interface `{name}`"));
+                    for (ty_name, ty) in &interface.types {
+                        check(
+                            &resolve.types[*ty].docs,
+                            format!("type `{ty_name}` of interface `{name}`"),
+                        );
+                    }
+                    for (func_name, func) in &interface.functions {
+                        check(
+                            &func.docs,
+                            format!("function `{func_name}` of interface `{name}`"),
+                        );
+                    }
+                }
+                WorldItem::Function(func) => check(&func.docs, format!("function `{name}`")),
+                WorldItem::Type(ty) => check(&resolve.types[*ty].docs, format!("type `{name}`")),
+            }
+        }
+
+        warnings
+    }
+
+    /// Gets the functions exported by the target world, including those of
+    /// exported interfaces and their resources.
+    pub fn exported_functions(&self) -> Vec<ExportedFunction> {
+        let resolve = &self.resolve;
+        let world = &resolve.worlds[self.world];
+        let mut functions = Vec::new();
+
+        for (key, item) in &world.exports {
+            match item {
+                WorldItem::Function(func) => functions.push(ExportedFunction::new(
+                    resolve,
+                    func,
+                    None,
+                    func.name.clone(),
+                )),
+                WorldItem::Interface(id) => {
+                    let interface = &resolve.interfaces[*id];
+                    let name = world_key_name(resolve, key);
+                    let module = match key {
+                        WorldKey::Name(name) => Some(name.as_str()),
+                        WorldKey::Interface(_) => interface.name.as_deref(),
+                    };
+
+                    for (func_name, func) in &interface.functions {
+                        functions.push(ExportedFunction::new(
+                            resolve,
+                            func,
+                            module.map(ToSnakeCase::to_snake_case),
+                            format!("{name}#{func_name}"),
+                        ));
+                    }
+                }
+                WorldItem::Type(_) => {}
+            }
+        }
+
+        functions
+    }
+
+    fn modified_dependency(&self, last_modified_output: SystemTime) -> Result<Option<String>> {
         for dep in &self.source_files {
             if last_modified_time(dep)? > last_modified_output {
-                log::debug!(
-                    "target source file `{path}` has been modified",
+                return Ok(Some(format!(
+                    "the target source file `{path}` was modified",
                     path = dep.display()
-                );
-                return Ok(true);
+                )));
             }
         }
 
-        for (_, dep) in self.resolution.all() {
+        for (id, dep) in self.resolution.all() {
             if last_modified_time(dep.path())? > last_modified_output {
-                log::debug!(
-                    "dependency `{path}` has been modified",
+                return Ok(Some(format!(
+                    "the dependency `{id}` at `{path}` was modified",
                     path = dep.path().display()
-                );
-                return Ok(true);
+                )));
             }
         }
 
-        Ok(false)
+        Ok(None)
     }
 
     fn create_target_world(
         resolution: &PackageDependencyResolution,
     ) -> Result<(Resolve, WorldId, Vec<PathBuf>)> {
+        let dependency_world = Self::dependency_world(resolution)?;
         let (mut merged, world_id, source_files) =
             if let Target::Package { id, world, .. } = &resolution.metadata.section.target {
                 Self::target_package(resolution, id, world.as_deref())?
+            } else if let Some((id, world)) = dependency_world {
+                Self::target_dependency(resolution, id, world)?
             } else if let Some(path) = resolution.metadata.target_path() {
-                Self::target_local_path(
+                let (merged, world, source_files) = Self::target_local_path(
                     resolution,
                     &path,
                     resolution.metadata.section.target.world(),
-                )?
+                )?;
+
+                let package = merged.worlds[world]
+                    .package
+                    .context("world has no package")?;
+                check_package_name(resolution.metadata, &merged.packages[package].name)?;
+                (merged, world, source_files)
             } else {
                 let (merged, world) = Self::target_empty_world(resolution);
                 (merged, world, Vec::new())
             };
 
+        // Select the worlds of specific cargo targets from the same package
+        let mut worlds = vec![world_id];
+        let package = merged.worlds[world_id].package;
+        for name in resolution.metadata.section.target_worlds.values() {
+            let id = select_world(
+                &merged,
+                package.context("world has no package")?,
+                Some(name),
+            )
+            .with_context(|| format!("failed to select target world `{name}`"))?;
+            if !worlds.contains(&id) {
+                worlds.push(id);
+            }
+        }
+
         // Merge all component dependencies as interface imports
         for (id, dependency) in &resolution.resolutions {
+            // The dependency providing the target world is not imported
+            if dependency_world.map(|(target, _)| target) == Some(id) {
+                continue;
+            }
+
             let (mut resolve, component_world_id) = dependency
                 .decode()?
                 .into_component_world()
                 .with_context(|| format!("failed to decode component dependency `{id}`"))?;
 
+            let source = format!("component dependency `{id}`");
+            for (package_id, package) in resolve.packages.iter() {
+                if let Some(existing) = merged.package_names.get(&package.name) {
+                    check_conflicting_interfaces(
+                        (&merged, *existing, "the target"),
+                        (&resolve, package_id, &source),
+                    )?;
+                }
+            }
+
             // Set the world name as currently it defaults to "root"
             // For now, set it to the name from the id
-            let world = &mut resolve.worlds[component_world_id];
-            world.name = id.name().to_string();
+            //
+            // The world is also renamed in its package so that merging does
+            // not map it onto the world of the same component decoded as a
+            // target dependency; interfaces and types are still merged with
+            // the existing ones so that the target's uses of the dependency's
+            // types refer to the same definitions as the imported functions
+            let pkg = resolve.worlds[component_world_id]
+                .package
+                .context("world has no package")?;
+            let name = id.name().to_string();
+            let old = std::mem::replace(&mut resolve.worlds[component_world_id].name, name.clone());
+            let pkg_worlds = &mut resolve.packages[pkg].worlds;
+            pkg_worlds.shift_remove(&old);
+            pkg_worlds.insert(name, component_world_id);
 
             let source = merged
                 .merge(resolve)
                 .with_context(|| format!("failed to merge world of dependency `{id}`"))?
                 .worlds[component_world_id.index()];
-            Self::import_world(&mut merged, source, world_id)?;
+            for world in &worlds {
+                Self::import_world(&mut merged, source, *world)?;
+            }
         }
 
         Ok((merged, world_id, source_files))
     }
 
+    /// Gets the component dependency and world name of a local target world
+    /// given as `<dependency>/<world>` (e.g. `my:comp/example`).
+    fn dependency_world<'b>(
+        resolution: &'b PackageDependencyResolution,
+    ) -> Result<Option<(&'b PackageId, &'b str)>> {
+        let world = match &resolution.metadata.section.target {
+            Target::Local {
+                world: Some(world), ..
+            } => world,
+            _ => return Ok(None),
+        };
+
+        let (id, world) = match split_qualified_world(world)? {
+            Some(parts) => parts,
+            None => return Ok(None),
+        };
+
+        Ok(resolution
+            .resolutions
+            .keys()
+            .find(|dependency| dependency.to_string() == id)
+            .map(|dependency| (dependency, world)))
+    }
+
+    fn target_dependency(
+        resolution: &PackageDependencyResolution,
+        id: &PackageId,
+        world: &str,
+    ) -> Result<(Resolve, WorldId, Vec<PathBuf>)> {
+        match resolution.resolutions[id].decode()? {
+            DecodedDependency::Wasm {
+                decoded: DecodedWasm::Component(mut resolve, world_id),
+                ..
+            } => {
+                // The world of a component is unnamed, so use the target's name
+                let pkg = resolve.worlds[world_id]
+                    .package
+                    .context("world has no package")?;
+                let old = std::mem::replace(&mut resolve.worlds[world_id].name, world.to_string());
+                let pkg_worlds = &mut resolve.packages[pkg].worlds;
+                pkg_worlds.shift_remove(&old);
+                pkg_worlds.insert(world.to_string(), world_id);
+                Ok((resolve, world_id, Vec::new()))
+            }
+            decoded => {
+                let (resolve, pkg, source_files) = decoded
+                    .resolve()
+                    .with_context(|| format!("failed to resolve dependency `{id}`"))?;
+                let world = resolve
+                    .select_world(pkg, Some(world))
+                    .with_context(|| format!("failed to select world from dependency `{id}`"))?;
+                Ok((resolve, world, source_files))
+            }
+        }
+    }
+
     fn target_package(
         resolution: &PackageDependencyResolution,
         id: &PackageId,
@@ -207,8 +963,7 @@ impl<'a> BindingsEncoder<'a> {
             )
         })?;
 
-        let world = resolve
-            .select_world(pkg, world)
+        let world = select_world(&resolve, pkg, world)
             .with_context(|| format!("failed to select world from target package `{id}`"))?;
 
         Ok((resolve, world, source_files))
@@ -219,25 +974,74 @@ impl<'a> BindingsEncoder<'a> {
         path: &Path,
         world: Option<&str>,
     ) -> Result<(Resolve, WorldId, Vec<PathBuf>)> {
+        let (merged, package, source_files) = Self::target_local_package(resolution, path)?;
+
+        let world = select_world(&merged, package, world).with_context(|| match world {
+            Some(world) => {
+                format!(
+                    "failed to select the specified world `{world}` for local target `{path}`",
+                    path = path.display()
+                )
+            }
+            None => format!(
+                "failed to select the default world to use for local target `{path}`",
+                path = path.display()
+            ),
+        })?;
+
+        Ok((merged, world, source_files))
+    }
+
+    fn target_local_package(
+        resolution: &PackageDependencyResolution,
+        path: &Path,
+    ) -> Result<(Resolve, wit_parser::PackageId, Vec<PathBuf>)> {
         let mut merged = Resolve::default();
 
         // Start by decoding all of the target dependencies
         let mut deps = IndexMap::new();
+        let mut nested = Vec::new();
         for (id, resolution) in &resolution.target_resolutions {
             let decoded = resolution.decode()?;
             let name = decoded.package_name();
 
+            // A directory dependency brings in the packages of its `deps` tree
+            if resolution.path().is_dir() {
+                for package in nested_wit_packages(resolution.path())? {
+                    nested.push(DecodedDependency::Wit {
+                        resolution,
+                        package,
+                    });
+                }
+            }
+
             if let Some(prev) = deps.insert(name.clone(), decoded) {
                 bail!("duplicate definitions of package `{name}` found while decoding target dependency `{id}`", name = prev.package_name());
             }
         }
 
+        // Packages specified as target dependencies take precedence over
+        // those found in the `deps` tree of a dependency
+        for dep in nested {
+            deps.entry(dep.package_name().clone()).or_insert(dep);
+        }
+
         // Parse the target package itself
-        let root = if path.is_dir() {
+        let from_stdin = path == Path::new(STDIN_TARGET_PATH);
+        let root = if from_stdin {
+            let mut contents = String::new();
+            std::io::stdin()
+                .read_to_string(&mut contents)
+                .context("failed to read local target from stdin")?;
+
+            UnresolvedPackage::parse(Path::new("<stdin>"), &contents)
+                .context("failed to parse local target from stdin")?
+        } else if path.is_dir() {
             UnresolvedPackage::parse_dir(path).with_context(|| {
                 format!(
-                    "failed to parse local target from directory `{}`",
-                    path.display()
+                    "failed to parse the local target of package `{name}` from directory `{path}`",
+                    name = resolution.metadata.name,
+                    path = path.display()
                 )
             })?
         } else {
@@ -249,7 +1053,12 @@ impl<'a> BindingsEncoder<'a> {
             })?
         };
 
-        let mut source_files: Vec<_> = root.source_files().map(Path::to_path_buf).collect();
+        // A target read from stdin has no source files to track
+        let mut source_files: Vec<_> = if from_stdin {
+            Vec::new()
+        } else {
+            root.source_files().map(Path::to_path_buf).collect()
+        };
 
         // Do a topological sort of the dependencies
         let mut order = IndexSet::new();
@@ -260,20 +1069,46 @@ impl<'a> BindingsEncoder<'a> {
 
         assert!(visiting.is_empty());
 
-        // Merge all of the dependencies first
+        // Merge all of the dependencies first, keeping track of the
+        // dependency that defined each package
+        let mut sources: HashMap<PackageName, String> = HashMap::new();
         for name in order {
             match deps.remove(&name).unwrap() {
                 DecodedDependency::Wit {
                     resolution,
                     package,
                 } => {
+                    let source = format!("target dependency `{id}`", id = resolution.id());
+                    if let Some(missing) = package
+                        .foreign_deps
+                        .keys()
+                        .find(|name| !merged.package_names.contains_key(*name))
+                    {
+                        bail!(
+                            "package `{missing}` used by package `{name}` of {source} was not found; \
+                             add it as a target dependency or to a `deps` directory of the dependency",
+                            name = package.name
+                        );
+                    }
+
+                    let name = package.name.clone();
+                    let existing = merged.package_names.get(&name).copied();
                     source_files.extend(package.source_files().map(Path::to_path_buf));
-                    merged.push(package).with_context(|| {
+                    let id = merged.push(package).with_context(|| {
                         format!(
                             "failed to merge target dependency `{id}`",
                             id = resolution.id()
                         )
                     })?;
+
+                    if let Some(existing) = existing {
+                        check_conflicting_interfaces(
+                            (&merged, existing, sources[&name].as_str()),
+                            (&merged, id, &source),
+                        )?;
+                    }
+
+                    sources.entry(name).or_insert(source);
                 }
                 DecodedDependency::Wasm {
                     resolution,
@@ -284,12 +1119,32 @@ impl<'a> BindingsEncoder<'a> {
                         DecodedWasm::Component(resolve, _) => resolve,
                     };
 
+                    let source = format!("target dependency `{id}`", id = resolution.id());
+                    for (id, package) in resolve.packages.iter() {
+                        if let Some(existing) = merged.package_names.get(&package.name) {
+                            check_conflicting_interfaces(
+                                (&merged, *existing, sources[&package.name].as_str()),
+                                (&resolve, id, &source),
+                            )?;
+                        }
+                    }
+
+                    let names: Vec<_> = resolve
+                        .packages
+                        .iter()
+                        .map(|(_, p)| p.name.clone())
+                        .collect();
+
                     merged.merge(resolve).with_context(|| {
                         format!(
                             "failed to merge world of target dependency `{id}`",
                             id = resolution.id()
                         )
                     })?;
+
+                    for name in names {
+                        sources.entry(name).or_insert_with(|| source.clone());
+                    }
                 }
             }
         }
@@ -301,22 +1156,7 @@ impl<'a> BindingsEncoder<'a> {
             )
         })?;
 
-        let world = merged
-            .select_world(package, world)
-            .with_context(|| match world {
-                Some(world) => {
-                    format!(
-                        "failed to select the specified world `{world}` for local target `{path}`",
-                        path = path.display()
-                    )
-                }
-                None => format!(
-                    "failed to select the default world to use for local target `{path}`",
-                    path = path.display()
-                ),
-            })?;
-
-        return Ok((merged, world, source_files));
+        return Ok((merged, package, source_files));
 
         fn visit<'a>(
             dep: &'a DecodedDependency<'a>,
@@ -339,9 +1179,8 @@ impl<'a> BindingsEncoder<'a> {
                             bail!("foreign dependency `{name}` forms a dependency cycle while parsing target dependency `{id}`", id = resolution.id());
                         }
 
-                        // Only visit known dependencies
-                        // wit-parser will error on unknown foreign dependencies when
-                        // the package is resolved
+                        // Only visit known dependencies; unknown foreign
+                        // dependencies are reported when the package is merged
                         if let Some(dep) = deps.get(name) {
                             visit(dep, deps, order, visiting)?
                         }
@@ -476,3 +1315,405 @@ impl<'a> BindingsEncoder<'a> {
         Ok(())
     }
 }
+
+/// Generates the Rust bindings for a world of an encoded target.
+///
+/// The bindings are generated as the `generate!` macro generates them with
+/// its default options: world and interface exports are implemented by
+/// `Component` and exported resources by the type of the same name. The
+/// additions the macro makes to the bindings, such as the `imports` module,
+/// are not included.
+pub fn generate_rust_bindings(
+    encoded: &[u8],
+    world: &str,
+    runtime_path: Option<&str>,
+) -> Result<String> {
+    let (resolve, world) =
+        match wit_component::decode(encoded).context("failed to decode the encoded target")? {
+            DecodedWasm::WitPackage(resolve, pkg) => {
+                let world = resolve.select_world(pkg, Some(world))?;
+                (resolve, world)
+            }
+            DecodedWasm::Component(..) => bail!("the encoded target is not a WIT package"),
+        };
+
+    let implementor = "super::Component".to_string();
+    let mut exports = HashMap::new();
+    exports.insert(ExportKey::World, implementor.clone());
+
+    for (key, item) in &resolve.worlds[world].exports {
+        let key = match key {
+            WorldKey::Name(name) => name.clone(),
+            WorldKey::Interface(id) => {
+                let interface = &resolve.interfaces[*id];
+                let package =
+                    &resolve.packages[interface.package.context("interface has no package")?];
+                format!(
+                    "{ns}:{pkg}/{name}",
+                    ns = package.name.namespace,
+                    pkg = package.name.name,
+                    name = interface.name.as_deref().context("interface has no name")?
+                )
+            }
+        };
+
+        match item {
+            WorldItem::Interface(id) => {
+                for (name, ty) in &resolve.interfaces[*id].types {
+                    if let TypeDefKind::Resource = resolve.types[*ty].kind {
+                        exports.insert(
+                            ExportKey::Name(format!("{key}/{name}")),
+                            format!("super::{name}", name = name.to_upper_camel_case()),
+                        );
+                    }
+                }
+            }
+            WorldItem::Type(id) => {
+                if let TypeDefKind::Resource = resolve.types[*id].kind {
+                    exports.insert(
+                        ExportKey::Name(key.clone()),
+                        format!("super::{name}", name = key.to_upper_camel_case()),
+                    );
+                }
+
+                continue;
+            }
+            WorldItem::Function(_) => {}
+        }
+
+        exports.insert(ExportKey::Name(key), implementor.clone());
+    }
+
+    let opts = Opts {
+        exports,
+        runtime_path: Some(
+            runtime_path
+                .unwrap_or("::cargo_component_bindings::rt")
+                .to_string(),
+        ),
+        bitflags_path: Some("::cargo_component_bindings::bitflags".to_string()),
+        ..Default::default()
+    };
+
+    let mut files = Files::default();
+    opts.build().generate(&resolve, world, &mut files)?;
+
+    let mut source = String::new();
+    for (_, contents) in files.iter() {
+        source.push_str(std::str::from_utf8(contents).context("bindings are not UTF-8")?);
+    }
+
+    Ok(source)
+}
+
+/// Prints the WIT of the package of an encoded component or WIT package.
+///
+/// For a component, this is the package of the component's world as it was
+/// decoded from the component's type.
+pub fn decode_wit(bytes: &[u8]) -> Result<String> {
+    let (resolve, pkg) = match wit_component::decode(bytes).context("failed to decode component")? {
+        DecodedWasm::WitPackage(resolve, pkg) => (resolve, pkg),
+        DecodedWasm::Component(resolve, world) => {
+            let pkg = resolve.worlds[world]
+                .package
+                .context("world has no package")?;
+            (resolve, pkg)
+        }
+    };
+
+    WitPrinter::default().print(&resolve, pkg)
+}
+
+/// Selects a world of the given package.
+///
+/// The world may be named with a fully-qualified `<package>/<world>` name
+/// (e.g. `component:foo/example`) to select a world of any package in the
+/// resolve; the package version may be omitted.
+///
+/// If no world is named, the package must contain exactly one world.
+fn select_world(
+    resolve: &Resolve,
+    package: wit_parser::PackageId,
+    world: Option<&str>,
+) -> Result<WorldId> {
+    let (package, world) = match world.map(split_qualified_world).transpose()?.flatten() {
+        Some((name, world)) => {
+            let package = resolve
+                .packages
+                .iter()
+                .find(|(_, p)| {
+                    p.name.to_string() == name
+                        || format!("{ns}:{pkg}", ns = p.name.namespace, pkg = p.name.name) == name
+                })
+                .map(|(id, _)| id)
+                .with_context(|| {
+                    format!(
+                        "package `{name}` was not found; available packages: {packages}",
+                        packages = resolve
+                            .packages
+                            .iter()
+                            .map(|(_, p)| format!("`{name}`", name = p.name))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                })?;
+            (package, Some(world))
+        }
+        None => (package, world),
+    };
+
+    let pkg = &resolve.packages[package];
+    let available = || {
+        pkg.worlds
+            .keys()
+            .map(|world| format!("`{name}/{world}`", name = pkg.name))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    match world {
+        Some(world) => pkg.worlds.get(world).copied().with_context(|| {
+            format!(
+                "world `{world}` was not found in package `{name}`; available worlds: {worlds}",
+                name = pkg.name,
+                worlds = available()
+            )
+        }),
+        None => match pkg.worlds.len() {
+            0 => bail!("package `{name}` contains no worlds", name = pkg.name),
+            1 => Ok(pkg.worlds[0]),
+            _ => bail!(
+                "package `{name}` contains multiple worlds; specify one with `target.world`: {worlds}",
+                name = pkg.name,
+                worlds = available()
+            ),
+        },
+    }
+}
+
+/// Checks that the interfaces of a package do not conflict with the
+/// interfaces of the same name in an existing package of the same name.
+///
+/// Each package is given with the resolve that contains it and a description
+/// of the dependency that defined it.
+fn check_conflicting_interfaces(
+    existing: (&Resolve, wit_parser::PackageId, &str),
+    package: (&Resolve, wit_parser::PackageId, &str),
+) -> Result<()> {
+    let (existing_resolve, existing_id, existing_source) = existing;
+    let (resolve, id, source) = package;
+    let existing_package = &existing_resolve.packages[existing_id];
+    let package = &resolve.packages[id];
+
+    for (name, interface) in &package.interfaces {
+        let existing_interface = match existing_package.interfaces.get(name) {
+            Some(interface) => *interface,
+            None => continue,
+        };
+
+        let definition = world_item_json(
+            resolve,
+            &WorldKey::Interface(*interface),
+            &WorldItem::Interface(*interface),
+        );
+        let existing_definition = world_item_json(
+            existing_resolve,
+            &WorldKey::Interface(existing_interface),
+            &WorldItem::Interface(existing_interface),
+        );
+
+        if definition != existing_definition {
+            bail!(
+                "conflicting definitions of interface `{package}/{name}` found\n\n\
+                 definition from {existing_source}: {existing_definition}\n\
+                 definition from {source}: {definition}",
+                package = package.name,
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn deprecation_note(docs: &Docs) -> Option<&str> {
+    docs.contents
+        .as_deref()?
+        .lines()
+        .find_map(|l| l.trim().strip_prefix("@deprecated"))
+        .map(str::trim)
+}
+
+pub(crate) fn world_key_name(resolve: &Resolve, key: &WorldKey) -> String {
+    match key {
+        WorldKey::Name(name) => name.clone(),
+        WorldKey::Interface(id) => resolve
+            .id_of(*id)
+            .or_else(|| resolve.interfaces[*id].name.clone())
+            .unwrap_or_default(),
+    }
+}
+
+fn world_item_json(resolve: &Resolve, key: &WorldKey, item: &WorldItem) -> serde_json::Value {
+    let name = world_key_name(resolve, key);
+    match item {
+        WorldItem::Interface(id) => {
+            let interface = &resolve.interfaces[*id];
+            json!({
+                "name": name,
+                "kind": "interface",
+                "types": interface
+                    .types
+                    .values()
+                    .map(|id| type_def_json(resolve, *id))
+                    .collect::<Vec<_>>(),
+                "functions": interface
+                    .functions
+                    .values()
+                    .map(|f| function_json(resolve, f))
+                    .collect::<Vec<_>>(),
+            })
+        }
+        WorldItem::Function(f) => {
+            let mut value = function_json(resolve, f);
+            value["kind"] = json!("function");
+            value
+        }
+        WorldItem::Type(id) => type_def_json(resolve, *id),
+    }
+}
+
+fn function_json(resolve: &Resolve, function: &Function) -> serde_json::Value {
+    let (kind, resource) = match &function.kind {
+        FunctionKind::Freestanding => ("freestanding", None),
+        FunctionKind::Method(id) => ("method", resolve.types[*id].name.as_deref()),
+        FunctionKind::Static(id) => ("static", resolve.types[*id].name.as_deref()),
+        FunctionKind::Constructor(id) => ("constructor", resolve.types[*id].name.as_deref()),
+    };
+
+    let results: Vec<_> = match &function.results {
+        Results::Named(results) => results
+            .iter()
+            .map(|(name, ty)| json!({ "name": name, "type": type_name(resolve, ty) }))
+            .collect(),
+        Results::Anon(ty) => vec![json!({ "type": type_name(resolve, ty) })],
+    };
+
+    json!({
+        "name": function.name,
+        "function-kind": kind,
+        "resource": resource,
+        "params": function
+            .params
+            .iter()
+            .map(|(name, ty)| json!({ "name": name, "type": type_name(resolve, ty) }))
+            .collect::<Vec<_>>(),
+        "results": results,
+    })
+}
+
+fn type_def_json(resolve: &Resolve, id: TypeId) -> serde_json::Value {
+    let ty = &resolve.types[id];
+    let mut value = json!({ "name": ty.name });
+    match &ty.kind {
+        TypeDefKind::Record(r) => {
+            value["kind"] = json!("record");
+            value["fields"] = r
+                .fields
+                .iter()
+                .map(|f| json!({ "name": f.name, "type": type_name(resolve, &f.ty) }))
+                .collect();
+        }
+        TypeDefKind::Variant(v) => {
+            value["kind"] = json!("variant");
+            value["cases"] = v
+                .cases
+                .iter()
+                .map(|c| {
+                    json!({
+                        "name": c.name,
+                        "type": c.ty.as_ref().map(|ty| type_name(resolve, ty)),
+                    })
+                })
+                .collect();
+        }
+        TypeDefKind::Enum(e) => {
+            value["kind"] = json!("enum");
+            value["cases"] = e.cases.iter().map(|c| json!(c.name)).collect();
+        }
+        TypeDefKind::Flags(f) => {
+            value["kind"] = json!("flags");
+            value["flags"] = f.flags.iter().map(|f| json!(f.name)).collect();
+        }
+        TypeDefKind::Resource => {
+            value["kind"] = json!("resource");
+        }
+        TypeDefKind::Type(t) => {
+            value["kind"] = json!("alias");
+            value["type"] = json!(type_name(resolve, t));
+        }
+        _ => {
+            value["kind"] = json!("alias");
+            value["type"] = json!(anonymous_type_name(resolve, ty));
+        }
+    }
+
+    value
+}
+
+fn type_name(resolve: &Resolve, ty: &Type) -> String {
+    match ty {
+        Type::Bool => "bool".to_string(),
+        Type::U8 => "u8".to_string(),
+        Type::U16 => "u16".to_string(),
+        Type::U32 => "u32".to_string(),
+        Type::U64 => "u64".to_string(),
+        Type::S8 => "s8".to_string(),
+        Type::S16 => "s16".to_string(),
+        Type::S32 => "s32".to_string(),
+        Type::S64 => "s64".to_string(),
+        Type::Float32 => "float32".to_string(),
+        Type::Float64 => "float64".to_string(),
+        Type::Char => "char".to_string(),
+        Type::String => "string".to_string(),
+        Type::Id(id) => {
+            let ty = &resolve.types[*id];
+            match &ty.name {
+                Some(name) => name.clone(),
+                None => anonymous_type_name(resolve, ty),
+            }
+        }
+    }
+}
+
+fn anonymous_type_name(resolve: &Resolve, ty: &TypeDef) -> String {
+    let resource_name = |id: &TypeId| resolve.types[*id].name.clone().unwrap_or_default();
+    match &ty.kind {
+        TypeDefKind::List(t) => format!("list<{t}>", t = type_name(resolve, t)),
+        TypeDefKind::Option(t) => format!("option<{t}>", t = type_name(resolve, t)),
+        TypeDefKind::Result(r) => match (&r.ok, &r.err) {
+            (None, None) => "result".to_string(),
+            (Some(ok), None) => format!("result<{ok}>", ok = type_name(resolve, ok)),
+            (None, Some(err)) => format!("result<_, {err}>", err = type_name(resolve, err)),
+            (Some(ok), Some(err)) => format!(
+                "result<{ok}, {err}>",
+                ok = type_name(resolve, ok),
+                err = type_name(resolve, err)
+            ),
+        },
+        TypeDefKind::Tuple(t) => format!(
+            "tuple<{types}>",
+            types = t
+                .types
+                .iter()
+                .map(|t| type_name(resolve, t))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        TypeDefKind::Handle(Handle::Own(id)) => format!("own<{name}>", name = resource_name(id)),
+        TypeDefKind::Handle(Handle::Borrow(id)) => {
+            format!("borrow<{name}>", name = resource_name(id))
+        }
+        TypeDefKind::Type(t) => type_name(resolve, t),
+        _ => "unknown".to_string(),
+    }
+}
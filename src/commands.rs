@@ -1,13 +1,31 @@
 //! Commands for the `cargo-component` CLI.
 
 mod add;
+mod bindings;
+mod doctor;
+mod expand;
+mod fmt;
 mod key;
+mod migrate;
 mod new;
 mod publish;
+mod tree;
 mod update;
+mod vendor_wit;
+mod verify;
+mod wit;
 
 pub use self::add::*;
+pub use self::bindings::*;
+pub use self::doctor::*;
+pub use self::expand::*;
+pub use self::fmt::*;
 pub use self::key::*;
+pub use self::migrate::*;
 pub use self::new::*;
 pub use self::publish::*;
+pub use self::tree::*;
 pub use self::update::*;
+pub use self::vendor_wit::*;
+pub use self::verify::*;
+pub use self::wit::*;
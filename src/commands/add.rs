@@ -59,6 +59,10 @@ pub struct AddCommand {
     /// Add a package dependency to a file or directory.
     #[clap(long = "path", value_name = "PATH")]
     pub path: Option<PathBuf>,
+
+    /// Replace an existing dependency with the same id
+    #[clap(long = "force")]
+    pub force: bool,
 }
 
 impl AddCommand {
@@ -255,13 +259,13 @@ impl AddCommand {
                     bail!("cannot add dependency `{id}` to a registry package target")
                 }
                 Target::Local { dependencies, .. } => {
-                    if dependencies.contains_key(id) {
-                        bail!("cannot add dependency `{id}` as it conflicts with an existing dependency");
+                    if !self.force && dependencies.contains_key(id) {
+                        bail!("cannot add dependency `{id}` as it conflicts with an existing dependency; use `--force` to replace it");
                     }
                 }
             }
-        } else if metadata.section.dependencies.contains_key(id) {
-            bail!("cannot add dependency `{id}` as it conflicts with an existing dependency");
+        } else if !self.force && metadata.section.dependencies.contains_key(id) {
+            bail!("cannot add dependency `{id}` as it conflicts with an existing dependency; use `--force` to replace it");
         }
 
         Ok(())
@@ -0,0 +1,139 @@
+use crate::{
+    config::{CargoArguments, CargoPackageSpec, Config},
+    find_package, generate_bindings, load_component_metadata, load_metadata,
+    PackageComponentMetadata,
+};
+use anyhow::{bail, Context, Result};
+use cargo_component_core::command::CommonOptions;
+use clap::Args;
+use std::{fs, path::PathBuf};
+
+/// Print the Rust bindings of a component package generated with the default
+/// `generate!` options.
+///
+/// Options given to `generate!` and the bindings options of the manifest are
+/// not applied; use `cargo component expand` to print the bindings exactly as
+/// the macro generates them.
+#[derive(Args)]
+#[clap(disable_version_flag = true)]
+pub struct BindingsCommand {
+    /// The common command options.
+    #[clap(flatten)]
+    pub common: CommonOptions,
+
+    /// Cargo package to generate the bindings of (see `cargo help pkgid`)
+    #[clap(long = "package", short = 'p', value_name = "SPEC")]
+    pub cargo_packages: Vec<CargoPackageSpec>,
+
+    /// Generate the bindings of all component packages in the workspace
+    #[clap(long = "workspace")]
+    pub workspace: bool,
+
+    /// Path to Cargo.toml
+    #[clap(long = "manifest-path", value_name = "PATH")]
+    pub manifest_path: Option<PathBuf>,
+
+    /// Generate the bindings of the given binary target
+    #[clap(long = "bin", value_name = "NAME")]
+    pub bin: Option<String>,
+
+    /// Write the bindings to the given file instead of stdout
+    #[clap(long = "out", value_name = "PATH")]
+    pub out: Option<PathBuf>,
+
+    /// Space or comma separated list of features to activate
+    #[clap(long = "features", value_name = "FEATURES")]
+    pub features: Vec<String>,
+
+    /// Activate all available features
+    #[clap(long = "all-features")]
+    pub all_features: bool,
+
+    /// Do not activate the `default` feature
+    #[clap(long = "no-default-features")]
+    pub no_default_features: bool,
+
+    /// Run without accessing the network
+    #[clap(long = "offline")]
+    pub offline: bool,
+}
+
+impl BindingsCommand {
+    /// Executes the command.
+    pub async fn exec(self) -> Result<()> {
+        log::debug!("executing bindings command");
+
+        let config = Config::new(self.common.new_terminal())?;
+        let metadata = load_metadata(self.manifest_path.as_deref())?;
+        let packages = if self.workspace || self.cargo_packages.len() > 1 {
+            load_component_metadata(&metadata, self.cargo_packages.iter(), self.workspace)?
+                .into_iter()
+                .filter(|p| p.metadata.is_some())
+                .collect()
+        } else {
+            vec![PackageComponentMetadata::new(find_package(
+                &metadata,
+                self.cargo_packages.first(),
+            )?)?]
+        };
+
+        if packages.len() > 1 {
+            if self.out.is_some() {
+                bail!("`--out` cannot be used when generating the bindings of multiple packages");
+            }
+
+            if self.bin.is_some() {
+                bail!("`--bin` cannot be used when generating the bindings of multiple packages");
+            }
+        }
+
+        let cargo_args = CargoArguments {
+            manifest_path: self.manifest_path.clone(),
+            offline: self.offline,
+            features: self
+                .features
+                .iter()
+                .flat_map(|f| f.split([' ', ',']))
+                .filter(|f| !f.is_empty())
+                .map(ToString::to_string)
+                .collect(),
+            all_features: self.all_features,
+            no_default_features: self.no_default_features,
+            ..Default::default()
+        };
+
+        for package in &packages {
+            let bindings = generate_bindings(
+                &config,
+                &metadata,
+                package,
+                self.bin.as_deref(),
+                &cargo_args,
+            )
+            .await?;
+
+            match &self.out {
+                Some(path) => {
+                    fs::write(path, bindings).with_context(|| {
+                        format!(
+                            "failed to write bindings to `{path}`",
+                            path = path.display()
+                        )
+                    })?;
+
+                    config.terminal().status(
+                        "Generated",
+                        format!(
+                            "bindings for {name} ({path})",
+                            name = package.package.name,
+                            path = path.display()
+                        ),
+                    )?;
+                }
+                None => config.terminal().write_stdout(bindings, None)?,
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,212 @@
+use crate::{
+    config::{CargoPackageSpec, Config},
+    load_component_metadata, load_metadata,
+    metadata::{Adapter, ComponentMetadata, Target},
+    target::is_target_installed,
+    PackageComponentMetadata, DEFAULT_WASM_TARGET,
+};
+use anyhow::{bail, Result};
+use cargo_component_core::command::CommonOptions;
+use cargo_metadata::Package;
+use clap::Args;
+use std::{fs, path::PathBuf};
+use wit_parser::UnresolvedPackage;
+
+/// The name of the crate that provides the `generate!` macro.
+const BINDINGS_CRATE_NAME: &str = "cargo-component-bindings";
+
+/// Diagnose common problems with the setup of component packages.
+#[derive(Args)]
+#[clap(disable_version_flag = true)]
+pub struct DoctorCommand {
+    /// The common command options.
+    #[clap(flatten)]
+    pub common: CommonOptions,
+
+    /// Cargo package to diagnose (see `cargo help pkgid`)
+    #[clap(long = "package", short = 'p', value_name = "SPEC")]
+    pub cargo_packages: Vec<CargoPackageSpec>,
+
+    /// Diagnose all packages in the workspace
+    #[clap(long = "workspace")]
+    pub workspace: bool,
+
+    /// Path to Cargo.toml
+    #[clap(long = "manifest-path", value_name = "PATH")]
+    pub manifest_path: Option<PathBuf>,
+
+    /// The target triple to check for (defaults to `wasm32-wasi`)
+    #[clap(long = "target", value_name = "TRIPLE")]
+    pub target: Option<String>,
+}
+
+impl DoctorCommand {
+    /// Executes the command.
+    pub async fn exec(self) -> Result<()> {
+        log::debug!("executing doctor command");
+
+        let config = Config::new(self.common.new_terminal())?;
+        let metadata = load_metadata(self.manifest_path.as_deref())?;
+        let packages =
+            load_component_metadata(&metadata, self.cargo_packages.iter(), self.workspace)?;
+
+        let mut findings = Vec::new();
+        let target = self.target.as_deref().unwrap_or(DEFAULT_WASM_TARGET);
+        if !is_target_installed(target)? {
+            findings.push(format!(
+                "the `{target}` target is not installed; \
+                 install it with `rustup target add {target}`"
+            ));
+        }
+
+        let mut components = 0;
+        for PackageComponentMetadata { package, metadata } in &packages {
+            if let Some(metadata) = metadata {
+                components += 1;
+                diagnose_package(package, metadata, &mut findings);
+            }
+        }
+
+        if components == 0 {
+            findings.push(
+                "no package has component metadata; \
+                 add a `[package.metadata.component]` section to `Cargo.toml`"
+                    .to_string(),
+            );
+        }
+
+        if findings.is_empty() {
+            config.terminal().status("Finished", "no problems found")?;
+            return Ok(());
+        }
+
+        for finding in &findings {
+            config.terminal().warn(finding)?;
+        }
+
+        bail!(
+            "found {count} problem{s} with the component setup",
+            count = findings.len(),
+            s = if findings.len() == 1 { "" } else { "s" }
+        );
+    }
+}
+
+/// Diagnoses a component package, adding any problems found to `findings`.
+fn diagnose_package(package: &Package, metadata: &ComponentMetadata, findings: &mut Vec<String>) {
+    let name = &package.name;
+    let manifest = &package.manifest_path;
+
+    let lib = package.targets.iter().find(|t| {
+        t.kind.iter().any(|k| {
+            matches!(
+                k.as_str(),
+                "lib" | "rlib" | "dylib" | "cdylib" | "staticlib"
+            )
+        })
+    });
+    let is_bin = package.targets.iter().any(|t| t.is_bin());
+
+    if let Some(lib) = lib {
+        if !is_bin && !lib.crate_types.iter().any(|t| t == "cdylib") {
+            findings.push(format!(
+                "the library of package `{name}` is not a `cdylib`; \
+                 add `crate-type = [\"cdylib\"]` to the `[lib]` section of `{manifest}`"
+            ));
+        }
+    }
+
+    if !package
+        .dependencies
+        .iter()
+        .any(|d| d.name == BINDINGS_CRATE_NAME)
+    {
+        findings.push(format!(
+            "package `{name}` does not depend on `{BINDINGS_CRATE_NAME}`; \
+             add it to the `[dependencies]` section of `{manifest}`"
+        ));
+    }
+
+    for target in package
+        .targets
+        .iter()
+        .filter(|t| t.is_bin() || lib.is_some_and(|lib| std::ptr::eq(lib, *t)))
+    {
+        let path = &target.src_path;
+        match fs::read_to_string(path) {
+            Ok(source) if source.contains("generate!") => {}
+            Ok(_) => findings.push(format!(
+                "`{path}` does not invoke `cargo_component_bindings::generate!` \
+                 to generate the bindings of package `{name}`"
+            )),
+            Err(e) => findings.push(format!("failed to read `{path}`: {e}")),
+        }
+    }
+
+    diagnose_adapter(name, &metadata.section.adapter, findings);
+
+    if matches!(metadata.section.target, Target::Local { .. }) && !metadata.target_from_stdin() {
+        match metadata.target_path() {
+            Some(path) => {
+                let parsed = if path.is_dir() {
+                    UnresolvedPackage::parse_dir(&path)
+                } else {
+                    UnresolvedPackage::parse_file(&path)
+                };
+
+                match parsed {
+                    Ok(pkg) if pkg.worlds.iter().next().is_none() => findings.push(format!(
+                        "the target WIT package of package `{name}` at `{path}` defines no world",
+                        path = path.display()
+                    )),
+                    Ok(_) => {}
+                    Err(e) => findings.push(format!(
+                        "failed to parse the target WIT package of package `{name}` at `{path}`: {e:#}",
+                        path = path.display()
+                    )),
+                }
+            }
+            // Commands may be built without a target world
+            None if is_bin => {}
+            None => findings.push(format!(
+                "package `{name}` defines no world to target; create a `wit` directory \
+                 next to `{manifest}` or set `package.metadata.component.target`"
+            )),
+        }
+    }
+}
+
+/// Diagnoses the adapter setting of a package, adding an unreadable
+/// adapter to `findings`.
+fn diagnose_adapter(name: &str, adapter: &Adapter, findings: &mut Vec<String>) {
+    match adapter {
+        Adapter::Builtin | Adapter::None => {}
+        Adapter::List { paths, modules } => {
+            if !paths.is_empty() && !paths.iter().any(|p| fs::File::open(p).is_ok()) {
+                findings.push(format!(
+                    "no module adapter of package `{name}` can be read from {paths}",
+                    paths = paths
+                        .iter()
+                        .map(|p| format!("`{p}`", p = p.display()))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+
+            for adapter in modules {
+                if let Err(e) = fs::File::open(&adapter.path) {
+                    findings.push(format!(
+                        "the adapter of module `{module}` of package `{name}` cannot be read from `{path}`: {e}",
+                        module = adapter.module,
+                        path = adapter.path.display()
+                    ));
+                }
+            }
+        }
+        Adapter::Targets(adapters) | Adapter::Profiles(adapters) => {
+            for adapter in adapters.values() {
+                diagnose_adapter(name, adapter, findings);
+            }
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use crate::{
+    config::{CargoArguments, CargoPackageSpec, Config},
+    find_package, load_metadata, run_cargo_command_with_env, PackageComponentMetadata,
+};
+use anyhow::{bail, Context, Result};
+use cargo_component_core::command::CommonOptions;
+use clap::Args;
+use std::{
+    fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Print the macro-expanded source of a component package.
+#[derive(Args)]
+#[clap(disable_version_flag = true)]
+pub struct ExpandCommand {
+    /// The common command options.
+    #[clap(flatten)]
+    pub common: CommonOptions,
+
+    /// Cargo package to expand (see `cargo help pkgid`)
+    #[clap(long = "package", short = 'p', value_name = "SPEC")]
+    pub cargo_package: Option<CargoPackageSpec>,
+
+    /// Path to Cargo.toml
+    #[clap(long = "manifest-path", value_name = "PATH")]
+    pub manifest_path: Option<PathBuf>,
+
+    /// Expand the given binary target instead of the library
+    #[clap(long = "bin", value_name = "NAME")]
+    pub bin: Option<String>,
+
+    /// Build for the target triple (defaults to `wasm32-wasi`)
+    #[clap(long = "target", value_name = "TRIPLE")]
+    pub target: Option<String>,
+
+    /// Space or comma separated list of features to activate
+    #[clap(long = "features", value_name = "FEATURES")]
+    pub features: Vec<String>,
+
+    /// Run without accessing the network
+    #[clap(long = "offline")]
+    pub offline: bool,
+}
+
+impl ExpandCommand {
+    /// Executes the command.
+    pub async fn exec(self) -> Result<()> {
+        log::debug!("executing expand command");
+
+        let config = Config::new(self.common.new_terminal())?;
+        let metadata = load_metadata(self.manifest_path.as_deref())?;
+        let package =
+            PackageComponentMetadata::new(find_package(&metadata, self.cargo_package.as_ref())?)?;
+
+        if package.metadata.is_none() {
+            bail!(
+                "package `{name}` is not a WebAssembly component package",
+                name = package.package.name
+            );
+        }
+
+        // The expanded source is written to a new file for every expansion so
+        // that cargo does not consider the crate fresh and skip compiling it
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default();
+        let dir = metadata.target_directory.join("expand");
+        fs::create_dir_all(&dir).with_context(|| format!("failed to create directory `{dir}`"))?;
+        let out = dir.join(format!(
+            "{name}-{pid}-{nanos}.rs",
+            name = package.package.name,
+            pid = std::process::id()
+        ));
+
+        let args = self.rustc_args(&package.package.name, out.as_str());
+        let cargo_args = CargoArguments::parse_from(args.iter())?;
+
+        // `-Zunpretty` is unstable, so expanding on a stable toolchain
+        // requires `RUSTC_BOOTSTRAP`; it is only set for the spawned cargo
+        let result = run_cargo_command_with_env(
+            &config,
+            &metadata,
+            std::slice::from_ref(&package),
+            Some("rustc"),
+            &cargo_args,
+            &args,
+            &[("RUSTC_BOOTSTRAP", "1")],
+        )
+        .await
+        .and_then(|_| {
+            fs::read_to_string(&out)
+                .with_context(|| format!("failed to read expanded source `{out}`"))
+        });
+
+        let _ = fs::remove_file(&out);
+        config.terminal().write_stdout(result?, None)
+    }
+
+    fn rustc_args(&self, package: &str, out: &str) -> Vec<String> {
+        let mut args = vec![
+            "rustc".to_string(),
+            "--profile=check".to_string(),
+            "--package".to_string(),
+            package.to_string(),
+        ];
+
+        match &self.bin {
+            Some(bin) => {
+                args.push("--bin".to_string());
+                args.push(bin.clone());
+            }
+            None => args.push("--lib".to_string()),
+        }
+
+        if let Some(manifest_path) = &self.manifest_path {
+            args.push("--manifest-path".to_string());
+            args.push(manifest_path.to_string_lossy().into_owned());
+        }
+
+        if let Some(target) = &self.target {
+            args.push("--target".to_string());
+            args.push(target.clone());
+        }
+
+        if !self.features.is_empty() {
+            args.push("--features".to_string());
+            args.push(self.features.join(","));
+        }
+
+        if self.offline {
+            args.push("--offline".to_string());
+        }
+
+        if self.common.quiet {
+            args.push("-q".to_string());
+        }
+
+        args.extend(
+            std::iter::repeat_n("-v", self.common.verbose as usize).map(ToString::to_string),
+        );
+
+        if let Some(color) = self.common.color {
+            args.push("--color".to_string());
+            args.push(color.to_string());
+        }
+
+        args.push("--".to_string());
+        args.push("-Zunpretty=expanded".to_string());
+        args.push("-o".to_string());
+        args.push(out.to_string());
+        args
+    }
+}
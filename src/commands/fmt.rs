@@ -0,0 +1,51 @@
+use crate::{
+    config::{CargoPackageSpec, Config},
+    format_wit, load_component_metadata, load_metadata,
+};
+use anyhow::Result;
+use cargo_component_core::command::CommonOptions;
+use clap::Args;
+use std::path::PathBuf;
+
+/// Format the local WIT files of component packages.
+#[derive(Args)]
+#[clap(disable_version_flag = true)]
+pub struct FmtCommand {
+    /// The common command options.
+    #[clap(flatten)]
+    pub common: CommonOptions,
+
+    /// Cargo package to format (see `cargo help pkgid`)
+    #[clap(long = "package", short = 'p', value_name = "SPEC")]
+    pub cargo_packages: Vec<CargoPackageSpec>,
+
+    /// Format all packages in the workspace
+    #[clap(long = "workspace")]
+    pub workspace: bool,
+
+    /// Path to Cargo.toml
+    #[clap(long = "manifest-path", value_name = "PATH")]
+    pub manifest_path: Option<PathBuf>,
+
+    /// Check if the WIT files are formatted without modifying them
+    #[clap(long = "check")]
+    pub check: bool,
+
+    /// Run without accessing the network
+    #[clap(long = "offline")]
+    pub offline: bool,
+}
+
+impl FmtCommand {
+    /// Executes the command.
+    pub async fn exec(self) -> Result<()> {
+        log::debug!("executing fmt command");
+
+        let config = Config::new(self.common.new_terminal())?;
+        let metadata = load_metadata(self.manifest_path.as_deref())?;
+        let packages =
+            load_component_metadata(&metadata, self.cargo_packages.iter(), self.workspace)?;
+
+        format_wit(&config, &packages, self.check, !self.offline).await
+    }
+}
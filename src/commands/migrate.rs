@@ -0,0 +1,162 @@
+use crate::{
+    config::{CargoPackageSpec, Config},
+    load_metadata, select_packages,
+};
+use anyhow::{bail, Context, Result};
+use cargo_component_core::command::CommonOptions;
+use clap::Args;
+use std::{fs, path::PathBuf};
+use toml_edit::{Document, Key, Table};
+
+/// Migrate the component metadata of `Cargo.toml` to the current schema.
+#[derive(Args)]
+#[clap(disable_version_flag = true)]
+pub struct MigrateCommand {
+    /// The common command options.
+    #[clap(flatten)]
+    pub common: CommonOptions,
+
+    /// Cargo package to migrate (see `cargo help pkgid`)
+    #[clap(long = "package", short = 'p', value_name = "SPEC")]
+    pub cargo_packages: Vec<CargoPackageSpec>,
+
+    /// Migrate all packages in the workspace
+    #[clap(long = "workspace")]
+    pub workspace: bool,
+
+    /// Path to Cargo.toml
+    #[clap(long = "manifest-path", value_name = "PATH")]
+    pub manifest_path: Option<PathBuf>,
+
+    /// Report the changes without writing the manifests
+    #[clap(long = "dry-run")]
+    pub dry_run: bool,
+}
+
+impl MigrateCommand {
+    /// Executes the command.
+    pub async fn exec(self) -> Result<()> {
+        log::debug!("executing migrate command");
+
+        let config = Config::new(self.common.new_terminal())?;
+
+        // The component metadata is not parsed as it may use an older schema
+        let metadata = load_metadata(self.manifest_path.as_deref())?;
+        let packages = select_packages(&metadata, self.cargo_packages.iter(), self.workspace)?;
+
+        let mut migrated = false;
+        for package in packages {
+            let path = &package.manifest_path;
+            let manifest = fs::read_to_string(path)
+                .with_context(|| format!("failed to read manifest file `{path}`"))?;
+
+            let mut document: Document = manifest
+                .parse()
+                .with_context(|| format!("failed to parse manifest file `{path}`"))?;
+
+            let changes = migrate_manifest(&mut document)
+                .with_context(|| format!("failed to migrate manifest file `{path}`"))?;
+
+            if changes.is_empty() {
+                continue;
+            }
+
+            migrated = true;
+            for change in &changes {
+                config.terminal().status(
+                    if self.dry_run {
+                        "Would migrate"
+                    } else {
+                        "Migrated"
+                    },
+                    format!("{change} in `{path}`"),
+                )?;
+            }
+
+            if !self.dry_run {
+                fs::write(path, document.to_string())
+                    .with_context(|| format!("failed to write manifest file `{path}`"))?;
+            }
+        }
+
+        if !migrated {
+            config
+                .terminal()
+                .note("component metadata is already up-to-date")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Migrates the component metadata of a manifest to the current schema.
+///
+/// Returns a description of each change made to the manifest.
+fn migrate_manifest(document: &mut Document) -> Result<Vec<String>> {
+    let mut changes = Vec::new();
+    let component = match document
+        .get_mut("package")
+        .and_then(|p| p.get_mut("metadata"))
+        .and_then(|m| m.get_mut("component"))
+    {
+        Some(component) => component
+            .as_table_mut()
+            .context("`package.metadata.component` is not a table")?,
+        None => return Ok(changes),
+    };
+
+    // Keys are kebab-case; older manifests may use snake_case keys
+    rename_snake_case_keys(component, "package.metadata.component", &mut changes)?;
+
+    if let Some(producers) = component
+        .get_mut("producers")
+        .and_then(|p| p.as_table_mut())
+    {
+        rename_snake_case_keys(
+            producers,
+            "package.metadata.component.producers",
+            &mut changes,
+        )?;
+    }
+
+    Ok(changes)
+}
+
+/// Renames the snake_case keys of a table to kebab-case, keeping the order
+/// and formatting of the table's entries.
+fn rename_snake_case_keys(table: &mut Table, path: &str, changes: &mut Vec<String>) -> Result<()> {
+    let keys: Vec<String> = table.iter().map(|(k, _)| k.to_string()).collect();
+    if !keys.iter().any(|k| k.contains('_')) {
+        return Ok(());
+    }
+
+    let mut entries = Vec::with_capacity(keys.len());
+    for key in &keys {
+        entries.push(table.remove_entry(key).unwrap());
+    }
+
+    for (key, item) in entries {
+        let name = key.get().replace('_', "-");
+        if name == key.get() {
+            table.insert_formatted(&key, item);
+            continue;
+        }
+
+        if keys.contains(&name) {
+            bail!(
+                "both `{path}.{key}` and `{path}.{name}` are specified",
+                key = key.get()
+            );
+        }
+
+        let mut new_key = Key::new(name.clone());
+        *new_key.decor_mut() = key.decor().clone();
+        table.insert_formatted(&new_key, item);
+        changes.push(format!(
+            "renamed `{path}.{key}` to `{path}.{name}`",
+            key = key.get()
+        ));
+    }
+
+    Ok(())
+}
@@ -20,7 +20,7 @@ use url::Url;
 const BINDINGS_CRATE_NAME: &str = "cargo-component-bindings";
 const BINDINGS_CRATE_URL: &str = "https://github.com/bytecodealliance/cargo-component";
 
-fn escape_wit(s: &str) -> Cow<str> {
+fn escape_wit(s: &str) -> Cow<'_, str> {
     match s {
         "use" | "type" | "func" | "u8" | "u16" | "u32" | "u64" | "s8" | "s16" | "s32" | "s64"
         | "float32" | "float64" | "char" | "record" | "flags" | "variant" | "enum" | "union"
@@ -47,11 +47,12 @@ pub struct NewCommand {
     #[clap(long = "vcs", value_name = "VCS", value_parser = ["git", "hg", "pijul", "fossil", "none"])]
     pub vcs: Option<String>,
 
-    /// Create a command component [default]
+    /// Create a command component with a `main` function and no target
+    /// world [default]
     #[clap(long = "command", conflicts_with("reactor"))]
     pub command: bool,
 
-    /// Create a reactor component
+    /// Create a reactor component targeting the world in `wit/world.wit`
     #[clap(long = "reactor")]
     pub reactor: bool,
 
@@ -75,12 +76,14 @@ pub struct NewCommand {
     #[clap(long = "editor", value_name = "EDITOR", value_parser = ["emacs", "vscode", "none"])]
     pub editor: Option<String>,
 
-    /// Use the specified target world from a WIT package.
+    /// Use the specified target world from a registry WIT package (e.g.
+    /// `my:world@1.0.0`), creating a reactor component with stubs for the
+    /// exports of the world.
     #[clap(
         long = "target",
         short = 't',
         value_name = "TARGET",
-        requires = "reactor"
+        conflicts_with("command")
     )]
     pub target: Option<String>,
 
@@ -88,10 +91,27 @@ pub struct NewCommand {
     #[clap(long = "registry", value_name = "REGISTRY")]
     pub registry: Option<String>,
 
+    /// Run without accessing the network
+    #[clap(long = "offline")]
+    pub offline: bool,
+
     /// Disable the use of `rustfmt` when generating source code.
     #[clap(long = "no-rustfmt")]
     pub no_rustfmt: bool,
 
+    /// Create a minimal package without example source or world contents.
+    #[clap(long = "bare", conflicts_with("target"))]
+    pub bare: bool,
+
+    /// Create a WIT package with the given name (e.g. `my:iface`) that
+    /// builds to a type component, without a Rust implementation.
+    #[clap(
+        long = "wit",
+        value_name = "PACKAGE",
+        conflicts_with_all = ["command", "reactor", "target", "bare"]
+    )]
+    pub wit: Option<String>,
+
     /// The path for the generated package.
     #[clap(value_name = "path")]
     pub path: PathBuf,
@@ -138,6 +158,27 @@ impl<'a> PackageName<'a> {
             display,
         })
     }
+
+    fn from_wit(id: &str, name: Option<&'a str>, path: &'a Path) -> Result<Self> {
+        let (namespace, pkg) = id.split_once(':').with_context(|| {
+            format!("WIT package name `{id}` is not of the form `<namespace>:<name>`")
+        })?;
+
+        for part in [namespace, pkg] {
+            wit_parser::validate_id(part).with_context(|| {
+                format!("WIT package name `{id}` is not a legal WIT package name")
+            })?;
+        }
+
+        Ok(Self {
+            namespace: namespace.to_string(),
+            name: pkg.to_string(),
+            display: match name {
+                Some(name) => name.into(),
+                None => path.as_os_str().to_string_lossy(),
+            },
+        })
+    }
 }
 
 impl NewCommand {
@@ -147,7 +188,10 @@ impl NewCommand {
 
         let config = Config::new(self.common.new_terminal())?;
 
-        let name = PackageName::new(&self.namespace, self.name.as_deref(), &self.path)?;
+        let name = match &self.wit {
+            Some(id) => PackageName::from_wit(id, self.name.as_deref(), &self.path)?,
+            None => PackageName::new(&self.namespace, self.name.as_deref(), &self.path)?,
+        };
 
         let out_dir = std::env::current_dir()
             .with_context(|| "couldn't get the current directory of the process")?
@@ -161,7 +205,7 @@ impl NewCommand {
         };
 
         let target = self
-            .resolve_target(&config, &registries, target, true)
+            .resolve_target(&config, &registries, target, !self.offline)
             .await?;
         let source = self.generate_source(&target)?;
 
@@ -205,7 +249,7 @@ impl NewCommand {
             command.arg("-q");
         }
 
-        command.args(std::iter::repeat("-v").take(self.common.verbose as usize));
+        command.args(std::iter::repeat_n("-v", self.common.verbose as usize));
 
         if let Some(color) = self.common.color {
             command.arg("--color").arg(color.to_string());
@@ -273,7 +317,9 @@ impl NewCommand {
             }
         }
 
-        component["dependencies"] = Item::Table(Table::new());
+        if !self.bare && self.wit.is_none() {
+            component["dependencies"] = Item::Table(Table::new());
+        }
 
         if !registries.is_empty() {
             let mut table = Table::new();
@@ -289,8 +335,12 @@ impl NewCommand {
         metadata["component"] = Item::Table(component);
 
         doc["package"]["metadata"] = Item::Table(metadata);
-        doc["dependencies"][BINDINGS_CRATE_NAME] =
-            value(InlineTable::from_iter([("git", BINDINGS_CRATE_URL)]));
+
+        // A WIT package has no bindings to generate
+        if self.wit.is_none() {
+            doc["dependencies"][BINDINGS_CRATE_NAME] =
+                value(InlineTable::from_iter([("git", BINDINGS_CRATE_URL)]));
+        }
 
         fs::write(&manifest_path, doc.to_string()).with_context(|| {
             format!(
@@ -308,19 +358,29 @@ impl NewCommand {
     }
 
     fn is_command(&self) -> bool {
-        self.command || !self.reactor
+        self.wit.is_none() && self.target.is_none() && (self.command || !self.reactor)
     }
 
     fn generate_source(
         &self,
         target: &Option<(RegistryResolution, Option<String>)>,
-    ) -> Result<Cow<str>> {
+    ) -> Result<Cow<'_, str>> {
         match target {
             Some((resolution, world)) => {
                 let generator =
                     SourceGenerator::new(&resolution.id, &resolution.path, !self.no_rustfmt);
                 generator.generate(world.as_deref()).map(Into::into)
             }
+            None if self.wit.is_some() => {
+                Ok("//! This package defines the WIT package in the `wit` directory.\n".into())
+            }
+            None if self.bare => {
+                if self.is_command() {
+                    Ok("cargo_component_bindings::generate!();\n\nfn main() {}\n".into())
+                } else {
+                    Ok("cargo_component_bindings::generate!();\n".into())
+                }
+            }
             None => {
                 if self.is_command() {
                     Ok(r#"cargo_component_bindings::generate!();
@@ -407,8 +467,25 @@ impl Guest for Component {
 
         let path = wit_path.join("world.wit");
 
-        fs::write(
-            &path,
+        let contents = if self.wit.is_some() {
+            format!(
+                r#"package {ns}:{pkg}
+
+/// An example interface for components to import or export.
+interface example {{
+    hello-world: func() -> string
+}}
+"#,
+                ns = escape_wit(&name.namespace),
+                pkg = escape_wit(&name.name),
+            )
+        } else if self.bare {
+            format!(
+                "package {ns}:{pkg}\n\nworld {pkg} {{}}\n",
+                ns = escape_wit(&name.namespace),
+                pkg = escape_wit(&name.name),
+            )
+        } else {
             format!(
                 r#"package {ns}:{pkg}
 
@@ -419,9 +496,10 @@ world example {{
 "#,
                 ns = escape_wit(&name.namespace),
                 pkg = escape_wit(&name.name),
-            ),
-        )
-        .with_context(|| {
+            )
+        };
+
+        fs::write(&path, contents).with_context(|| {
             format!(
                 "failed to write targets file `{path}`",
                 path = path.display()
@@ -502,7 +580,11 @@ world example {{
 
                 resolver.add_dependency(&id, &dependency).await?;
 
-                let dependencies = resolver.resolve().await?;
+                let dependencies = resolver.resolve().await.with_context(|| {
+                    format!(
+                        "failed to fetch the WIT package of target `{id}` needed to generate the component source"
+                    )
+                })?;
                 assert_eq!(dependencies.len(), 1);
 
                 match dependencies
@@ -1,14 +1,24 @@
 use crate::{
+    check_target_conformance,
     config::{CargoArguments, CargoPackageSpec, Config},
-    is_wasm_target, load_metadata, publish, run_cargo_command, PackageComponentMetadata,
-    PublishOptions,
+    find_package, is_wasm_target, load_metadata,
+    metadata::{ComponentMetadata, Target},
+    publish, run_cargo_command, PackageComponentMetadata, PublishOptions,
 };
 use anyhow::{bail, Context, Result};
-use cargo_component_core::{command::CommonOptions, keyring::get_signing_key, registry::find_url};
+use cargo_component_core::{
+    command::CommonOptions,
+    keyring::get_signing_key,
+    registry::{find_url, Dependency},
+};
 use clap::Args;
-use std::path::PathBuf;
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+};
 use warg_client::RegistryUrl;
 use warg_crypto::signing::PrivateKey;
+use warg_protocol::registry::PackageId;
 
 /// Publish a package to a registry.
 #[derive(Args)]
@@ -77,6 +87,10 @@ pub struct PublishCommand {
     /// Initialize a new package in the registry.
     #[clap(long = "init")]
     pub init: bool,
+
+    /// Allow publishing with dependencies on local paths.
+    #[clap(long = "allow-local-deps")]
+    pub allow_local_deps: bool,
 }
 
 impl PublishCommand {
@@ -93,27 +107,10 @@ impl PublishCommand {
         }
 
         let metadata = load_metadata(self.manifest_path.as_deref())?;
-        let packages = [PackageComponentMetadata::new(
-            if let Some(spec) = &self.cargo_package {
-                metadata
-                    .packages
-                    .iter()
-                    .find(|p| {
-                        p.name == spec.name
-                            && match spec.version.as_ref() {
-                                Some(v) => &p.version == v,
-                                None => true,
-                            }
-                    })
-                    .with_context(|| {
-                        format!("package ID specification `{spec}` did not match any packages")
-                    })?
-            } else {
-                metadata
-                    .root_package()
-                    .context("no root package found in manifest")?
-            },
-        )?];
+        let packages = [PackageComponentMetadata::new(find_package(
+            &metadata,
+            self.cargo_package.as_ref(),
+        )?)?];
 
         let package = packages[0].package;
         let component_metadata = packages[0].metadata.as_ref().with_context(|| {
@@ -132,6 +129,21 @@ impl PublishCommand {
             )
         })?;
 
+        if !self.allow_local_deps {
+            let local = local_dependencies(component_metadata);
+            if !local.is_empty() {
+                let mut deps = String::new();
+                for (id, path) in local {
+                    write!(deps, "\n  `{id}` (path `{path}`)", path = path.display()).unwrap();
+                }
+
+                bail!(
+                    "package `{name}` has dependencies on local paths that will not be available to consumers of the published package:{deps}\n\nuse `--allow-local-deps` to publish anyway",
+                    name = package.name,
+                );
+            }
+        }
+
         let registry_url = find_url(
             self.registry.as_deref(),
             &component_metadata.section.registries,
@@ -157,9 +169,32 @@ impl PublishCommand {
             frozen: self.frozen,
             locked: self.locked,
             release: true,
+            profile: None,
             offline: self.offline,
             workspace: false,
             packages: self.cargo_package.clone().into_iter().collect(),
+            message_format: None,
+            features: self
+                .features
+                .iter()
+                .flat_map(|f| f.split([' ', ',']))
+                .filter(|f| !f.is_empty())
+                .map(ToString::to_string)
+                .collect(),
+            all_features: self.all_features,
+            no_default_features: self.no_default_features,
+            explain_encoding: false,
+            strict: false,
+            smoke_test: false,
+            export_symbols: false,
+            keep_temps: false,
+            warn_unimplemented: false,
+            test_components: false,
+            dump_component_type: None,
+            temp_dir: None,
+            output: None,
+            cargo: None,
+            reuse_lock_from: None,
         };
 
         let spawn_args = self.build_args()?;
@@ -179,6 +214,15 @@ impl PublishCommand {
             );
         }
 
+        check_target_conformance(
+            &config,
+            &metadata,
+            &packages[0],
+            &outputs[0],
+            &cargo_build_args,
+        )
+        .await?;
+
         let options = PublishOptions {
             package,
             registry_url,
@@ -203,9 +247,7 @@ impl PublishCommand {
         }
 
         args.extend(
-            std::iter::repeat("-v")
-                .take(self.common.verbose as usize)
-                .map(ToString::to_string),
+            std::iter::repeat_n("-v", self.common.verbose as usize).map(ToString::to_string),
         );
 
         if let Some(color) = self.common.color {
@@ -288,3 +330,25 @@ impl PublishCommand {
         Ok(args)
     }
 }
+
+/// Gets the dependencies of a component that refer to local paths, sorted by
+/// package id.
+fn local_dependencies(metadata: &ComponentMetadata) -> Vec<(&PackageId, &Path)> {
+    let target = match &metadata.section.target {
+        Target::Local { dependencies, .. } => Some(dependencies),
+        Target::Package { .. } => None,
+    };
+
+    let mut local: Vec<_> = target
+        .into_iter()
+        .flatten()
+        .chain(&metadata.section.dependencies)
+        .filter_map(|(id, dependency)| match dependency {
+            Dependency::Local(local) => Some((id, local.path.as_path())),
+            Dependency::Package(_) => None,
+        })
+        .collect();
+
+    local.sort_by_key(|(id, _)| id.to_string());
+    local
+}
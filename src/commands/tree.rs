@@ -0,0 +1,62 @@
+use crate::{
+    config::{CargoPackageSpec, Config},
+    load_component_metadata, load_metadata, print_dependency_tree,
+};
+use anyhow::{bail, Result};
+use cargo_component_core::command::CommonOptions;
+use clap::Args;
+use std::path::PathBuf;
+
+/// Display the dependency tree of component packages.
+#[derive(Args)]
+#[clap(disable_version_flag = true)]
+pub struct TreeCommand {
+    /// The common command options.
+    #[clap(flatten)]
+    pub common: CommonOptions,
+
+    /// Cargo package(s) to display the tree of (see `cargo help pkgid`)
+    #[clap(long = "package", short = 'p', value_name = "SPEC")]
+    pub cargo_package: Vec<CargoPackageSpec>,
+
+    /// Display the trees of all packages in the workspace
+    #[clap(long = "workspace")]
+    pub workspace: bool,
+
+    /// Path to Cargo.toml
+    #[clap(long = "manifest-path", value_name = "PATH")]
+    pub manifest_path: Option<PathBuf>,
+
+    /// Run without accessing the network
+    #[clap(long = "offline")]
+    pub offline: bool,
+}
+
+impl TreeCommand {
+    /// Executes the command.
+    pub async fn exec(self) -> Result<()> {
+        log::debug!("executing tree command");
+
+        let config = Config::new(self.common.new_terminal())?;
+        let metadata = load_metadata(self.manifest_path.as_deref())?;
+        let packages: Vec<_> =
+            load_component_metadata(&metadata, self.cargo_package.iter(), self.workspace)?
+                .into_iter()
+                .filter(|p| p.metadata.is_some())
+                .collect();
+
+        if packages.is_empty() {
+            bail!("no WebAssembly component packages were selected");
+        }
+
+        for (i, package) in packages.iter().enumerate() {
+            if i > 0 {
+                config.terminal().write_stdout("\n", None)?;
+            }
+
+            print_dependency_tree(&config, &metadata, package, !self.offline).await?;
+        }
+
+        Ok(())
+    }
+}
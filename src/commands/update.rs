@@ -3,6 +3,7 @@ use anyhow::Result;
 use cargo_component_core::command::CommonOptions;
 use clap::Args;
 use std::path::PathBuf;
+use warg_protocol::registry::PackageId;
 
 /// Update dependencies as recorded in the component lock file
 #[derive(Args)]
@@ -31,6 +32,11 @@ pub struct UpdateCommand {
     /// Run without accessing the network
     #[clap(long = "offline")]
     pub offline: bool,
+
+    /// The registry dependencies to update; all dependencies are updated if
+    /// none are given
+    #[clap(value_name = "PACKAGE")]
+    pub packages: Vec<PackageId>,
 }
 
 impl UpdateCommand {
@@ -47,6 +53,7 @@ impl UpdateCommand {
             &config,
             &metadata,
             &packages,
+            &self.packages,
             network_allowed,
             lock_update_allowed,
             self.locked,
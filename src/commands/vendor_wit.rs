@@ -0,0 +1,48 @@
+use crate::{
+    config::{CargoPackageSpec, Config},
+    find_package, load_metadata, vendor_wit, PackageComponentMetadata,
+};
+use anyhow::Result;
+use cargo_component_core::command::CommonOptions;
+use clap::Args;
+use std::path::PathBuf;
+
+/// Vendor the target dependencies of a component package.
+#[derive(Args)]
+#[clap(disable_version_flag = true)]
+pub struct VendorWitCommand {
+    /// The common command options.
+    #[clap(flatten)]
+    pub common: CommonOptions,
+
+    /// Cargo package to vendor the target dependencies of (see `cargo help pkgid`)
+    #[clap(long = "package", short = 'p', value_name = "SPEC")]
+    pub cargo_package: Option<CargoPackageSpec>,
+
+    /// Path to Cargo.toml
+    #[clap(long = "manifest-path", value_name = "PATH")]
+    pub manifest_path: Option<PathBuf>,
+
+    /// The directory, relative to the package directory, to vendor the
+    /// dependencies to
+    #[clap(long = "out", value_name = "DIR", default_value = "wit/deps")]
+    pub out: PathBuf,
+
+    /// Run without accessing the network
+    #[clap(long = "offline")]
+    pub offline: bool,
+}
+
+impl VendorWitCommand {
+    /// Executes the command.
+    pub async fn exec(self) -> Result<()> {
+        log::debug!("executing vendor-wit command");
+
+        let config = Config::new(self.common.new_terminal())?;
+        let metadata = load_metadata(self.manifest_path.as_deref())?;
+        let package =
+            PackageComponentMetadata::new(find_package(&metadata, self.cargo_package.as_ref())?)?;
+
+        vendor_wit(&config, &metadata, &package, &self.out, !self.offline).await
+    }
+}
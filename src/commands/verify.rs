@@ -0,0 +1,153 @@
+use crate::{config::Config, conformance::unmet_imports, validate_component};
+use anyhow::{bail, Context, Result};
+use cargo_component_core::command::CommonOptions;
+use clap::Args;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use wit_component::DecodedWasm;
+use wit_parser::{Resolve, UnresolvedPackage, WorldId};
+
+/// Verify that a component is valid, optionally checking that its imports are
+/// satisfied by a host world.
+#[derive(Args)]
+#[clap(disable_version_flag = true)]
+pub struct VerifyCommand {
+    /// The common command options.
+    #[clap(flatten)]
+    pub common: CommonOptions,
+
+    /// The path to the WIT file or directory defining a host world to verify
+    /// the imports of the component against
+    #[clap(long = "against", value_name = "PATH")]
+    pub against: Option<PathBuf>,
+
+    /// The name of the host world to verify against
+    #[clap(long = "world", value_name = "WORLD", requires = "against")]
+    pub world: Option<String>,
+
+    /// The path to the component to verify
+    #[clap(value_name = "COMPONENT")]
+    pub component: PathBuf,
+}
+
+impl VerifyCommand {
+    /// Executes the command.
+    pub async fn exec(self) -> Result<()> {
+        log::debug!("executing verify command");
+
+        let config = Config::new(self.common.new_terminal())?;
+
+        let bytes = fs::read(&self.component).with_context(|| {
+            format!(
+                "failed to read component `{path}`",
+                path = self.component.display()
+            )
+        })?;
+
+        validate_component(&bytes, &self.component)?;
+
+        // Without a host world to verify against, the producers section is
+        // what identifies the component as built by a known toolchain
+        let producers = wasm_metadata::Producers::from_wasm(&bytes).with_context(|| {
+            format!(
+                "failed to read the producers section of component `{path}`",
+                path = self.component.display()
+            )
+        })?;
+        if producers.is_none() && self.against.is_none() {
+            bail!(
+                "component `{path}` has no `producers` section",
+                path = self.component.display()
+            );
+        }
+
+        let (component, component_world) =
+            match wit_component::decode(&bytes).with_context(|| {
+                format!(
+                    "failed to decode component `{path}`",
+                    path = self.component.display()
+                )
+            })? {
+                DecodedWasm::Component(resolve, world) => (resolve, world),
+                DecodedWasm::WitPackage(..) => bail!(
+                    "`{path}` is not a WebAssembly component",
+                    path = self.component.display()
+                ),
+            };
+
+        let host = self
+            .against
+            .as_deref()
+            .map(|against| self.host_world(against))
+            .transpose()?;
+
+        if let Some((host, host_world)) = &host {
+            let unmet = unmet_imports(&component, component_world, host, *host_world);
+            if !unmet.is_empty() {
+                bail!(
+                    "component `{path}` has imports not provided by host world `{world}`:\n{unmet}",
+                    path = self.component.display(),
+                    world = host.worlds[*host_world].name,
+                    unmet = unmet
+                        .iter()
+                        .map(|i| format!("  {i}"))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                );
+            }
+        }
+
+        let terminal = config.terminal();
+        match &host {
+            Some((host, host_world)) => terminal.status(
+                "Verified",
+                format!(
+                    "component `{path}` against host world `{world}`",
+                    path = self.component.display(),
+                    world = host.worlds[*host_world].name
+                ),
+            )?,
+            None => terminal.status(
+                "Verified",
+                format!("component `{path}`", path = self.component.display()),
+            )?,
+        }
+
+        terminal.status("World", &component.worlds[component_world].name)?;
+        for (field, values) in producers.iter().flat_map(|p| p.iter()) {
+            for (name, version) in values.iter() {
+                terminal.status("Producer", format!("{field}: {name} {version}"))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn host_world(&self, against: &Path) -> Result<(Resolve, WorldId)> {
+        let mut resolve = Resolve::default();
+        let pkg = if against.is_dir() {
+            resolve.push_dir(against).map(|(pkg, _)| pkg)
+        } else {
+            UnresolvedPackage::parse_file(against).and_then(|pkg| resolve.push(pkg))
+        }
+        .with_context(|| {
+            format!(
+                "failed to parse host world `{path}`",
+                path = against.display()
+            )
+        })?;
+
+        let world = resolve
+            .select_world(pkg, self.world.as_deref())
+            .with_context(|| {
+                format!(
+                    "failed to select a host world from `{path}`",
+                    path = against.display()
+                )
+            })?;
+
+        Ok((resolve, world))
+    }
+}
@@ -0,0 +1,86 @@
+use crate::{
+    config::{CargoArguments, CargoPackageSpec, Config},
+    find_package, load_metadata, print_component_wit, print_target_world, PackageComponentMetadata,
+};
+use anyhow::Result;
+use cargo_component_core::command::CommonOptions;
+use clap::Args;
+use std::path::PathBuf;
+
+/// Print the resolved target world of a component package.
+#[derive(Args)]
+#[clap(disable_version_flag = true)]
+pub struct WitCommand {
+    /// The common command options.
+    #[clap(flatten)]
+    pub common: CommonOptions,
+
+    /// Cargo package to print the target world of (see `cargo help pkgid`)
+    #[clap(long = "package", short = 'p', value_name = "SPEC")]
+    pub cargo_package: Option<CargoPackageSpec>,
+
+    /// Path to Cargo.toml
+    #[clap(long = "manifest-path", value_name = "PATH")]
+    pub manifest_path: Option<PathBuf>,
+
+    /// Print the target world as JSON instead of WIT
+    #[clap(long = "json")]
+    pub json: bool,
+
+    /// Print the paths of the WIT files the target world was resolved from
+    #[clap(long = "list-sources", conflicts_with = "json")]
+    pub list_sources: bool,
+
+    /// Print the WIT of the built component instead of the target world
+    #[clap(long = "built", conflicts_with_all = ["json", "list_sources"])]
+    pub built: bool,
+
+    /// Print the WIT of the component built for the target triple (defaults to `wasm32-wasi`)
+    #[clap(long = "target", value_name = "TRIPLE", requires = "built")]
+    pub target: Option<String>,
+
+    /// Print the WIT of the component built with the release profile
+    #[clap(long = "release", requires = "built", conflicts_with = "profile")]
+    pub release: bool,
+
+    /// Print the WIT of the component built with the given cargo profile
+    #[clap(long = "profile", value_name = "PROFILE", requires = "built")]
+    pub profile: Option<String>,
+
+    /// Run without accessing the network
+    #[clap(long = "offline")]
+    pub offline: bool,
+}
+
+impl WitCommand {
+    /// Executes the command.
+    pub async fn exec(self) -> Result<()> {
+        log::debug!("executing wit command");
+
+        let config = Config::new(self.common.new_terminal())?;
+        let metadata = load_metadata(self.manifest_path.as_deref())?;
+        let package =
+            PackageComponentMetadata::new(find_package(&metadata, self.cargo_package.as_ref())?)?;
+
+        if self.built {
+            let cargo_args = CargoArguments {
+                targets: self.target.into_iter().collect(),
+                release: self.release,
+                profile: self.profile,
+                ..Default::default()
+            };
+
+            return print_component_wit(&config, &metadata, &package, &cargo_args);
+        }
+
+        print_target_world(
+            &config,
+            &metadata,
+            &package,
+            self.json,
+            self.list_sources,
+            !self.offline,
+        )
+        .await
+    }
+}
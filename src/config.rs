@@ -338,6 +338,45 @@ impl Args {
     }
 }
 
+/// The argument that explains why a target is encoded.
+pub const EXPLAIN_ENCODING_ARG: &str = "--explain-encoding";
+
+/// The argument that turns build-time check warnings into errors.
+pub const STRICT_ARG: &str = "--strict";
+
+/// The argument that instantiates built components as a smoke test.
+pub const SMOKE_TEST_ARG: &str = "--smoke-test";
+
+/// The argument that prints the core module symbols of component exports.
+pub const EXPORT_SYMBOLS_ARG: &str = "--export-symbols";
+
+/// The argument that keeps the intermediate artifacts of component creation.
+pub const KEEP_TEMPS_ARG: &str = "--keep-temps";
+
+/// The argument that warns about exports left unimplemented after a build.
+pub const WARN_UNIMPLEMENTED_ARG: &str = "--warn-unimplemented";
+
+/// The argument that builds and tests components before running `cargo test`.
+pub const TEST_COMPONENTS_ARG: &str = "--components";
+
+/// The argument that writes the WIT of a built component's type to a file.
+pub const DUMP_COMPONENT_TYPE_ARG: &str = "--dump-component-type";
+
+/// The argument that sets the directory for intermediate build artifacts.
+pub const TEMP_DIR_ARG: &str = "--temp-dir";
+
+/// The argument that copies a built component to a path.
+pub const OUTPUT_ARG: &str = "--output";
+
+/// The short form of [`OUTPUT_ARG`].
+pub const OUTPUT_SHORT_ARG: &str = "-o";
+
+/// The argument that sets the cargo executable to run.
+pub const CARGO_ARG: &str = "--cargo";
+
+/// The argument that resolves dependencies from another project's lock file.
+pub const REUSE_LOCK_FROM_ARG: &str = "--reuse-lock-from";
+
 /// Represents known cargo arguments.
 ///
 /// This is a subset of the arguments that cargo supports that
@@ -360,12 +399,72 @@ pub struct CargoArguments {
     pub locked: bool,
     /// The --release argument.
     pub release: bool,
+    /// The --profile argument.
+    pub profile: Option<String>,
     /// The --offline argument.
     pub offline: bool,
     /// The --workspace argument.
     pub workspace: bool,
     /// The --package argument.
     pub packages: Vec<CargoPackageSpec>,
+    /// The --message-format argument.
+    pub message_format: Option<String>,
+    /// The --features argument.
+    ///
+    /// Each space or comma separated feature is a separate entry.
+    pub features: Vec<String>,
+    /// The --all-features argument.
+    pub all_features: bool,
+    /// The --no-default-features argument.
+    pub no_default_features: bool,
+    /// The --explain-encoding argument.
+    ///
+    /// This argument is specific to cargo-component and is not passed to cargo.
+    pub explain_encoding: bool,
+    /// The --strict argument.
+    ///
+    /// This argument is specific to cargo-component and is not passed to cargo.
+    pub strict: bool,
+    /// The --smoke-test argument.
+    ///
+    /// This argument is specific to cargo-component and is not passed to cargo.
+    pub smoke_test: bool,
+    /// The --export-symbols argument.
+    ///
+    /// This argument is specific to cargo-component and is not passed to cargo.
+    pub export_symbols: bool,
+    /// The --keep-temps argument.
+    ///
+    /// This argument is specific to cargo-component and is not passed to cargo.
+    pub keep_temps: bool,
+    /// The --warn-unimplemented argument.
+    ///
+    /// This argument is specific to cargo-component and is not passed to cargo.
+    pub warn_unimplemented: bool,
+    /// The --components argument.
+    ///
+    /// This argument is specific to cargo-component and is not passed to cargo.
+    pub test_components: bool,
+    /// The --dump-component-type argument.
+    ///
+    /// This argument is specific to cargo-component and is not passed to cargo.
+    pub dump_component_type: Option<PathBuf>,
+    /// The --temp-dir argument.
+    ///
+    /// This argument is specific to cargo-component and is not passed to cargo.
+    pub temp_dir: Option<PathBuf>,
+    /// The --output argument.
+    ///
+    /// This argument is specific to cargo-component and is not passed to cargo.
+    pub output: Option<PathBuf>,
+    /// The --cargo argument.
+    ///
+    /// This argument is specific to cargo-component and is not passed to cargo.
+    pub cargo: Option<PathBuf>,
+    /// The --reuse-lock-from argument.
+    ///
+    /// This argument is specific to cargo-component and is not passed to cargo.
+    pub reuse_lock_from: Option<PathBuf>,
 }
 
 impl CargoArguments {
@@ -379,27 +478,111 @@ impl CargoArguments {
         !self.frozen && !self.locked
     }
 
+    /// Determines if the short message format was requested.
+    ///
+    /// Cargo accepts a comma-separated list of formats, so this checks
+    /// for a `short` (or `json-diagnostic-short`) entry in the list.
+    pub fn short_message_format(&self) -> bool {
+        self.message_format
+            .as_deref()
+            .map(|f| {
+                f.split(',')
+                    .any(|f| matches!(f.trim(), "short" | "json-diagnostic-short"))
+            })
+            .unwrap_or(false)
+    }
+
+    /// Gets the name of the cargo profile being built.
+    ///
+    /// This is the `--profile` argument, if given, or otherwise `release`
+    /// or `dev` depending on `--release`.
+    pub fn profile(&self) -> &str {
+        match &self.profile {
+            Some(profile) => profile,
+            None if self.release => "release",
+            None => "dev",
+        }
+    }
+
+    /// Gets the name of the directory of the cargo profile being built in
+    /// the target directory.
+    pub fn profile_dir(&self) -> &str {
+        match self.profile() {
+            "dev" | "test" => "debug",
+            "bench" => "release",
+            profile => profile,
+        }
+    }
+
+    /// Determines if JSON messages are requested with `--message-format`.
+    ///
+    /// Cargo accepts a comma-separated list of formats, so this checks
+    /// for a `json` (or `json-*`) entry in the list.
+    pub fn json_message_format(&self) -> bool {
+        self.message_format
+            .as_deref()
+            .map(|f| f.split(',').any(|f| f.trim().starts_with("json")))
+            .unwrap_or(false)
+    }
+
+    /// Gets the directory for intermediate build artifacts.
+    ///
+    /// The `--temp-dir` argument takes precedence over the environment.
+    pub fn temp_dir(&self) -> Option<PathBuf> {
+        self.temp_dir
+            .clone()
+            .or_else(|| std::env::var_os(TEMP_DIR_ENV_VAR).map(PathBuf::from))
+    }
+
+    /// Gets the path of the cargo executable to run.
+    ///
+    /// The `--cargo` argument takes precedence over the `CARGO` environment
+    /// variable; otherwise `cargo` is found in `PATH`.
+    pub fn cargo(&self) -> PathBuf {
+        self.cargo
+            .clone()
+            .or_else(|| std::env::var_os("CARGO").map(PathBuf::from))
+            .unwrap_or_else(|| PathBuf::from("cargo"))
+    }
+
     /// Parses the arguments from the environment.
     pub fn parse() -> Result<Self> {
         Self::parse_from(std::env::args().skip(1))
     }
 
     /// Parses the arguments from an iterator.
-    fn parse_from<T>(iter: impl Iterator<Item = T>) -> Result<Self>
+    pub fn parse_from<T>(iter: impl Iterator<Item = T>) -> Result<Self>
     where
         T: Into<String>,
     {
         let mut args = Args::default()
             .single("--color", "WHEN", Some('c'))
             .single("--manifest-path", "PATH", None)
+            .single("--message-format", "FMT", None)
+            .single("--profile", "PROFILE-NAME", None)
+            .single(DUMP_COMPONENT_TYPE_ARG, "PATH", None)
+            .single(TEMP_DIR_ARG, "DIR", None)
+            .single(OUTPUT_ARG, "PATH", Some('o'))
+            .single(CARGO_ARG, "PATH", None)
+            .single(REUSE_LOCK_FROM_ARG, "PATH", None)
             .multiple("--package", "SPEC", Some('p'))
             .multiple("--target", "TRIPLE", None)
+            .multiple("--features", "FEATURES", Some('F'))
             .flag("--release", Some('r'))
             .flag("--frozen", None)
             .flag("--locked", None)
             .flag("--offline", None)
             .flag("--all", None)
             .flag("--workspace", None)
+            .flag("--all-features", None)
+            .flag("--no-default-features", None)
+            .flag(EXPLAIN_ENCODING_ARG, None)
+            .flag(STRICT_ARG, None)
+            .flag(SMOKE_TEST_ARG, None)
+            .flag(EXPORT_SYMBOLS_ARG, None)
+            .flag(KEEP_TEMPS_ARG, None)
+            .flag(WARN_UNIMPLEMENTED_ARG, None)
+            .flag(TEST_COMPONENTS_ARG, None)
             .counting("--verbose", Some('v'))
             .flag("--quiet", Some('q'));
 
@@ -443,6 +626,7 @@ impl CargoArguments {
             locked: args.get("--locked").unwrap().count() > 0,
             offline: args.get("--offline").unwrap().count() > 0,
             release: args.get("--release").unwrap().count() > 0,
+            profile: args.get_mut("--profile").unwrap().take_single(),
             workspace: args.get("--workspace").unwrap().count() > 0
                 || args.get("--all").unwrap().count() > 0,
             packages: args
@@ -452,10 +636,71 @@ impl CargoArguments {
                 .into_iter()
                 .map(CargoPackageSpec::new)
                 .collect::<Result<_>>()?,
+            message_format: args.get_mut("--message-format").unwrap().take_single(),
+            features: args
+                .get_mut("--features")
+                .unwrap()
+                .take_multiple()
+                .iter()
+                .flat_map(|f| f.split([' ', ',']))
+                .filter(|f| !f.is_empty())
+                .map(ToString::to_string)
+                .collect(),
+            all_features: args.get("--all-features").unwrap().count() > 0,
+            no_default_features: args.get("--no-default-features").unwrap().count() > 0,
+            explain_encoding: args.get(EXPLAIN_ENCODING_ARG).unwrap().count() > 0,
+            strict: args.get(STRICT_ARG).unwrap().count() > 0,
+            smoke_test: args.get(SMOKE_TEST_ARG).unwrap().count() > 0,
+            export_symbols: args.get(EXPORT_SYMBOLS_ARG).unwrap().count() > 0,
+            keep_temps: args.get(KEEP_TEMPS_ARG).unwrap().count() > 0,
+            warn_unimplemented: args.get(WARN_UNIMPLEMENTED_ARG).unwrap().count() > 0,
+            test_components: args.get(TEST_COMPONENTS_ARG).unwrap().count() > 0,
+            dump_component_type: args
+                .get_mut(DUMP_COMPONENT_TYPE_ARG)
+                .unwrap()
+                .take_single()
+                .map(PathBuf::from),
+            temp_dir: args
+                .get_mut(TEMP_DIR_ARG)
+                .unwrap()
+                .take_single()
+                .map(PathBuf::from),
+            output: args
+                .get_mut(OUTPUT_ARG)
+                .unwrap()
+                .take_single()
+                .map(PathBuf::from),
+            cargo: args
+                .get_mut(CARGO_ARG)
+                .unwrap()
+                .take_single()
+                .map(PathBuf::from),
+            reuse_lock_from: args
+                .get_mut(REUSE_LOCK_FROM_ARG)
+                .unwrap()
+                .take_single()
+                .map(PathBuf::from),
         })
     }
 }
 
+/// The environment variable used to set the build number of components.
+pub const BUILD_NUMBER_ENV_VAR: &str = "CARGO_COMPONENT_BUILD_NUMBER";
+
+/// The environment variable used to override the `runtime-path` setting of
+/// components.
+pub const RUNTIME_PATH_ENV_VAR: &str = "CARGO_COMPONENT_RUNTIME_PATH";
+
+/// The environment variable used to override the cache directory.
+///
+/// Registry and content caches are placed in subdirectories of the
+/// configured directory.
+pub const CACHE_DIR_ENV_VAR: &str = "CARGO_COMPONENT_CACHE_DIR";
+
+/// The environment variable used to set the directory for intermediate
+/// build artifacts when `--temp-dir` is not given.
+pub const TEMP_DIR_ENV_VAR: &str = "CARGO_COMPONENT_TEMP_DIR";
+
 /// Configuration information for cargo-component.
 ///
 /// This is used to configure the behavior of cargo-component.
@@ -470,10 +715,14 @@ pub struct Config {
 impl Config {
     /// Create a new `Config` with the given terminal.
     pub fn new(terminal: Terminal) -> Result<Self> {
-        Ok(Self {
-            warg: warg_client::Config::from_default_file()?.unwrap_or_default(),
-            terminal,
-        })
+        let mut warg = warg_client::Config::from_default_file()?.unwrap_or_default();
+        if let Some(dir) = std::env::var_os(CACHE_DIR_ENV_VAR) {
+            let dir = PathBuf::from(dir);
+            warg.registries_dir = Some(dir.join("registries"));
+            warg.content_dir = Some(dir.join("content"));
+        }
+
+        Ok(Self { warg, terminal })
     }
 
     /// Gets the warg client configuration.
@@ -757,11 +1006,28 @@ mod test {
                 targets: Vec::new(),
                 manifest_path: None,
                 release: false,
+                profile: None,
                 frozen: false,
                 locked: false,
                 offline: false,
                 workspace: true,
                 packages: Vec::new(),
+                message_format: None,
+                features: Vec::new(),
+                all_features: false,
+                no_default_features: false,
+                explain_encoding: false,
+                strict: false,
+                smoke_test: false,
+                export_symbols: false,
+                keep_temps: false,
+                warn_unimplemented: false,
+                test_components: false,
+                dump_component_type: None,
+                temp_dir: None,
+                output: None,
+                cargo: None,
+                reuse_lock_from: None,
             }
         );
 
@@ -774,6 +1040,8 @@ mod test {
                 "--manifest-path",
                 "Cargo.toml",
                 "--release",
+                "--profile",
+                "custom",
                 "--package",
                 "package1",
                 "-p=package2@1.1.1",
@@ -785,6 +1053,27 @@ mod test {
                 "--locked",
                 "--offline",
                 "--all",
+                "--message-format=short",
+                "--features",
+                "foo,bar baz",
+                "-F=qux",
+                "--all-features",
+                "--no-default-features",
+                "--explain-encoding",
+                "--strict",
+                "--smoke-test",
+                "--export-symbols",
+                "--keep-temps",
+                "--warn-unimplemented",
+                "--components",
+                "--dump-component-type",
+                "component.wit",
+                "--temp-dir=/tmp/component",
+                "-o",
+                "dist/foo.wasm",
+                "--cargo=/usr/local/bin/cargo-wrapper",
+                "--reuse-lock-from",
+                "../other/Cargo-component.lock",
                 "--not-an-option",
             ]
             .into_iter(),
@@ -799,6 +1088,7 @@ mod test {
                 targets: vec!["foo".to_string(), "bar".to_string()],
                 manifest_path: Some("Cargo.toml".into()),
                 release: true,
+                profile: Some("custom".to_string()),
                 frozen: true,
                 locked: true,
                 offline: true,
@@ -813,7 +1103,32 @@ mod test {
                         version: Some(Version::parse("1.1.1").unwrap())
                     }
                 ],
+                message_format: Some("short".to_string()),
+                features: vec![
+                    "foo".to_string(),
+                    "bar".to_string(),
+                    "baz".to_string(),
+                    "qux".to_string()
+                ],
+                all_features: true,
+                no_default_features: true,
+                explain_encoding: true,
+                strict: true,
+                smoke_test: true,
+                export_symbols: true,
+                keep_temps: true,
+                warn_unimplemented: true,
+                test_components: true,
+                dump_component_type: Some("component.wit".into()),
+                temp_dir: Some("/tmp/component".into()),
+                output: Some("dist/foo.wasm".into()),
+                cargo: Some("/usr/local/bin/cargo-wrapper".into()),
+                reuse_lock_from: Some("../other/Cargo-component.lock".into()),
             }
         );
+        assert!(args.short_message_format());
+        assert_eq!(args.profile(), "custom");
+        assert_eq!(args.profile_dir(), "custom");
+        assert_eq!(args.cargo(), PathBuf::from("/usr/local/bin/cargo-wrapper"));
     }
 }
@@ -0,0 +1,210 @@
+//! Module for comparing the worlds of components.
+
+use crate::bindings::world_key_name;
+use indexmap::IndexMap;
+use wit_parser::{
+    Function, Handle, Resolve, Type, TypeDef, TypeDefKind, TypeOwner, WorldId, WorldItem, WorldKey,
+};
+
+/// Gets the imports of the component world that the host world does not provide.
+pub(crate) fn unmet_imports(
+    component: &Resolve,
+    component_world: WorldId,
+    host: &Resolve,
+    host_world: WorldId,
+) -> Vec<String> {
+    unmet_items(
+        component,
+        component.worlds[component_world].imports.iter(),
+        host,
+        &host.worlds[host_world].imports,
+    )
+}
+
+/// Gets the exports of the target world that the component world does not
+/// provide.
+pub(crate) fn missing_exports(
+    target: &Resolve,
+    target_world: WorldId,
+    component: &Resolve,
+    component_world: WorldId,
+) -> Vec<String> {
+    unmet_items(
+        target,
+        target.worlds[target_world].exports.iter(),
+        component,
+        &component.worlds[component_world].exports,
+    )
+}
+
+/// Gets the given items that are not provided, with the same types, by the
+/// items of another world.
+pub(crate) fn unmet_items<'a>(
+    component: &Resolve,
+    items: impl Iterator<Item = (&'a WorldKey, &'a WorldItem)>,
+    host: &Resolve,
+    provided: &IndexMap<WorldKey, WorldItem>,
+) -> Vec<String> {
+    let mut unmet = Vec::new();
+
+    for (key, item) in items {
+        let name = world_key_name(component, key);
+        let provided = provided
+            .iter()
+            .find(|(k, _)| world_key_name(host, k) == name)
+            .map(|(_, item)| item);
+
+        match (item, provided) {
+            (WorldItem::Interface(id), Some(WorldItem::Interface(host_id))) => {
+                let host_interface = &host.interfaces[*host_id];
+                for (function_name, function) in &component.interfaces[*id].functions {
+                    match host_interface.functions.get(function_name) {
+                        Some(host_function)
+                            if functions_match(component, function, host, host_function) => {}
+                        Some(_) => unmet.push(format!(
+                            "function `{function_name}` of interface `{name}` (mismatched signature)"
+                        )),
+                        None => unmet.push(format!(
+                            "function `{function_name}` of interface `{name}`"
+                        )),
+                    }
+                }
+            }
+            (WorldItem::Function(function), Some(WorldItem::Function(host_function))) => {
+                if !functions_match(component, function, host, host_function) {
+                    unmet.push(format!("`{name}` (mismatched signature)"));
+                }
+            }
+            (WorldItem::Type(ty), Some(WorldItem::Type(host_ty))) => {
+                if !types_match(component, &Type::Id(*ty), host, &Type::Id(*host_ty)) {
+                    unmet.push(format!("`{name}` (mismatched type)"));
+                }
+            }
+            _ => unmet.push(format!("`{name}`")),
+        }
+    }
+
+    unmet
+}
+
+/// Determines if a host function satisfies the import of a component
+/// function by comparing the types of their parameters and results.
+fn functions_match(
+    component: &Resolve,
+    function: &Function,
+    host: &Resolve,
+    host_function: &Function,
+) -> bool {
+    function.params.len() == host_function.params.len()
+        && function.results.len() == host_function.results.len()
+        && function
+            .params
+            .iter()
+            .zip(&host_function.params)
+            .all(|((_, ty), (_, host_ty))| types_match(component, ty, host, host_ty))
+        && function
+            .results
+            .iter_types()
+            .zip(host_function.results.iter_types())
+            .all(|(ty, host_ty)| types_match(component, ty, host, host_ty))
+}
+
+/// Determines if two types of different resolves are structurally the same.
+///
+/// Type aliases are compared by the types they refer to, and resources by
+/// their names and the names of the interfaces or worlds defining them.
+fn types_match(component: &Resolve, ty: &Type, host: &Resolve, host_ty: &Type) -> bool {
+    match (dealias(component, *ty), dealias(host, *host_ty)) {
+        (Type::Id(id), Type::Id(host_id)) => {
+            type_defs_match(component, &component.types[id], host, &host.types[host_id])
+        }
+        (ty, host_ty) => ty == host_ty,
+    }
+}
+
+fn type_defs_match(component: &Resolve, ty: &TypeDef, host: &Resolve, host_ty: &TypeDef) -> bool {
+    let types = |ty: &Type, host_ty: &Type| types_match(component, ty, host, host_ty);
+    let optional_types = |ty: &Option<Type>, host_ty: &Option<Type>| match (ty, host_ty) {
+        (Some(ty), Some(host_ty)) => types(ty, host_ty),
+        (None, None) => true,
+        _ => false,
+    };
+
+    match (&ty.kind, &host_ty.kind) {
+        (TypeDefKind::Record(record), TypeDefKind::Record(host_record)) => {
+            record.fields.len() == host_record.fields.len()
+                && record
+                    .fields
+                    .iter()
+                    .zip(&host_record.fields)
+                    .all(|(f, h)| f.name == h.name && types(&f.ty, &h.ty))
+        }
+        (TypeDefKind::Resource, TypeDefKind::Resource) => {
+            ty.name == host_ty.name
+                && owner_name(component, ty.owner) == owner_name(host, host_ty.owner)
+        }
+        (TypeDefKind::Handle(Handle::Own(id)), TypeDefKind::Handle(Handle::Own(host_id)))
+        | (TypeDefKind::Handle(Handle::Borrow(id)), TypeDefKind::Handle(Handle::Borrow(host_id))) => {
+            types(&Type::Id(*id), &Type::Id(*host_id))
+        }
+        (TypeDefKind::Flags(flags), TypeDefKind::Flags(host_flags)) => flags
+            .flags
+            .iter()
+            .map(|f| &f.name)
+            .eq(host_flags.flags.iter().map(|f| &f.name)),
+        (TypeDefKind::Tuple(tuple), TypeDefKind::Tuple(host_tuple)) => {
+            tuple.types.len() == host_tuple.types.len()
+                && tuple
+                    .types
+                    .iter()
+                    .zip(&host_tuple.types)
+                    .all(|(t, h)| types(t, h))
+        }
+        (TypeDefKind::Variant(variant), TypeDefKind::Variant(host_variant)) => {
+            variant.cases.len() == host_variant.cases.len()
+                && variant
+                    .cases
+                    .iter()
+                    .zip(&host_variant.cases)
+                    .all(|(c, h)| c.name == h.name && optional_types(&c.ty, &h.ty))
+        }
+        (TypeDefKind::Enum(e), TypeDefKind::Enum(host_enum)) => e
+            .cases
+            .iter()
+            .map(|c| &c.name)
+            .eq(host_enum.cases.iter().map(|c| &c.name)),
+        (TypeDefKind::Option(ty), TypeDefKind::Option(host_ty))
+        | (TypeDefKind::List(ty), TypeDefKind::List(host_ty)) => types(ty, host_ty),
+        (TypeDefKind::Result(result), TypeDefKind::Result(host_result)) => {
+            optional_types(&result.ok, &host_result.ok)
+                && optional_types(&result.err, &host_result.err)
+        }
+        (TypeDefKind::Future(ty), TypeDefKind::Future(host_ty)) => optional_types(ty, host_ty),
+        (TypeDefKind::Stream(stream), TypeDefKind::Stream(host_stream)) => {
+            optional_types(&stream.element, &host_stream.element)
+                && optional_types(&stream.end, &host_stream.end)
+        }
+        _ => false,
+    }
+}
+
+/// Resolves a type through any aliases to the type it refers to.
+fn dealias(resolve: &Resolve, mut ty: Type) -> Type {
+    while let Type::Id(id) = ty {
+        match resolve.types[id].kind {
+            TypeDefKind::Type(aliased) => ty = aliased,
+            _ => break,
+        }
+    }
+
+    ty
+}
+
+/// Gets the name of the interface or world that defines a type.
+fn owner_name(resolve: &Resolve, owner: TypeOwner) -> Option<String> {
+    match owner {
+        TypeOwner::Interface(id) => resolve.id_of(id),
+        TypeOwner::World(id) => Some(resolve.worlds[id].name.clone()),
+        TypeOwner::None => None,
+    }
+}
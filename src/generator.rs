@@ -96,7 +96,7 @@ impl UseTrie {
     /// This method handles the proper casing for path segments and type names.
     ///
     /// Returns the string to use when printing the type reference.
-    fn insert<'a, I>(&mut self, path: I, ty: &str) -> Cow<str>
+    fn insert<'a, I>(&mut self, path: I, ty: &str) -> Cow<'_, str>
     where
         I: IntoIterator<Item = &'a str>,
         I::IntoIter: Clone,
@@ -152,7 +152,7 @@ impl UseTrie {
         resolve: &Resolve,
         interface: &Interface,
         ty: &str,
-    ) -> Cow<str> {
+    ) -> Cow<'_, str> {
         let pkg = &resolve.packages[interface.package.expect("interface should have a package")];
         let name = interface.name.as_deref().expect("unnamed interface");
 
@@ -169,7 +169,7 @@ impl UseTrie {
     }
 
     /// Inserts an export trait for the given world key.
-    fn insert_export_trait(&mut self, resolve: &Resolve, key: &WorldKey) -> Cow<str> {
+    fn insert_export_trait(&mut self, resolve: &Resolve, key: &WorldKey) -> Cow<'_, str> {
         match key {
             WorldKey::Name(name) => self.insert(["bindings", "exports", name.as_str()], "Guest"),
             WorldKey::Interface(id) => {
@@ -2,23 +2,33 @@
 
 #![deny(missing_docs)]
 
-use crate::target::install_wasm32_wasi;
+use crate::target::install_target;
 use anyhow::{bail, Context, Result};
-use bindings::BindingsEncoder;
+use bindings::{BindingsEncoder, ExportedFunction};
 use bytes::Bytes;
 use cargo_component_core::{
-    lock::{LockFile, LockFileResolver, LockedPackage, LockedPackageVersion},
-    registry::create_client,
-    terminal::Colors,
+    lock::{FileLock, LockFile, LockFileResolver, LockedPackage, LockedPackageVersion},
+    registry::{create_client, DependencyResolution},
+    terminal::{Colors, Verbosity},
+};
+use cargo_metadata::{
+    camino::{Utf8Path, Utf8PathBuf},
+    CargoOpt, Metadata, MetadataCommand, Package,
+};
+use config::{
+    CargoArguments, CargoPackageSpec, Config, BUILD_NUMBER_ENV_VAR, CARGO_ARG,
+    DUMP_COMPONENT_TYPE_ARG, EXPLAIN_ENCODING_ARG, EXPORT_SYMBOLS_ARG, KEEP_TEMPS_ARG, OUTPUT_ARG,
+    OUTPUT_SHORT_ARG, REUSE_LOCK_FROM_ARG, RUNTIME_PATH_ENV_VAR, SMOKE_TEST_ARG, STRICT_ARG,
+    TEMP_DIR_ARG, TEST_COMPONENTS_ARG, WARN_UNIMPLEMENTED_ARG,
 };
-use cargo_metadata::{Metadata, MetadataCommand, Package};
-use config::{CargoArguments, CargoPackageSpec, Config};
 use lock::{acquire_lock_file_ro, acquire_lock_file_rw};
-use metadata::ComponentMetadata;
+use metadata::{Adapter, ComponentMetadata, Strip, Target};
 use registry::{PackageDependencyResolution, PackageResolutionMap};
 use semver::Version;
 use std::{
     borrow::Cow,
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    fmt::Write,
     fs::{self, File},
     io::Read,
     path::{Path, PathBuf},
@@ -26,22 +36,98 @@ use std::{
     time::{Duration, SystemTime},
 };
 use warg_client::storage::{ContentStorage, PublishEntry, PublishInfo};
-use warg_crypto::signing::PrivateKey;
+use warg_crypto::{
+    hash::{AnyHash, Digest, Hash, HashAlgorithm, Sha256},
+    signing::PrivateKey,
+};
 use warg_protocol::registry::PackageId;
-use wasm_metadata::{Link, LinkType, RegistryMetadata};
-use wit_component::ComponentEncoder;
+use wasm_encoder::{ComponentSection, CustomSection, RawSection, Section};
+use wasm_metadata::{AddMetadata, Link, LinkType, RegistryMetadata};
+use wit_component::{ComponentEncoder, DecodedWasm, WitPrinter};
+use wit_parser::{Resolve, WorldId, WorldItem, WorldKey};
 
 mod bindings;
 pub mod commands;
 pub mod config;
+mod conformance;
 mod generator;
 mod lock;
 mod metadata;
 mod registry;
-mod target;
+#[cfg(feature = "smoke-test")]
+mod smoke;
+pub mod target;
+mod unimplemented;
+
+/// The version of `wit-bindgen` used to generate bindings.
+///
+/// This is the version used by the `cargo-component-bindings` crate
+/// released alongside this version of `cargo-component`.
+pub const WIT_BINDGEN_VERSION: &str = env!("WIT_BINDGEN_VERSION");
+
+/// The name of the `wit-bindgen` crate that generates Rust bindings.
+const WIT_BINDGEN_CODEGEN_CRATE: &str = "wit-bindgen-rust";
+
+/// The name of the directory, next to a build's output modules or in the
+/// temporary directory, in which encoded components are cached.
+const COMPONENT_CACHE_DIR: &str = "component-cache";
+
+/// The name of the directory, next to a build's output modules, in which the
+/// intermediate artifacts of creating components are kept with `--keep-temps`.
+const COMPONENT_DEBUG_DIR: &str = "component-debug";
+
+/// The name of the module adapted by the WASI adapter.
+const WASI_ADAPTER_NAME: &str = "wasi_snapshot_preview1";
+
+/// The WebAssembly targets that components can be built for.
+///
+/// The WASI targets are adapted with the bundled WASI preview1 adapter.
+const WASM_TARGETS: &[&str] = &["wasm32-wasi", "wasm32-wasip1", "wasm32-unknown-unknown"];
+
+/// The target components are built for when no WebAssembly target is given.
+pub(crate) const DEFAULT_WASM_TARGET: &str = "wasm32-wasi";
 
 fn is_wasm_target(target: &str) -> bool {
-    target == "wasm32-wasi" || target == "wasm32-unknown-unknown"
+    WASM_TARGETS.contains(&target)
+}
+
+/// Gets the WebAssembly targets selected by the given cargo arguments.
+///
+/// If no WebAssembly target is selected, the default target is used.
+fn wasm_targets(cargo_args: &CargoArguments) -> impl Iterator<Item = &str> + Clone {
+    let targets = cargo_args
+        .targets
+        .iter()
+        .map(String::as_str)
+        .filter(|t| is_wasm_target(t));
+    let default = targets
+        .clone()
+        .next()
+        .is_none()
+        .then_some(DEFAULT_WASM_TARGET);
+    targets.chain(default)
+}
+
+/// Checks that the given WebAssembly targets are supported.
+///
+/// Non-WebAssembly targets are passed through to cargo unchecked.
+fn check_wasm_targets(targets: &[String]) -> Result<()> {
+    for target in targets {
+        if (target.starts_with("wasm32-") || target.starts_with("wasm64-"))
+            && !is_wasm_target(target)
+        {
+            bail!(
+                "WebAssembly target `{target}` is not supported; supported targets are {supported}",
+                supported = WASM_TARGETS
+                    .iter()
+                    .map(|t| format!("`{t}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+    }
+
+    Ok(())
 }
 
 /// Represents a cargo package paired with its component metadata.
@@ -64,6 +150,31 @@ impl<'a> PackageComponentMetadata<'a> {
     }
 }
 
+/// The error returned when a spawned cargo command exits with a failure.
+#[derive(Debug)]
+pub struct CargoCommandError {
+    code: Option<i32>,
+}
+
+impl CargoCommandError {
+    /// Gets the exit code of the cargo command, if it was not terminated by
+    /// a signal.
+    pub fn code(&self) -> Option<i32> {
+        self.code
+    }
+}
+
+impl std::fmt::Display for CargoCommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.code {
+            Some(code) => write!(f, "cargo exited with status code {code}"),
+            None => write!(f, "cargo was terminated by a signal"),
+        }
+    }
+}
+
+impl std::error::Error for CargoCommandError {}
+
 /// Runs the cargo command as specified in the configuration.
 ///
 /// Note: if the command returns a non-zero status, this
@@ -78,12 +189,70 @@ pub async fn run_cargo_command(
     cargo_args: &CargoArguments,
     spawn_args: &[String],
 ) -> Result<Vec<PathBuf>> {
-    encode_targets(config, metadata, packages, cargo_args).await?;
+    run_cargo_command_with_env(
+        config,
+        metadata,
+        packages,
+        subcommand,
+        cargo_args,
+        spawn_args,
+        &[],
+    )
+    .await
+}
 
-    let cargo = std::env::var("CARGO")
-        .map(PathBuf::from)
-        .ok()
-        .unwrap_or_else(|| PathBuf::from("cargo"));
+/// Runs the cargo command as specified in the configuration, setting the
+/// given environment variables for the spawned cargo process only.
+///
+/// Note: if the command returns a non-zero status, this
+/// function will exit the process.
+pub(crate) async fn run_cargo_command_with_env(
+    config: &Config,
+    metadata: &Metadata,
+    packages: &[PackageComponentMetadata<'_>],
+    subcommand: Option<&str>,
+    cargo_args: &CargoArguments,
+    spawn_args: &[String],
+    env: &[(&str, &str)],
+) -> Result<Vec<PathBuf>> {
+    spawn_cargo_command(
+        config, metadata, packages, subcommand, cargo_args, spawn_args, env,
+    )
+    .await
+    .map(|outputs| outputs.into_iter().map(|(_, path)| path).collect())
+    .inspect_err(|e| {
+        if let Some(e) = e.downcast_ref::<CargoCommandError>() {
+            std::process::exit(e.code().unwrap_or(1));
+        }
+    })
+}
+
+/// Runs the cargo command as specified in the configuration, returning a
+/// [`CargoCommandError`] if the command fails.
+async fn spawn_cargo_command(
+    config: &Config,
+    metadata: &Metadata,
+    packages: &[PackageComponentMetadata<'_>],
+    subcommand: Option<&str>,
+    cargo_args: &CargoArguments,
+    spawn_args: &[String],
+    env: &[(&str, &str)],
+) -> Result<Vec<(cargo_metadata::PackageId, PathBuf)>> {
+    check_wasm_targets(&cargo_args.targets)?;
+
+    // The dependency graph is only resolved when it is needed, as resolving it
+    // may access the network
+    let resolved = needs_dependency_graph(config, packages)
+        .then(|| load_resolved_metadata(cargo_args))
+        .transpose()?;
+    let packages = &apply_feature_worlds(packages, cargo_args, resolved.as_ref())?;
+    let EncodedTargets {
+        type_components,
+        deprecations,
+        exports,
+    } = encode_targets(config, metadata, packages, cargo_args, resolved.as_ref()).await?;
+
+    let cargo = cargo_args.cargo();
 
     let mut args = spawn_args.iter().peekable();
     if let Some(arg) = args.peek() {
@@ -92,6 +261,52 @@ pub async fn run_cargo_command(
         }
     }
 
+    // Remove arguments specific to cargo-component before the first `--`
+    let mut passthrough = false;
+    let mut value = false;
+    let args = args.filter(move |arg| {
+        passthrough |= *arg == "--";
+        if passthrough {
+            return true;
+        }
+
+        // Remove the value of an argument specific to cargo-component
+        if std::mem::take(&mut value) {
+            return false;
+        }
+
+        if [
+            DUMP_COMPONENT_TYPE_ARG,
+            TEMP_DIR_ARG,
+            OUTPUT_ARG,
+            OUTPUT_SHORT_ARG,
+            CARGO_ARG,
+            REUSE_LOCK_FROM_ARG,
+        ]
+        .contains(&arg.as_str())
+        {
+            value = true;
+            return false;
+        }
+
+        ![
+            EXPLAIN_ENCODING_ARG,
+            STRICT_ARG,
+            SMOKE_TEST_ARG,
+            EXPORT_SYMBOLS_ARG,
+            KEEP_TEMPS_ARG,
+            WARN_UNIMPLEMENTED_ARG,
+            TEST_COMPONENTS_ARG,
+        ]
+        .contains(&arg.as_str())
+            && !arg.starts_with(&format!("{DUMP_COMPONENT_TYPE_ARG}="))
+            && !arg.starts_with(&format!("{TEMP_DIR_ARG}="))
+            && !arg.starts_with(&format!("{OUTPUT_ARG}="))
+            && !arg.starts_with(&format!("{CARGO_ARG}="))
+            && !arg.starts_with(&format!("{REUSE_LOCK_FROM_ARG}="))
+            && (!arg.starts_with(OUTPUT_SHORT_ARG) || arg.starts_with("--"))
+    });
+
     // Spawn the actual cargo command
     log::debug!(
         "spawning cargo `{cargo}` with arguments `{args:?}`",
@@ -100,24 +315,45 @@ pub async fn run_cargo_command(
     );
 
     let mut cmd = Command::new(&cargo);
-    cmd.args(args);
+    cmd.args(args).envs(env.iter().copied());
 
     let is_build = matches!(subcommand, Some("b") | Some("build") | Some("rustc"));
 
-    // Handle the target for build commands
-    if is_build {
-        install_wasm32_wasi(config)?;
+    // Checks are for the same target as builds, but produce no components
+    let is_check = matches!(subcommand, Some("c") | Some("check"));
 
-        // Add an implicit wasm32-wasi target if there isn't a wasm target present
+    // Handle the target for build and check commands
+    if is_build || is_check {
+        for target in wasm_targets(cargo_args) {
+            install_target(config, target)?;
+        }
+
+        // Add an implicit target if there isn't a wasm target present
         if !cargo_args.targets.iter().any(|t| is_wasm_target(t)) {
-            cmd.arg("--target").arg("wasm32-wasi");
+            cmd.arg("--target").arg(DEFAULT_WASM_TARGET);
+        }
+
+        let rustflags = component_rustflags(packages);
+        if !rustflags.is_empty() {
+            if config.terminal().verbosity() == Verbosity::Verbose {
+                config.terminal().note(format!(
+                    "adding component rustflags to the wasm compilation: `{flags}`",
+                    flags = rustflags.join(" ")
+                ))?;
+            }
+
+            add_rustflags(&mut cmd, cargo_args, &rustflags);
         }
     }
 
     match cmd.status() {
         Ok(status) => {
             if !status.success() {
-                std::process::exit(status.code().unwrap_or(1));
+                report_deprecations(config, &deprecations)?;
+                return Err(CargoCommandError {
+                    code: status.code(),
+                }
+                .into());
             }
         }
         Err(e) => {
@@ -128,46 +364,89 @@ pub async fn run_cargo_command(
     let mut outputs = Vec::new();
     if is_build {
         log::debug!("searching for WebAssembly modules to componentize");
-        let targets = cargo_args
-            .targets
-            .iter()
-            .map(String::as_str)
-            .filter(|t| is_wasm_target(t))
-            .chain(cargo_args.targets.is_empty().then_some("wasm32-wasi"));
+        let targets = wasm_targets(cargo_args);
+
+        let components = packages.iter().filter(|p| p.metadata.is_some()).count();
+        let multiple = components > 1 || targets.clone().count() > 1;
+        if let Some(path) = &cargo_args.dump_component_type {
+            if multiple {
+                bail!(
+                    "cannot dump the types of multiple components to `{path}`",
+                    path = path.display()
+                );
+            }
+        }
+
+        if let Some(path) = &cargo_args.output {
+            if multiple {
+                bail!(
+                    "cannot write multiple components to output file `{path}`",
+                    path = path.display()
+                );
+            }
+        }
 
+        let bindings_dir = metadata.target_directory.join("bindings");
         for target in targets {
-            let out_dir = metadata
-                .target_directory
-                .join(target)
-                .join(if cargo_args.release {
-                    "release"
-                } else {
-                    "debug"
-                });
+            let out_dir = target_output_dir(metadata, target, cargo_args);
 
+            let mut output_names = HashMap::new();
             for PackageComponentMetadata { package, metadata } in packages {
                 let metadata = match metadata {
                     Some(metadata) => metadata,
                     None => continue,
                 };
 
-                let is_bin = package.targets.iter().any(|t| t.is_bin());
+                let output_name = metadata.output_name();
+                if let Some(other) = output_names.insert(output_name, &package.name) {
+                    bail!(
+                        "packages `{other}` and `{name}` both output component `{output_name}.wasm`",
+                        name = package.name
+                    );
+                }
 
-                // First try for <name>.wasm
-                let path = out_dir.join(&package.name).with_extension("wasm");
-                if path.exists() {
-                    create_component(config, metadata, path.as_std_path(), is_bin)?;
-                    outputs.push(path.to_path_buf().into_std_path_buf());
+                if let Some(encoded) = type_components.get(&package.id) {
+                    let path = out_dir.join(output_name).with_extension("wasm");
+                    write_type_component(config, path.as_std_path(), encoded)?;
+                    outputs.push((package.id.clone(), path.into_std_path_buf()));
                     continue;
                 }
 
-                // Next, try replacing `-` with `_`
-                let path = out_dir
-                    .join(package.name.replace('-', "_"))
-                    .with_extension("wasm");
-                if path.exists() {
-                    create_component(config, metadata, path.as_std_path(), is_bin)?;
-                    outputs.push(path.to_path_buf().into_std_path_buf());
+                let is_bin = package.targets.iter().any(|t| t.is_bin());
+
+                if let Some(path) = find_cargo_output(&out_dir, package) {
+                    create_component(
+                        config,
+                        metadata,
+                        path.as_std_path(),
+                        Some(target),
+                        Some(cargo_args.profile()),
+                        is_bin,
+                        cargo_args,
+                    )?;
+
+                    let output = out_dir.join(output_name).with_extension("wasm");
+                    let renamed = if metadata.section.output_name.is_some() && path != output {
+                        rename_component(config, &path, &output)?
+                    } else {
+                        path.clone()
+                    };
+
+                    if cargo_args.json_message_format() {
+                        print_component_message(
+                            package,
+                            metadata,
+                            bindings_dir.as_std_path(),
+                            path.as_std_path(),
+                            renamed.as_std_path(),
+                            target,
+                            cargo_args,
+                        )?;
+                    }
+
+                    let path = renamed;
+
+                    outputs.push((package.id.clone(), path.into_std_path_buf()));
                     continue;
                 }
 
@@ -176,9 +455,343 @@ pub async fn run_cargo_command(
         }
     }
 
+    if let (Some(output), [(_, path)]) = (&cargo_args.output, outputs.as_slice()) {
+        copy_output(config, path, output)?;
+    }
+
+    if cargo_args.smoke_test {
+        let paths: Vec<_> = outputs.iter().map(|(_, path)| path.clone()).collect();
+        smoke_test(config, &paths)?;
+    }
+
+    if is_build && cargo_args.warn_unimplemented {
+        warn_unimplemented_exports(config, packages, &exports)?;
+    }
+
+    report_deprecations(config, &deprecations)?;
+
     Ok(outputs)
 }
 
+/// Gets the name of the world targeted by the component of a package.
+fn component_world(
+    package: &Package,
+    metadata: &ComponentMetadata,
+    bindings_dir: &Path,
+) -> Option<String> {
+    // The world of a cargo target may differ from the package's target world
+    match package
+        .targets
+        .iter()
+        .filter(|t| {
+            t.kind
+                .iter()
+                .any(|k| matches!(k.as_str(), "bin" | "lib" | "cdylib"))
+        })
+        .find_map(|t| metadata.section.target_worlds.get(&t.name))
+    {
+        Some(world) => Some(world.clone()),
+        None => fs::read_to_string(bindings_dir.join(&metadata.name).join("world")).ok(),
+    }
+}
+
+/// Gets the directory of a target triple to which cargo writes the outputs
+/// of the profile being built.
+fn target_output_dir(
+    metadata: &Metadata,
+    target: &str,
+    cargo_args: &CargoArguments,
+) -> Utf8PathBuf {
+    metadata
+        .target_directory
+        .join(target)
+        .join(cargo_args.profile_dir())
+}
+
+/// Finds the WebAssembly output that cargo wrote for a package.
+fn find_cargo_output(out_dir: &Utf8Path, package: &Package) -> Option<Utf8PathBuf> {
+    // First try for <name>.wasm, then try replacing `-` with `_`
+    [package.name.clone(), package.name.replace('-', "_")]
+        .into_iter()
+        .map(|name| out_dir.join(name).with_extension("wasm"))
+        .find(|path| path.exists())
+}
+
+/// Prints a JSON message for a built component to stdout.
+///
+/// The message is printed alongside the JSON messages of cargo when
+/// `--message-format=json` is given.
+fn print_component_message(
+    package: &Package,
+    metadata: &ComponentMetadata,
+    bindings_dir: &Path,
+    module: &Path,
+    path: &Path,
+    target: &str,
+    cargo_args: &CargoArguments,
+) -> Result<()> {
+    let world = component_world(package, metadata, bindings_dir);
+
+    let adapter = !matches!(
+        metadata
+            .section
+            .adapter
+            .select(Some(target), Some(cargo_args.profile())),
+        Adapter::None
+    );
+
+    let mut message = serde_json::json!({
+        "reason": "component-artifact",
+        "package_id": package.id.repr,
+        "name": package.name,
+        "target": target,
+        "component": cargo_args.output.as_deref().unwrap_or(path),
+        "adapter": adapter,
+        "world": world,
+    });
+
+    // The module built by cargo is replaced by the component, so it is only
+    // reported when it is kept in the temporary directory
+    if let Some(dir) = cargo_args.temp_dir() {
+        message["module"] = serde_json::json!(staged_module_path(&dir, module));
+    }
+
+    println!("{message}");
+    Ok(())
+}
+
+/// Copies a built component to the path given with `--output`.
+///
+/// The component in the target directory is kept for incremental builds.
+fn copy_output(config: &Config, path: &Path, output: &Path) -> Result<()> {
+    if let Some(parent) = output.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).with_context(|| {
+            format!(
+                "failed to create output directory `{parent}`",
+                parent = parent.display()
+            )
+        })?;
+    }
+
+    fs::copy(path, output).with_context(|| {
+        format!(
+            "failed to copy component `{path}` to `{output}`",
+            path = path.display(),
+            output = output.display()
+        )
+    })?;
+
+    config.terminal().status(
+        "Copied",
+        format!(
+            "component {path} to {output}",
+            path = path.display(),
+            output = output.display()
+        ),
+    )
+}
+
+/// Moves a built component to the file named by the component's output name.
+///
+/// The module built by cargo is restored at its original path by the next
+/// build and componentized again from the component cache.
+fn rename_component(config: &Config, path: &Utf8Path, output: &Utf8Path) -> Result<Utf8PathBuf> {
+    fs::rename(path, output)
+        .with_context(|| format!("failed to move component `{path}` to `{output}`"))?;
+
+    config
+        .terminal()
+        .status("Renamed", format!("component {path} to {output}"))?;
+    Ok(output.to_path_buf())
+}
+
+/// Prints a summary of the deprecated WIT items used by the target worlds of
+/// the built packages.
+///
+/// The deprecations are given per package name.
+fn report_deprecations(
+    config: &Config,
+    deprecations: &BTreeMap<String, Vec<String>>,
+) -> Result<()> {
+    let count: usize = deprecations.values().map(Vec::len).sum();
+    if count == 0 {
+        return Ok(());
+    }
+
+    let mut message = format!(
+        "the target worlds of this build use {count} deprecated WIT item{s}",
+        s = if count == 1 { "" } else { "s" }
+    );
+
+    for (name, items) in deprecations {
+        write!(message, "\n\npackage `{name}`:")?;
+        for item in items {
+            write!(message, "\n  {item}")?;
+        }
+    }
+
+    write!(
+        message,
+        "\n\ndeprecated items may be removed in a future version of the WIT \
+         packages that define them; use `{STRICT_ARG}` to fail the build instead"
+    )?;
+
+    config.terminal().warn(message)
+}
+
+/// Warns about the exports of the target worlds that are implemented with only
+/// a `todo!()` or `unimplemented!()` in the source of component packages.
+fn warn_unimplemented_exports(
+    config: &Config,
+    packages: &[PackageComponentMetadata<'_>],
+    exports: &HashMap<cargo_metadata::PackageId, Vec<ExportedFunction>>,
+) -> Result<()> {
+    for PackageComponentMetadata { package, .. } in packages {
+        let exports = match exports.get(&package.id) {
+            Some(exports) if !exports.is_empty() => exports,
+            _ => continue,
+        };
+
+        let mut unimplemented = Vec::new();
+        for target in package.targets.iter().filter(|t| {
+            t.kind
+                .iter()
+                .any(|k| matches!(k.as_str(), "bin" | "lib" | "cdylib"))
+        }) {
+            unimplemented.extend(unimplemented::find_unimplemented_exports(
+                target.src_path.as_std_path(),
+                exports,
+            )?);
+        }
+
+        unimplemented.sort();
+        unimplemented.dedup();
+        if unimplemented.is_empty() {
+            continue;
+        }
+
+        let mut message = format!(
+            "package `{name}` has {count} unimplemented export{s}:",
+            name = package.name,
+            count = unimplemented.len(),
+            s = if unimplemented.len() == 1 { "" } else { "s" }
+        );
+
+        for export in unimplemented {
+            write!(message, "\n  `{export}`")?;
+        }
+
+        config.terminal().warn(message)?;
+    }
+
+    Ok(())
+}
+
+/// Instantiates the given components with wasmtime to catch linking errors.
+#[cfg(feature = "smoke-test")]
+fn smoke_test(config: &Config, outputs: &[PathBuf]) -> Result<()> {
+    for path in outputs {
+        smoke::instantiate(path)?;
+        config.terminal().status(
+            "Instantiated",
+            format!("component {path}", path = path.display()),
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(not(feature = "smoke-test"))]
+fn smoke_test(_: &Config, _: &[PathBuf]) -> Result<()> {
+    bail!("`{SMOKE_TEST_ARG}` requires `cargo-component` to be built with the `smoke-test` feature")
+}
+
+/// Gets the additional `rustc` flags for building the given packages.
+///
+/// This combines the `rustflags`, `panic`, and `link-args` settings of each
+/// package.
+fn component_rustflags(packages: &[PackageComponentMetadata<'_>]) -> Vec<String> {
+    packages
+        .iter()
+        .filter_map(|p| p.metadata.as_ref())
+        .flat_map(|metadata| {
+            let section = &metadata.section;
+            section
+                .rustflags
+                .iter()
+                .cloned()
+                .chain(section.panic.map(|panic| format!("-Cpanic={panic}")))
+                .chain(
+                    section
+                        .link_args
+                        .iter()
+                        .map(|arg| format!("-Clink-arg={arg}")),
+                )
+        })
+        .collect()
+}
+
+/// Adds the given flags to the `rustc` flags of the wasm targets being built.
+///
+/// If the flags are set with `CARGO_ENCODED_RUSTFLAGS` or `RUSTFLAGS`, cargo
+/// ignores any configured flags, so the flags are appended to the variable;
+/// otherwise, the flags are added to the configuration of each wasm target so
+/// that they do not affect the host (e.g. build scripts).
+fn add_rustflags(cmd: &mut Command, cargo_args: &CargoArguments, flags: &[String]) {
+    if let Ok(encoded) = std::env::var("CARGO_ENCODED_RUSTFLAGS") {
+        let mut all: Vec<_> = encoded
+            .split('\x1f')
+            .filter(|f| !f.is_empty())
+            .map(ToString::to_string)
+            .collect();
+        all.extend(flags.iter().cloned());
+        cmd.env("CARGO_ENCODED_RUSTFLAGS", all.join("\x1f"));
+        return;
+    }
+
+    if let Ok(rustflags) = std::env::var("RUSTFLAGS") {
+        let mut all: Vec<_> = rustflags
+            .split_whitespace()
+            .map(ToString::to_string)
+            .collect();
+        all.extend(flags.iter().cloned());
+        cmd.env_remove("RUSTFLAGS");
+        cmd.env("CARGO_ENCODED_RUSTFLAGS", all.join("\x1f"));
+        return;
+    }
+
+    let value = toml_edit::Value::from_iter(flags.iter().map(String::as_str));
+    for target in wasm_targets(cargo_args) {
+        cmd.arg("--config")
+            .arg(format!("target.{target}.rustflags={value}"));
+    }
+}
+
+/// Renders an error for cargo's short message format.
+///
+/// The error chain is collapsed to a single line. If a cause in the chain
+/// carries a source location (e.g. a WIT parse error), the location is
+/// returned separately so it may be printed as `file: error: message`.
+pub fn short_error_message(e: &anyhow::Error) -> (Option<String>, String) {
+    let mut location = None;
+    let mut messages = Vec::new();
+    for cause in e.chain() {
+        let cause = cause.to_string();
+        let mut lines = cause.lines();
+        if let Some(first) = lines.next() {
+            messages.push(first.trim().to_string());
+        }
+
+        if location.is_none() {
+            location = lines
+                .find_map(|l| l.trim_start().strip_prefix("--> "))
+                .map(|l| l.trim().to_string());
+        }
+    }
+
+    (location, messages.join(": "))
+}
+
 fn last_modified_time(path: &Path) -> Result<SystemTime> {
     path.metadata()
         .with_context(|| {
@@ -222,76 +835,407 @@ pub fn load_component_metadata<'a>(
     specs: impl ExactSizeIterator<Item = &'a CargoPackageSpec>,
     workspace: bool,
 ) -> Result<Vec<PackageComponentMetadata<'a>>> {
-    let pkgs = if workspace {
-        metadata.workspace_packages()
-    } else if specs.len() > 0 {
-        let mut pkgs = Vec::with_capacity(specs.len());
-        for spec in specs {
-            let pkg = metadata
-                .packages
-                .iter()
-                .find(|p| {
-                    p.name == spec.name
-                        && match spec.version.as_ref() {
-                            Some(v) => &p.version == v,
-                            None => true,
-                        }
-                })
-                .with_context(|| {
-                    format!("package ID specification `{spec}` did not match any packages")
-                })?;
-            pkgs.push(pkg);
-        }
+    select_packages(metadata, specs, workspace)?
+        .into_iter()
+        .map(PackageComponentMetadata::new)
+        .collect::<Result<_>>()
+}
 
-        pkgs
-    } else {
+/// Selects the cargo packages given by the package specs or the workspace.
+fn select_packages<'a>(
+    metadata: &'a Metadata,
+    specs: impl ExactSizeIterator<Item = &'a CargoPackageSpec>,
+    workspace: bool,
+) -> Result<Vec<&'a Package>> {
+    if workspace {
+        return Ok(metadata.workspace_packages());
+    }
+
+    if specs.len() == 0 {
         // TODO: this should be the default members, or default to all members
         // However, `cargo-metadata` doesn't return the workspace default members yet
         // See: https://github.com/oli-obk/cargo_metadata/issues/215
-        metadata.workspace_packages()
-    };
+        return Ok(metadata.workspace_packages());
+    }
 
-    pkgs.into_iter()
-        .map(PackageComponentMetadata::new)
-        .collect::<Result<_>>()
+    specs
+        .map(|spec| find_package(metadata, Some(spec)))
+        .collect()
 }
 
-async fn encode_targets(
-    config: &Config,
-    metadata: &Metadata,
-    packages: &[PackageComponentMetadata<'_>],
-    cargo_args: &CargoArguments,
-) -> Result<()> {
-    let bindings_dir = metadata.target_directory.join("bindings");
-    let file_lock = acquire_lock_file_ro(config.terminal(), metadata)?;
-    let lock_file = file_lock
-        .as_ref()
-        .map(|f| {
-            LockFile::read(f.file()).with_context(|| {
-                format!(
-                    "failed to read lock file `{path}`",
-                    path = f.path().display()
-                )
+/// Finds the cargo package given by the package spec.
+///
+/// If no package spec is given, the root package of the workspace is returned.
+pub fn find_package<'a>(
+    metadata: &'a Metadata,
+    spec: Option<&CargoPackageSpec>,
+) -> Result<&'a Package> {
+    match spec {
+        Some(spec) => metadata
+            .packages
+            .iter()
+            .find(|p| {
+                p.name == spec.name
+                    && match spec.version.as_ref() {
+                        Some(v) => &p.version == v,
+                        None => true,
+                    }
             })
-        })
-        .transpose()?;
+            .with_context(|| {
+                format!("package ID specification `{spec}` did not match any packages")
+            }),
+        None => metadata
+            .root_package()
+            .context("no root package found in manifest"),
+    }
+}
 
-    let resolver = lock_file.as_ref().map(LockFileResolver::new);
-    let map =
-        create_resolution_map(config, packages, resolver, cargo_args.network_allowed()).await?;
-    for PackageComponentMetadata { package, .. } in packages {
-        let resolution = match map.get(&package.id) {
-            Some(resolution) => resolution,
+/// Determines if building the given packages needs the dependency graph
+/// resolved by cargo.
+///
+/// The graph is needed to select the target worlds of enabled features and
+/// to find the `wit-bindgen` version of the bindings, which is checked when a
+/// package requires a version and reported with verbose output.
+fn needs_dependency_graph(config: &Config, packages: &[PackageComponentMetadata<'_>]) -> bool {
+    config.terminal().verbosity() == Verbosity::Verbose
+        || packages
+            .iter()
+            .filter_map(|p| p.metadata.as_ref())
+            .any(|m| !m.section.features.is_empty() || m.section.wit_bindgen.is_some())
+}
+
+/// Loads the workspace metadata with the dependency graph resolved by cargo
+/// for the feature arguments.
+fn load_resolved_metadata(cargo_args: &CargoArguments) -> Result<Metadata> {
+    let mut command = MetadataCommand::new();
+    command.cargo_path(cargo_args.cargo());
+
+    if let Some(path) = &cargo_args.manifest_path {
+        command.manifest_path(path);
+    }
+
+    if cargo_args.all_features {
+        command.features(CargoOpt::AllFeatures);
+    }
+
+    if cargo_args.no_default_features {
+        command.features(CargoOpt::NoDefaultFeatures);
+    }
+
+    if !cargo_args.features.is_empty() {
+        command.features(CargoOpt::SomeFeatures(cargo_args.features.clone()));
+    }
+
+    let options = [
+        (cargo_args.offline, "--offline"),
+        (cargo_args.locked, "--locked"),
+        (cargo_args.frozen, "--frozen"),
+    ];
+    command.other_options(
+        options
+            .into_iter()
+            .filter(|(set, _)| *set)
+            .map(|(_, option)| option.to_string())
+            .collect::<Vec<_>>(),
+    );
+
+    let metadata = command
+        .exec()
+        .context("failed to resolve the dependency graph of the workspace")?;
+    if metadata.resolve.is_none() {
+        bail!("cargo metadata did not resolve the dependency graph");
+    }
+
+    Ok(metadata)
+}
+
+/// Gets the cargo features of the given packages enabled by the feature
+/// arguments, as resolved by cargo.
+fn enabled_features(
+    packages: &[PackageComponentMetadata<'_>],
+    resolved: &Metadata,
+) -> HashMap<cargo_metadata::PackageId, HashSet<String>> {
+    resolved
+        .resolve
+        .as_ref()
+        .unwrap()
+        .nodes
+        .iter()
+        .filter(|node| packages.iter().any(|p| p.package.id == node.id))
+        .map(|node| (node.id.clone(), node.features.iter().cloned().collect()))
+        .collect()
+}
+
+/// Gets the version of the `wit-bindgen-rust` crate that generates the
+/// bindings of each of the given packages, as resolved by cargo.
+///
+/// The crate is found in the dependencies of a package, typically through
+/// the `cargo-component-bindings` crate. A package that does not depend on
+/// it is assumed to use the version of this release.
+fn codegen_versions(
+    packages: &[PackageComponentMetadata<'_>],
+    resolved: &Metadata,
+) -> Result<HashMap<cargo_metadata::PackageId, Version>> {
+    let resolve = resolved.resolve.as_ref().unwrap();
+    let by_id: HashMap<_, _> = resolved.packages.iter().map(|p| (&p.id, p)).collect();
+    let nodes: HashMap<_, _> = resolve.nodes.iter().map(|n| (&n.id, n)).collect();
+
+    let mut versions = HashMap::new();
+    for PackageComponentMetadata { package, metadata } in packages {
+        if metadata.is_none() {
+            continue;
+        }
+
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::from([&package.id]);
+        while let Some(id) = queue.pop_front() {
+            if !visited.insert(id) {
+                continue;
+            }
+
+            match by_id.get(id) {
+                Some(dep) if dep.name == WIT_BINDGEN_CODEGEN_CRATE => {
+                    versions.insert(package.id.clone(), dep.version.clone());
+                    break;
+                }
+                _ => {}
+            }
+
+            if let Some(node) = nodes.get(id) {
+                queue.extend(node.deps.iter().map(|dep| &dep.pkg));
+            }
+        }
+
+        if !versions.contains_key(&package.id) {
+            log::debug!(
+                "package `{name}` does not depend on `{WIT_BINDGEN_CODEGEN_CRATE}`; assuming wit-bindgen v{WIT_BINDGEN_VERSION}",
+                name = package.name
+            );
+            versions.insert(
+                package.id.clone(),
+                WIT_BINDGEN_VERSION
+                    .parse()
+                    .context("invalid wit-bindgen version")?,
+            );
+        }
+    }
+
+    Ok(versions)
+}
+
+/// Applies the target worlds of the enabled cargo features of the given
+/// packages.
+///
+/// The world of an enabled feature replaces the target world of the package.
+///
+/// The dependency graph is resolved for the enabled features unless it has
+/// already been resolved.
+fn apply_feature_worlds<'a>(
+    packages: &[PackageComponentMetadata<'a>],
+    cargo_args: &CargoArguments,
+    resolved: Option<&Metadata>,
+) -> Result<Vec<PackageComponentMetadata<'a>>> {
+    let any_features = packages
+        .iter()
+        .filter_map(|p| p.metadata.as_ref())
+        .any(|m| !m.section.features.is_empty());
+    let enabled = match (any_features, resolved) {
+        (false, _) => HashMap::new(),
+        (true, Some(resolved)) => enabled_features(packages, resolved),
+        (true, None) => enabled_features(packages, &load_resolved_metadata(cargo_args)?),
+    };
+
+    packages
+        .iter()
+        .map(|PackageComponentMetadata { package, metadata }| {
+            let mut metadata = metadata.clone();
+            if let Some(metadata) = &mut metadata {
+                let enabled = enabled.get(&package.id);
+                let mut selected: Option<(&str, &str)> = None;
+                for (feature, settings) in &metadata.section.features {
+                    if !package.features.contains_key(feature) {
+                        bail!(
+                            "`package.metadata.component.features` of package `{name}` contains `{feature}`, which is not a feature of the package",
+                            name = package.name
+                        );
+                    }
+
+                    let world = match (
+                        &settings.world,
+                        enabled.is_some_and(|e| e.contains(feature)),
+                    ) {
+                        (Some(world), true) => world,
+                        _ => continue,
+                    };
+
+                    match selected {
+                        Some((other, other_world)) if other_world != world => {
+                            let (first, second) = if other < feature.as_str() {
+                                (other, feature.as_str())
+                            } else {
+                                (feature.as_str(), other)
+                            };
+                            bail!(
+                                "features `{first}` and `{second}` of package `{name}` select different target worlds",
+                                name = package.name
+                            );
+                        }
+                        _ => selected = Some((feature, world)),
+                    }
+                }
+
+                if let Some((feature, world)) = selected {
+                    log::debug!(
+                        "feature `{feature}` of package `{name}` selects target world `{world}`",
+                        name = package.name
+                    );
+                    let world = world.to_string();
+                    metadata.section.target.set_world(world);
+                }
+            }
+
+            Ok(PackageComponentMetadata {
+                package,
+                metadata,
+            })
+        })
+        .collect()
+}
+
+/// The result of encoding the targets of component packages.
+struct EncodedTargets {
+    /// The encoded type components of packages without a world.
+    type_components: HashMap<cargo_metadata::PackageId, Vec<u8>>,
+    /// The deprecated WIT items used, keyed by package name.
+    deprecations: BTreeMap<String, Vec<String>>,
+    /// The functions exported by the target worlds, keyed by package.
+    ///
+    /// This is only populated with `--warn-unimplemented`.
+    exports: HashMap<cargo_metadata::PackageId, Vec<ExportedFunction>>,
+}
+
+async fn encode_targets(
+    config: &Config,
+    metadata: &Metadata,
+    packages: &[PackageComponentMetadata<'_>],
+    cargo_args: &CargoArguments,
+    resolved: Option<&Metadata>,
+) -> Result<EncodedTargets> {
+    let bindings_dir = metadata.target_directory.join("bindings");
+    let file_lock = acquire_lock_file_ro(config.terminal(), metadata)?;
+    let lock_file = file_lock
+        .as_ref()
+        .map(|f| {
+            LockFile::read(f.file()).with_context(|| {
+                format!(
+                    "failed to read lock file `{path}`",
+                    path = f.path().display()
+                )
+            })
+        })
+        .transpose()?;
+
+    // Packages not in the lock file may be resolved from another lock file
+    let reused_lock_file = cargo_args
+        .reuse_lock_from
+        .as_deref()
+        .map(|path| {
+            FileLock::open_ro(path)
+                .and_then(|f| LockFile::read(f.file()))
+                .with_context(|| {
+                    format!(
+                        "failed to read lock file `{path}` to reuse",
+                        path = path.display()
+                    )
+                })
+        })
+        .transpose()?;
+
+    let resolver = match (lock_file.as_ref(), reused_lock_file.as_ref()) {
+        (Some(lock_file), Some(reused)) => {
+            Some(LockFileResolver::new(lock_file).with_fallback(reused))
+        }
+        (lock_file, reused) => lock_file.or(reused).map(LockFileResolver::new),
+    };
+
+    let map =
+        create_resolution_map(config, packages, resolver, cargo_args.network_allowed()).await?;
+    let codegen_versions = resolved
+        .map(|resolved| codegen_versions(packages, resolved))
+        .transpose()?
+        .unwrap_or_default();
+    let mut type_components = HashMap::new();
+    let mut deprecations = BTreeMap::new();
+    let mut exports = HashMap::new();
+    for PackageComponentMetadata { package, .. } in packages {
+        let resolution = match map.get(&package.id) {
+            Some(resolution) => resolution,
             None => continue,
         };
 
-        encode_target_world(config, resolution, bindings_dir.as_std_path()).await?;
+        // A target without a world is built as a type component; there is
+        // no world to generate bindings for
+        if let Some(encoded) = bindings::encode_type_package(resolution)? {
+            type_components.insert(package.id.clone(), encoded);
+            continue;
+        }
+
+        let warnings = encode_target_world(
+            config,
+            resolution,
+            codegen_versions.get(&package.id),
+            bindings_dir.as_std_path(),
+            cargo_args.explain_encoding,
+            cargo_args.strict,
+        )
+        .await?;
+
+        if !warnings.is_empty() {
+            deprecations.insert(resolution.metadata.name.clone(), warnings);
+        }
+
+        if cargo_args.warn_unimplemented {
+            let encoder = BindingsEncoder::new(resolution)?;
+            exports.insert(package.id.clone(), encoder.exported_functions());
+        }
+    }
+
+    // Local path dependencies that changed since they were locked keep their
+    // locked digest until `cargo component update` locks their contents again
+    let mut new_lock_file = map.to_lock_file(metadata.workspace_root.as_std_path())?;
+    if let Some(lock_file) = &lock_file {
+        for path in &mut new_lock_file.paths {
+            if let Some(locked) = lock_file.find_path(&path.id, &path.path) {
+                if locked.digest != path.digest {
+                    config.terminal().warn(format!(
+                        "path dependency `{id}` at `{path}` has changed since it was locked; \
+                         run `cargo component update` to lock its current contents",
+                        id = path.id,
+                        path = path.path
+                    ))?;
+                    path.digest = locked.digest.clone();
+                }
+            }
+        }
     }
 
-    // Update the lock file if it exists or if the new lock file is non-empty
-    let new_lock_file = map.to_lock_file();
-    if (lock_file.is_some() || !new_lock_file.packages.is_empty())
+    // With `--locked` or `--frozen`, only a change to the registry
+    // dependencies is an error; local path dependencies are not locked
+    // against drift and projects without external dependencies need no lock
+    // file at all
+    let registry_changed = match &lock_file {
+        Some(lock_file) => {
+            new_lock_file.packages != lock_file.packages
+                || new_lock_file.components != lock_file.components
+        }
+        None => !new_lock_file.packages.is_empty() || !new_lock_file.components.is_empty(),
+    };
+
+    // Update the lock file if it exists or if the new lock file is non-empty;
+    // a lock file is not created for packages without any dependencies
+    if (lock_file.is_some() || !new_lock_file.is_empty())
         && Some(&new_lock_file) != lock_file.as_ref()
+        && (cargo_args.lock_update_allowed() || registry_changed || lock_file.is_none())
     {
         drop(file_lock);
         let file_lock = acquire_lock_file_rw(
@@ -310,218 +1254,1873 @@ async fn encode_targets(
             })?;
     }
 
-    Ok(())
+    Ok(EncodedTargets {
+        type_components,
+        deprecations,
+        exports,
+    })
 }
 
-async fn create_resolution_map<'a>(
+/// Prints the resolved target world of the given package to stdout.
+///
+/// The world is printed as WIT unless `json` is true, in which case a JSON
+/// representation of the world is printed instead.
+pub async fn print_target_world(
     config: &Config,
-    packages: &'a [PackageComponentMetadata<'_>],
-    lock_file: Option<LockFileResolver<'_>>,
+    metadata: &Metadata,
+    package: &PackageComponentMetadata<'_>,
+    json: bool,
+    list_sources: bool,
     network_allowed: bool,
-) -> Result<PackageResolutionMap<'a>> {
-    let mut map = PackageResolutionMap::default();
+) -> Result<()> {
+    let map = resolve_package(config, metadata, package, network_allowed).await?;
+    let resolution = package_resolution(&map, package)?;
 
-    for PackageComponentMetadata { package, metadata } in packages {
-        match metadata {
-            Some(metadata) => {
-                let resolution =
-                    PackageDependencyResolution::new(config, metadata, lock_file, network_allowed)
-                        .await?;
-                map.insert(package.id.clone(), resolution);
+    let encoder = BindingsEncoder::new(resolution)?;
+    if list_sources {
+        let mut printed = HashSet::new();
+        for path in encoder.source_files() {
+            if printed.insert(path) {
+                config
+                    .terminal()
+                    .write_stdout(format!("{path}\n", path = path.display()), None)?;
             }
-            None => continue,
         }
+
+        return Ok(());
     }
 
-    Ok(map)
+    let output = if json {
+        serde_json::to_string_pretty(&encoder.to_json())?
+    } else {
+        encoder.to_wit()?
+    };
+
+    config.terminal().write_stdout(format!("{output}\n"), None)
 }
 
-async fn encode_target_world(
+/// Prints the WIT of the built component of the given package to stdout.
+///
+/// The component is found where a build with the given cargo arguments
+/// writes it: in the output directory of the first WebAssembly target (or
+/// `wasm32-wasi`) and profile, under its `output-name` if configured.
+pub fn print_component_wit(
     config: &Config,
-    resolution: &PackageDependencyResolution<'_>,
-    bindings_dir: &Path,
+    metadata: &Metadata,
+    package: &PackageComponentMetadata<'_>,
+    cargo_args: &CargoArguments,
 ) -> Result<()> {
-    let output_dir = bindings_dir.join(&resolution.metadata.name);
-    let target_path = output_dir.join("target.wasm");
-    let world_path = output_dir.join("world");
-
-    let last_modified_output = target_path
-        .is_file()
-        .then(|| last_modified_time(&target_path))
-        .transpose()?
-        .unwrap_or(SystemTime::UNIX_EPOCH);
+    if let Some(target) = cargo_args.targets.iter().find(|t| !is_wasm_target(t)) {
+        bail!("target `{target}` is not a WebAssembly target");
+    }
 
-    let encoder = BindingsEncoder::new(resolution)?;
-    match encoder.reason(last_modified_output)? {
-        Some(reason) => {
-            ::log::debug!(
-                "encoding target for package `{name}` at `{path}` because {reason}",
-                name = resolution.metadata.name,
-                path = target_path.display(),
-            );
+    let target = wasm_targets(cargo_args).next().unwrap();
+    let out_dir = target_output_dir(metadata, target, cargo_args);
 
-            config.terminal().status(
-                "Encoding",
-                format!(
-                    "target for {name} ({path})",
-                    name = resolution.metadata.name,
-                    path = target_path.display()
-                ),
-            )?;
+    // A renamed component is preferred over the output written by cargo
+    let path = package
+        .metadata
+        .as_ref()
+        .map(|m| out_dir.join(m.output_name()).with_extension("wasm"))
+        .filter(|path| path.exists())
+        .or_else(|| find_cargo_output(&out_dir, package.package))
+        .with_context(|| {
+            let mut command = "cargo component build".to_string();
+            if let Some(profile) = &cargo_args.profile {
+                command.push_str(&format!(" --profile {profile}"));
+            } else if cargo_args.release {
+                command.push_str(" --release");
+            }
+            if target != DEFAULT_WASM_TARGET {
+                command.push_str(&format!(" --target {target}"));
+            }
 
-            let encoded = encoder.encode()?;
-            fs::create_dir_all(&output_dir).with_context(|| {
-                format!(
-                    "failed to create output directory `{path}`",
-                    path = output_dir.display()
-                )
-            })?;
+            format!(
+                "package `{name}` has no built component in `{out_dir}`; build it with `{command}`",
+                name = package.package.name,
+            )
+        })?;
 
-            fs::write(&target_path, encoded).with_context(|| {
-                format!(
-                    "failed to write target file `{path}`",
-                    path = target_path.display()
-                )
-            })?;
+    let bytes = fs::read(&path).with_context(|| format!("failed to read component `{path}`"))?;
+    let wit = bindings::decode_wit(&bytes)
+        .with_context(|| format!("failed to decode the WIT of component `{path}`"))?;
 
-            let world = resolution.metadata.section.target.world().unwrap_or("");
+    config.terminal().write_stdout(format!("{wit}\n"), None)
+}
 
-            fs::write(&world_path, world).with_context(|| {
-                format!(
-                    "failed to write world name `{path}`",
-                    path = world_path.display()
-                )
-            })?;
-        }
-        None => {
-            ::log::debug!(
-                "existing target encoding for package `{name}` at `{path}` is up-to-date",
-                name = resolution.metadata.name,
-                path = target_path.display(),
+/// Generates the Rust bindings of the target world of the given package.
+///
+/// If `bin` is specified and the binary target has its own world in
+/// `target-worlds`, the bindings of that world are generated instead.
+///
+/// The world of an enabled cargo feature replaces the target world of the
+/// package.
+pub async fn generate_bindings(
+    config: &Config,
+    metadata: &Metadata,
+    package: &PackageComponentMetadata<'_>,
+    bin: Option<&str>,
+    cargo_args: &CargoArguments,
+) -> Result<String> {
+    if let Some(bin) = bin {
+        if !package
+            .package
+            .targets
+            .iter()
+            .any(|t| t.name == bin && t.kind.iter().any(|k| k == "bin"))
+        {
+            bail!(
+                "package `{name}` has no binary target named `{bin}`",
+                name = package.package.name
             );
         }
     }
 
-    Ok(())
-}
-
-fn is_wasm_module(path: impl AsRef<Path>) -> Result<bool> {
-    let path = path.as_ref();
+    // The target world may be selected by the enabled features
+    let packages = apply_feature_worlds(std::slice::from_ref(package), cargo_args, None)?;
+    let package = &packages[0];
+    let map = resolve_package(config, metadata, package, cargo_args.network_allowed()).await?;
+    let resolution = package_resolution(&map, package)?;
+
+    if !resolution
+        .metadata
+        .section
+        .bindings
+        .to_macro_options()
+        .is_empty()
+    {
+        config.terminal().warn(format!(
+            "the bindings options in manifest `{path}` are not applied to the printed bindings; \
+             use `cargo component expand` to print the bindings as `generate!` generates them",
+            path = package.package.manifest_path
+        ))?;
+    }
 
-    let mut file = File::open(path)
-        .with_context(|| format!("failed to open `{path}` for read", path = path.display()))?;
+    let encoder = BindingsEncoder::new(resolution)?;
+    let world = match bin.and_then(|bin| resolution.metadata.section.target_worlds.get(bin)) {
+        Some(world) => world.clone(),
+        None => encoder.world_name().to_string(),
+    };
 
-    let mut bytes = [0u8; 8];
-    file.read(&mut bytes).with_context(|| {
+    let encoded = encoder.encode()?;
+    bindings::generate_rust_bindings(
+        &encoded,
+        &world,
+        runtime_path(resolution.metadata).as_deref(),
+    )
+    .with_context(|| {
         format!(
-            "failed to read file header for `{path}`",
-            path = path.display()
+            "failed to generate bindings for package `{name}`",
+            name = package.package.name
         )
-    })?;
-
-    if bytes[0..4] != [0x0, b'a', b's', b'm'] {
-        bail!(
-            "expected `{path}` to be a WebAssembly module",
-            path = path.display()
-        );
-    }
-
-    // Check for the module header version
-    Ok(bytes[4..] == [0x01, 0x00, 0x00, 0x00])
+    })
 }
 
-fn adapter_bytes(metadata: &ComponentMetadata, binary: bool) -> Result<Cow<[u8]>> {
-    if let Some(adapter) = &metadata.section.adapter {
-        return Ok(fs::read(adapter)
-            .with_context(|| {
+/// Resolves the dependencies of a single component package.
+async fn resolve_package<'a>(
+    config: &Config,
+    metadata: &Metadata,
+    package: &'a PackageComponentMetadata<'_>,
+    network_allowed: bool,
+) -> Result<PackageResolutionMap<'a>> {
+    let file_lock = acquire_lock_file_ro(config.terminal(), metadata)?;
+    let lock_file = file_lock
+        .as_ref()
+        .map(|f| {
+            LockFile::read(f.file()).with_context(|| {
                 format!(
-                    "failed to read module adapter `{path}`",
-                    path = adapter.display()
+                    "failed to read lock file `{path}`",
+                    path = f.path().display()
                 )
-            })?
-            .into());
-    }
-
-    if binary {
-        Ok(Cow::Borrowed(include_bytes!(concat!(
+            })
+        })
+        .transpose()?;
+
+    let resolver = lock_file.as_ref().map(LockFileResolver::new);
+    let packages = std::slice::from_ref(package);
+    create_resolution_map(config, packages, resolver, network_allowed).await
+}
+
+/// Gets the dependency resolution of a package from a resolution map.
+fn package_resolution<'a, 'b>(
+    map: &'b PackageResolutionMap<'a>,
+    package: &PackageComponentMetadata<'_>,
+) -> Result<&'b PackageDependencyResolution<'a>> {
+    map.get(&package.package.id).with_context(|| {
+        format!(
+            "package `{name}` is missing component metadata in manifest `{path}`",
+            name = package.package.name,
+            path = package.package.manifest_path
+        )
+    })
+}
+
+/// Prints the dependency tree of a component package to stdout.
+///
+/// The tree shows the WIT package of the target and the WIT packages it
+/// depends on, transitively, followed by the component dependencies of the
+/// package. A package whose dependencies were already printed is marked with
+/// `(*)`, and a dependency cycle is reported rather than followed.
+pub async fn print_dependency_tree(
+    config: &Config,
+    metadata: &Metadata,
+    package: &PackageComponentMetadata<'_>,
+    network_allowed: bool,
+) -> Result<()> {
+    let map = resolve_package(config, metadata, package, network_allowed).await?;
+    let resolution = package_resolution(&map, package)?;
+    let graph = bindings::target_package_graph(resolution)?;
+    let manifest_dir = package
+        .package
+        .manifest_path
+        .parent()
+        .context("manifest path has no parent directory")?
+        .as_std_path();
+
+    let source = |resolution: &DependencyResolution| match resolution {
+        DependencyResolution::Registry(registry) => match &registry.registry {
+            Some(name) => format!("v{version}, registry `{name}`", version = registry.version),
+            None => format!("v{version}", version = registry.version),
+        },
+        DependencyResolution::Local(local) => format!(
+            "path {path}",
+            path = local
+                .path
+                .strip_prefix(manifest_dir)
+                .unwrap_or(&local.path)
+                .display()
+        ),
+    };
+
+    // Target dependencies are keyed by the name of the package they define
+    let target_sources: HashMap<String, String> = resolution
+        .target_resolutions
+        .values()
+        .map(|res| {
+            let id = match res {
+                DependencyResolution::Registry(registry) => &registry.package,
+                DependencyResolution::Local(local) => local.package.as_ref().unwrap_or(&local.id),
+            };
+            (id.to_string(), source(res))
+        })
+        .collect();
+
+    let mut tree = TreePrinter::default();
+    writeln!(
+        tree.out,
+        "{name} v{version}",
+        name = package.package.name,
+        version = package.package.version
+    )?;
+
+    let mut components: Vec<_> = resolution.resolutions.values().collect();
+    components.sort_by_key(|res| res.id().to_string());
+
+    if let Some(root) = &graph.root {
+        tree.package(
+            &graph,
+            &target_sources,
+            root,
+            "target",
+            components.is_empty(),
+        )?;
+    }
+
+    for (i, res) in components.iter().enumerate() {
+        tree.line(
+            &format!(
+                "{id} (component dependency, {source})",
+                id = res.id(),
+                source = source(res)
+            ),
+            i == components.len() - 1,
+        )?;
+    }
+
+    for cycle in &tree.cycles {
+        config
+            .terminal()
+            .warn(format!("dependency cycle detected: {cycle}"))?;
+    }
+
+    config.terminal().write_stdout(tree.out, None)
+}
+
+/// Renders a dependency tree with cargo's `cargo tree` line style.
+#[derive(Default)]
+struct TreePrinter {
+    out: String,
+    /// Whether each open level of the tree is on its last child.
+    levels: Vec<bool>,
+    /// The packages on the path from the root to the current package.
+    path: Vec<String>,
+    /// The packages whose dependencies were printed.
+    printed: HashSet<String>,
+    /// The dependency cycles found.
+    cycles: Vec<String>,
+}
+
+impl TreePrinter {
+    fn line(&mut self, label: &str, last: bool) -> Result<()> {
+        for level in &self.levels {
+            self.out.push_str(if *level { "    " } else { "│   " });
+        }
+
+        writeln!(
+            self.out,
+            "{branch}{label}",
+            branch = if last { "└── " } else { "├── " }
+        )?;
+        Ok(())
+    }
+
+    fn package(
+        &mut self,
+        graph: &bindings::PackageGraph,
+        sources: &HashMap<String, String>,
+        name: &wit_parser::PackageName,
+        kind: &str,
+        last: bool,
+    ) -> Result<()> {
+        let key = name.to_string();
+        let id = format!("{ns}:{name}", ns = name.namespace, name = name.name);
+        let mut label = match sources.get(&id) {
+            Some(source) => format!("{key} ({kind}, {source})"),
+            None if self.path.is_empty() => format!("{key} ({kind})"),
+            None => key.clone(),
+        };
+
+        if let Some(start) = self.path.iter().position(|p| *p == key) {
+            let mut cycle = self.path[start..].to_vec();
+            cycle.push(key);
+            self.cycles.push(cycle.join(" -> "));
+            label.push_str(" (cycle)");
+            return self.line(&label, last);
+        }
+
+        let deps = graph
+            .dependencies
+            .get(name)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        if !self.printed.insert(key.clone()) && !deps.is_empty() {
+            label.push_str(" (*)");
+            return self.line(&label, last);
+        }
+
+        self.line(&label, last)?;
+        self.levels.push(last);
+        self.path.push(key);
+        for (i, dep) in deps.iter().enumerate() {
+            self.package(
+                graph,
+                sources,
+                dep,
+                "target dependency",
+                i == deps.len() - 1,
+            )?;
+        }
+        self.path.pop();
+        self.levels.pop();
+        Ok(())
+    }
+}
+
+/// Formats the local WIT files of the given packages.
+///
+/// Only whitespace is changed; a file whose canonical form would remove
+/// comments or reorder items is left unchanged with a warning, as is a WIT
+/// package defined in multiple files.
+///
+/// If `check` is true, the files are not modified and an error is returned
+/// if any file is not formatted.
+pub async fn format_wit(
+    config: &Config,
+    packages: &[PackageComponentMetadata<'_>],
+    check: bool,
+    network_allowed: bool,
+) -> Result<()> {
+    let map = create_resolution_map(config, packages, None, network_allowed).await?;
+
+    let mut unformatted = 0;
+    for PackageComponentMetadata { package, .. } in packages {
+        let resolution = match map.get(&package.id) {
+            Some(resolution) => resolution,
+            None => continue,
+        };
+
+        for formatted in bindings::format_local_target(resolution)? {
+            let path = match formatted.files.as_slice() {
+                [path] => path,
+                _ => {
+                    config.terminal().warn(format!(
+                        "skipping formatting of WIT package `{name}` as it is defined in multiple files",
+                        name = formatted.name
+                    ))?;
+                    continue;
+                }
+            };
+
+            let contents = fs::read_to_string(path).with_context(|| {
+                format!("failed to read WIT file `{path}`", path = path.display())
+            })?;
+
+            if contents == formatted.source {
+                continue;
+            }
+
+            // The canonical form drops comments that are not doc comments and
+            // may reorder items, so only whitespace changes are written
+            if !differs_only_in_whitespace(&contents, &formatted.source) {
+                config.terminal().warn(format!(
+                    "skipping formatting of WIT file `{path}` as it would remove comments or reorder items",
+                    path = path.display()
+                ))?;
+                continue;
+            }
+
+            if check {
+                config.terminal().error(format!(
+                    "WIT file `{path}` is not formatted",
+                    path = path.display()
+                ))?;
+                unformatted += 1;
+                continue;
+            }
+
+            fs::write(path, &formatted.source).with_context(|| {
+                format!("failed to write WIT file `{path}`", path = path.display())
+            })?;
+
+            config.terminal().status(
+                "Formatted",
+                format!("WIT file `{path}`", path = path.display()),
+            )?;
+        }
+    }
+
+    if unformatted > 0 {
+        bail!(
+            "{unformatted} WIT file{s} not formatted",
+            s = if unformatted == 1 { " is" } else { "s are" }
+        );
+    }
+
+    Ok(())
+}
+
+/// Determines if two WIT sources differ only in whitespace.
+fn differs_only_in_whitespace(a: &str, b: &str) -> bool {
+    a.chars()
+        .filter(|c| !c.is_whitespace())
+        .eq(b.chars().filter(|c| !c.is_whitespace()))
+}
+
+/// Vendors the target dependencies of the given package into the given
+/// directory, relative to the package directory, and updates the manifest of
+/// the package to refer to the vendored copies.
+///
+/// Each dependency is written as either a directory containing its WIT
+/// package, with the packages it depends on in a nested `deps` directory, or
+/// as a WebAssembly file.
+pub async fn vendor_wit(
+    config: &Config,
+    metadata: &Metadata,
+    package: &PackageComponentMetadata<'_>,
+    out: &Path,
+    network_allowed: bool,
+) -> Result<()> {
+    if let Some(Target::Package { .. }) = package.metadata.as_ref().map(|m| &m.section.target) {
+        bail!(
+            "cannot vendor the target dependencies of package `{name}` as it targets a registry package",
+            name = package.package.name
+        );
+    }
+
+    let map = resolve_package(config, metadata, package, network_allowed).await?;
+    let resolution = package_resolution(&map, package)?;
+    let vendored = bindings::vendor_target_dependencies(resolution)?;
+    if vendored.is_empty() {
+        config.terminal().status(
+            "Skipping",
+            format!(
+                "package `{name}` has no target dependencies to vendor",
+                name = package.package.name
+            ),
+        )?;
+        return Ok(());
+    }
+
+    let manifest_dir = package
+        .package
+        .manifest_path
+        .parent()
+        .context("manifest path has no parent directory")?
+        .as_std_path();
+
+    let mut entries = Vec::new();
+    for (id, contents) in vendored {
+        let name = format!("{ns}-{name}", ns = id.namespace(), name = id.name());
+        let (path, package) = match contents {
+            bindings::VendoredContents::Wit { package, deps } => {
+                let path = out.join(&name);
+                let dir = manifest_dir.join(&path);
+                if dir.is_dir() {
+                    fs::remove_dir_all(&dir).with_context(|| {
+                        format!("failed to remove directory `{dir}`", dir = dir.display())
+                    })?;
+                }
+
+                write_vendored_package(&dir, &package)?;
+                for dep in &deps {
+                    let mut name =
+                        format!("{ns}-{name}", ns = dep.name.namespace, name = dep.name.name);
+                    if let Some(version) = &dep.name.version {
+                        write!(&mut name, "-{version}").unwrap();
+                    }
+
+                    write_vendored_package(&dir.join("deps").join(name), dep)?;
+                }
+
+                (path, None)
+            }
+            bindings::VendoredContents::Wasm { bytes, package } => {
+                let path = out.join(format!("{name}.wasm"));
+                let file = manifest_dir.join(&path);
+                if let Some(parent) = file.parent() {
+                    fs::create_dir_all(parent).with_context(|| {
+                        format!("failed to create directory `{dir}`", dir = parent.display())
+                    })?;
+                }
+
+                fs::write(&file, bytes).with_context(|| {
+                    format!("failed to write file `{path}`", path = file.display())
+                })?;
+
+                (path, package)
+            }
+        };
+
+        config.terminal().status(
+            "Vendored",
+            format!(
+                "target dependency `{id}` to `{path}`",
+                path = path.display()
+            ),
+        )?;
+
+        entries.push((id, path, package));
+    }
+
+    let manifest_path = &package.package.manifest_path;
+    let manifest = fs::read_to_string(manifest_path)
+        .with_context(|| format!("failed to read manifest file `{manifest_path}`"))?;
+    let mut document: toml_edit::Document = manifest
+        .parse()
+        .with_context(|| format!("failed to parse manifest file `{manifest_path}`"))?;
+
+    let dependencies = document["package"]["metadata"]["component"]["target"]["dependencies"]
+        .as_table_like_mut()
+        .with_context(|| {
+            format!("failed to find target dependencies in manifest file `{manifest_path}`")
+        })?;
+
+    for (id, path, package) in entries {
+        let mut entry = toml_edit::InlineTable::new();
+        entry.insert(
+            "path",
+            path.to_str()
+                .context("vendored path is not valid UTF-8")?
+                .replace('\\', "/")
+                .into(),
+        );
+        if let Some(package) = package {
+            entry.insert("package", package.to_string().into());
+        }
+
+        dependencies.insert(id.as_ref(), toml_edit::value(entry));
+    }
+
+    fs::write(manifest_path, document.to_string())
+        .with_context(|| format!("failed to write manifest file `{manifest_path}`"))
+}
+
+fn write_vendored_package(dir: &Path, package: &bindings::VendoredPackage) -> Result<()> {
+    fs::create_dir_all(dir)
+        .with_context(|| format!("failed to create directory `{dir}`", dir = dir.display()))?;
+
+    let path = dir.join("package.wit");
+    fs::write(&path, &package.source)
+        .with_context(|| format!("failed to write WIT file `{path}`", path = path.display()))
+}
+
+async fn create_resolution_map<'a>(
+    config: &Config,
+    packages: &'a [PackageComponentMetadata<'_>],
+    lock_file: Option<LockFileResolver<'_>>,
+    network_allowed: bool,
+) -> Result<PackageResolutionMap<'a>> {
+    let mut map = PackageResolutionMap::default();
+
+    for PackageComponentMetadata { package, metadata } in packages {
+        match metadata {
+            Some(metadata) => {
+                let resolution =
+                    PackageDependencyResolution::new(config, metadata, lock_file, network_allowed)
+                        .await?;
+                map.insert(package.id.clone(), resolution);
+            }
+            None => continue,
+        }
+    }
+
+    Ok(map)
+}
+
+async fn encode_target_world(
+    config: &Config,
+    resolution: &PackageDependencyResolution<'_>,
+    codegen_version: Option<&Version>,
+    bindings_dir: &Path,
+    explain: bool,
+    strict: bool,
+) -> Result<Vec<String>> {
+    let output_dir = bindings_dir.join(&resolution.metadata.name);
+    let target_path = output_dir.join("target.wasm");
+    let world_path = output_dir.join("world");
+    let world_id_path = output_dir.join("world-id");
+
+    // The version is only known when the dependency graph was resolved
+    if let Some(version) = codegen_version {
+        check_codegen_version(config, resolution, version, &output_dir)?;
+    }
+
+    let last_modified_output = target_path
+        .is_file()
+        .then(|| last_modified_time(&target_path))
+        .transpose()?
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+
+    // Deprecations are reported at the end of the build unless strict
+    let encoder = BindingsEncoder::new(resolution)?;
+    let deprecations = encoder.deprecations();
+    if strict {
+        if let Some(warning) = deprecations.first() {
+            bail!(
+                "{warning} (used by package `{name}`)",
+                name = resolution.metadata.name
+            );
+        }
+    }
+
+    if strict {
+        let unused = encoder.unused_dependencies()?;
+        if !unused.is_empty() {
+            bail!(
+                "the target of package `{name}` does not use the following target dependencies: {deps}",
+                name = resolution.metadata.name,
+                deps = unused
+                    .iter()
+                    .map(|id| format!("`{id}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+    }
+
+    // The runtime path may be overridden by the environment, so a change to
+    // it is not reflected by the modification time of the manifest
+    let runtime = runtime_path(resolution.metadata).unwrap_or_default();
+    let runtime_path_file = output_dir.join("runtime");
+    let last_world = fs::read_to_string(&world_id_path).ok();
+    let reason = match encoder.reason(last_modified_output, last_world.as_deref())? {
+        Some(reason) => Some(reason),
+        None => fs::read_to_string(&runtime_path_file)
+            .ok()
+            .filter(|last| *last != runtime)
+            .map(|last| format!("the runtime path changed from `{last}` to `{runtime}`")),
+    };
+
+    match reason {
+        Some(reason) => {
+            ::log::debug!(
+                "encoding target for package `{name}` at `{path}` because {reason}",
+                name = resolution.metadata.name,
+                path = target_path.display(),
+            );
+
+            if explain {
+                config.terminal().note(format!(
+                    "encoding target for package `{name}` because {reason}",
+                    name = resolution.metadata.name,
+                ))?;
+            }
+
+            config.terminal().status(
+                "Encoding",
+                format!(
+                    "target for {name} ({path})",
+                    name = resolution.metadata.name,
+                    path = target_path.display()
+                ),
+            )?;
+
+            let world = encoder.world_name().to_string();
+            let world_id = encoder.world_id();
+            let encoded = encoder.encode()?;
+            fs::create_dir_all(&output_dir).with_context(|| {
+                format!(
+                    "failed to create output directory `{path}`",
+                    path = output_dir.display()
+                )
+            })?;
+
+            fs::write(&target_path, encoded).with_context(|| {
+                format!(
+                    "failed to write target file `{path}`",
+                    path = target_path.display()
+                )
+            })?;
+
+            fs::write(&world_path, world).with_context(|| {
+                format!(
+                    "failed to write world name `{path}`",
+                    path = world_path.display()
+                )
+            })?;
+
+            fs::write(&world_id_path, world_id).with_context(|| {
+                format!(
+                    "failed to write world id `{path}`",
+                    path = world_id_path.display()
+                )
+            })?;
+
+            let worlds_path = output_dir.join("worlds");
+            let worlds: String = resolution
+                .metadata
+                .section
+                .target_worlds
+                .iter()
+                .map(|(target, world)| format!("{target}={world}\n"))
+                .collect();
+
+            fs::write(&worlds_path, worlds).with_context(|| {
+                format!(
+                    "failed to write target world names `{path}`",
+                    path = worlds_path.display()
+                )
+            })?;
+
+            fs::write(&runtime_path_file, &runtime).with_context(|| {
+                format!(
+                    "failed to write runtime path `{path}`",
+                    path = runtime_path_file.display()
+                )
+            })?;
+
+            let options_path = output_dir.join("options");
+            fs::write(
+                &options_path,
+                resolution.metadata.section.bindings.to_macro_options(),
+            )
+            .with_context(|| {
+                format!(
+                    "failed to write bindings options `{path}`",
+                    path = options_path.display()
+                )
+            })?;
+        }
+        None => {
+            ::log::debug!(
+                "existing target encoding for package `{name}` at `{path}` is up-to-date",
+                name = resolution.metadata.name,
+                path = target_path.display(),
+            );
+
+            if explain {
+                config.terminal().note(format!(
+                    "target for package `{name}` is up-to-date",
+                    name = resolution.metadata.name,
+                ))?;
+            }
+        }
+    }
+
+    Ok(deprecations)
+}
+
+/// Checks the version of `wit-bindgen` that generates the bindings of a
+/// package against the requirement of the package.
+///
+/// The version is the one resolved by cargo for the package; a package that
+/// does not depend on `wit-bindgen` is checked against the version used by
+/// the `cargo-component-bindings` crate of this release.
+fn check_codegen_version(
+    config: &Config,
+    resolution: &PackageDependencyResolution<'_>,
+    version: &Version,
+    output_dir: &Path,
+) -> Result<()> {
+    let name = &resolution.metadata.name;
+
+    if let Some(req) = &resolution.metadata.section.wit_bindgen {
+        if !req.matches(version) {
+            bail!(
+                "package `{name}` requires wit-bindgen version `{req}` but bindings are generated with wit-bindgen v{version}"
+            );
+        }
+    }
+
+    if config.terminal().verbosity() == Verbosity::Verbose {
+        config.terminal().note(format!(
+            "bindings for package `{name}` are generated with wit-bindgen v{version}"
+        ))?;
+    }
+
+    // Record the codegen version so that a change in version can be reported
+    let path = output_dir.join("codegen");
+    match fs::read_to_string(&path) {
+        Ok(prev) if prev.trim() == version.to_string() => return Ok(()),
+        Ok(prev) => {
+            config.terminal().warn(format!(
+                "the wit-bindgen version for package `{name}` changed from v{prev} to v{version}; generated bindings may change",
+                prev = prev.trim()
+            ))?;
+        }
+        Err(_) => {}
+    }
+
+    fs::create_dir_all(output_dir).with_context(|| {
+        format!(
+            "failed to create output directory `{path}`",
+            path = output_dir.display()
+        )
+    })?;
+
+    fs::write(&path, version.to_string()).with_context(|| {
+        format!(
+            "failed to write codegen version `{path}`",
+            path = path.display()
+        )
+    })
+}
+
+fn is_wasm_module(path: impl AsRef<Path>) -> Result<bool> {
+    let path = path.as_ref();
+
+    let mut file = File::open(path)
+        .with_context(|| format!("failed to open `{path}` for read", path = path.display()))?;
+
+    let mut bytes = [0u8; 8];
+    file.read(&mut bytes).with_context(|| {
+        format!(
+            "failed to read file header for `{path}`",
+            path = path.display()
+        )
+    })?;
+
+    if bytes[0..4] != [0x0, b'a', b's', b'm'] {
+        bail!(
+            "expected `{path}` to be a WebAssembly module",
+            path = path.display()
+        );
+    }
+
+    // Check for the module header version
+    Ok(bytes[4..] == [0x01, 0x00, 0x00, 0x00])
+}
+
+fn adapter_bytes<'a>(
+    metadata: &'a ComponentMetadata,
+    target: Option<&str>,
+    profile: Option<&str>,
+    binary: bool,
+) -> Result<Option<Cow<'a, [u8]>>> {
+    match metadata.section.adapter.select(target, profile) {
+        Adapter::Builtin | Adapter::Targets(_) | Adapter::Profiles(_) => {}
+        Adapter::None => return Ok(None),
+        Adapter::List { paths, .. } => match paths.as_slice() {
+            [] => {}
+            [adapter] => {
+                return Ok(Some(
+                    fs::read(adapter)
+                        .with_context(|| {
+                            format!(
+                                "failed to read module adapter `{path}`",
+                                path = adapter.display()
+                            )
+                        })?
+                        .into(),
+                ));
+            }
+            adapters => {
+                // Use the first adapter that can be read
+                let mut errors = String::new();
+                for adapter in adapters {
+                    match fs::read(adapter) {
+                        Ok(bytes) => return Ok(Some(bytes.into())),
+                        Err(e) => {
+                            log::debug!(
+                                "failed to read module adapter `{path}`: {e}",
+                                path = adapter.display()
+                            );
+                            write!(errors, "\n  `{path}`: {e}", path = adapter.display()).unwrap();
+                        }
+                    }
+                }
+
+                bail!("failed to read a module adapter from any of the configured paths:{errors}");
+            }
+        },
+    }
+
+    // The WASI targets all target WASI preview1, which the bundled adapter
+    // adapts to the component model
+    if binary {
+        Ok(Some(Cow::Borrowed(include_bytes!(concat!(
             "../adapters/",
             env!("WASI_ADAPTER_VERSION"),
             "/wasi_snapshot_preview1.command.wasm"
-        ))))
+        )))))
+    } else {
+        Ok(Some(Cow::Borrowed(include_bytes!(concat!(
+            "../adapters/",
+            env!("WASI_ADAPTER_VERSION"),
+            "/wasi_snapshot_preview1.reactor.wasm"
+        )))))
+    }
+}
+
+/// Creates a component from a WebAssembly module produced by a linker.
+///
+/// This is used by the `cargo-component-ld` linker wrapper so that a plain
+/// `cargo build` can produce a component.
+///
+/// The target triple and cargo profile select the adapters configured by
+/// target or profile; without them, the fallback adapter is used.
+///
+/// If the package is not a component package, this does nothing.
+pub fn componentize_linked_module(
+    config: &Config,
+    manifest_path: &Path,
+    package_name: &str,
+    path: &Path,
+    target: Option<&str>,
+    profile: Option<&str>,
+    binary: bool,
+) -> Result<()> {
+    let metadata = load_metadata(Some(manifest_path))?;
+    let package = metadata
+        .packages
+        .iter()
+        .find(|p| p.name == package_name)
+        .with_context(|| {
+            format!(
+                "package `{package_name}` was not found in manifest `{path}`",
+                path = manifest_path.display()
+            )
+        })?;
+
+    match ComponentMetadata::from_package(package)? {
+        Some(metadata) => create_component(
+            config,
+            &metadata,
+            path,
+            target,
+            profile,
+            binary,
+            &CargoArguments::default(),
+        ),
+        None => {
+            log::debug!("package `{package_name}` is not a component package");
+            Ok(())
+        }
+    }
+}
+
+fn create_component(
+    config: &Config,
+    metadata: &ComponentMetadata,
+    path: &Path,
+    target: Option<&str>,
+    profile: Option<&str>,
+    binary: bool,
+    cargo_args: &CargoArguments,
+) -> Result<()> {
+    // If the compilation output is not a WebAssembly module, then do nothing
+    // Note: due to the way cargo currently works on macOS, it will overwrite
+    // a previously generated component on an up-to-date build.
+    //
+    // As a result, users on macOS will see a "creating component" message
+    // even if the build is up-to-date.
+    //
+    // See: https://github.com/rust-lang/cargo/blob/99ad42deb4b0be0cdb062d333d5e63460a94c33c/crates/cargo-util/src/paths.rs#L542-L550
+    //
+    // The component is still recreated from the cached module if the adapter
+    // changed since the component was created.
+    let adapter = adapter_bytes(metadata, target, profile, binary)?;
+    let modules = module_adapters(metadata, target, profile)?;
+    let adapters: Vec<_> = adapter
+        .as_deref()
+        .map(|adapter| (WASI_ADAPTER_NAME, adapter))
+        .into_iter()
+        .chain(
+            modules
+                .iter()
+                .map(|(name, adapter)| (*name, adapter.as_slice())),
+        )
+        .collect();
+
+    let cache_dir = component_cache_dir(path, cargo_args)?;
+    let module = if is_wasm_module(path)? {
+        fs::read(path).with_context(|| {
+            format!(
+                "failed to read output module `{path}`",
+                path = path.display()
+            )
+        })?
+    } else {
+        match cached_module_for_adapter(&cache_dir, path, &adapters) {
+            Some(module) => {
+                ::log::debug!(
+                    "adapter of component `{path}` changed; recreating the component from the cached module",
+                    path = path.display()
+                );
+                module
+            }
+            None => {
+                ::log::debug!(
+                    "output file `{path}` is already a WebAssembly component",
+                    path = path.display()
+                );
+                return Ok(());
+            }
+        }
+    };
+
+    ::log::debug!(
+        "componentizing WebAssembly module `{path}` as a {kind} component",
+        path = path.display(),
+        kind = if binary { "command" } else { "reactor" },
+    );
+
+    config.terminal().status(
+        "Creating",
+        format!("component {path}", path = path.display()),
+    )?;
+
+    // The inputs are kept before encoding so that they are available when
+    // the encoding fails
+    if cargo_args.keep_temps {
+        keep_temp(config, path, "core.wasm", Some(&module))?;
+        keep_temp(config, path, "adapter.wasm", adapter.as_deref())?;
+        for (name, adapter) in &modules {
+            keep_temp(config, path, &format!("adapter.{name}.wasm"), Some(adapter))?;
+        }
+    }
+
+    let component = encode_component(&cache_dir, path, &module, &adapters)?;
+
+    if cargo_args.keep_temps {
+        keep_temp(config, path, "component.wasm", Some(&component))?;
+    }
+
+    let fields = &metadata.section.producers;
+    let mut producers = wasm_metadata::Producers::empty();
+    if fields.processed_by {
+        producers.add(
+            "processed-by",
+            env!("CARGO_PKG_NAME"),
+            option_env!("CARGO_VERSION_INFO").unwrap_or(env!("CARGO_PKG_VERSION")),
+        );
+    }
+
+    if fields.language {
+        producers.add("language", "Rust", "");
+    }
+
+    let build_number = build_number(metadata)?;
+    if let Some(build_number) = &build_number {
+        producers.add("build-number", &metadata.name, &build_number.number);
+    }
+
+    // The crate may be named differently than the component package
+    if fields.source {
+        producers.add("source", &metadata.name, &metadata.version.to_string());
+    }
+
+    check_forbidden_imports(metadata, &component)?;
+
+    if cargo_args.export_symbols {
+        print_export_symbols(config, &module, &component)?;
+    }
+
+    if let Some(path) = &cargo_args.dump_component_type {
+        dump_component_type(config, &module, &component, path)?;
+    }
+
+    let component = if producers.is_empty() {
+        component
     } else {
-        Ok(Cow::Borrowed(include_bytes!(concat!(
-            "../adapters/",
-            env!("WASI_ADAPTER_VERSION"),
-            "/wasi_snapshot_preview1.reactor.wasm"
-        ))))
+        producers.add_to_wasm(&component).with_context(|| {
+            format!(
+                "failed to add metadata to output component `{path}`",
+                path = path.display()
+            )
+        })?
+    };
+
+    // Name the component after the crate unless a name is configured
+    let name = metadata
+        .section
+        .module_name
+        .as_deref()
+        .unwrap_or(&metadata.name);
+    let component = AddMetadata {
+        name: Some(name.to_string()),
+        ..Default::default()
+    }
+    .to_wasm(&component)
+    .with_context(|| {
+        format!(
+            "failed to add name to output component `{path}`",
+            path = path.display()
+        )
+    })?;
+
+    let version = component_version(config, metadata, cargo_args.strict)?;
+    let mut component = add_custom_section(&component, "version", version.to_string().as_bytes());
+
+    if !metadata.section.metadata.is_empty() {
+        component = add_custom_section(
+            &component,
+            "metadata",
+            &serde_json::to_vec(&metadata.section.metadata)?,
+        );
+    }
+
+    let strip = metadata.section.strip.select(profile);
+    if strip != Strip::None || !metadata.section.drop_sections.is_empty() {
+        let keep = |name: &str| metadata.keeps_section(strip, name);
+        component = filter_custom_sections(&component, &keep).with_context(|| {
+            format!(
+                "failed to strip custom sections from output component `{path}`",
+                path = path.display()
+            )
+        })?;
+    }
+
+    match cargo_args.temp_dir() {
+        Some(dir) => write_staged_component(&dir, path, &module, &component)?,
+        None => fs::write(path, component).with_context(|| {
+            format!(
+                "failed to write output component `{path}`",
+                path = path.display()
+            )
+        })?,
+    }
+
+    // The number is only consumed once the component is written
+    match build_number {
+        Some(build_number) => build_number.record(),
+        None => Ok(()),
     }
 }
 
-fn create_component(
-    config: &Config,
-    metadata: &ComponentMetadata,
+/// Keeps an intermediate artifact of creating the component at the given
+/// output path in the component debug directory next to it.
+///
+/// The artifact is named after the output with the given suffix (e.g.
+/// `foo.core.wasm`) so that the next build replaces it. An artifact without
+/// contents, such as the adapter of a component created without one, is
+/// removed if kept by a previous build.
+fn keep_temp(config: &Config, path: &Path, suffix: &str, contents: Option<&[u8]>) -> Result<()> {
+    let dir = path
+        .parent()
+        .context("output path has no parent directory")?
+        .join(COMPONENT_DEBUG_DIR);
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let temp = dir.join(format!("{stem}.{suffix}"));
+
+    let contents = match contents {
+        Some(contents) => contents,
+        None => {
+            if temp.is_file() {
+                fs::remove_file(&temp).with_context(|| {
+                    format!("failed to remove file `{path}`", path = temp.display())
+                })?;
+            }
+            return Ok(());
+        }
+    };
+
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("failed to create directory `{dir}`", dir = dir.display()))?;
+    fs::write(&temp, contents)
+        .with_context(|| format!("failed to write file `{path}`", path = temp.display()))?;
+
+    config
+        .terminal()
+        .status("Keeping", format!("{path}", path = temp.display()))
+}
+
+/// Reads the adapters of modules other than WASI preview1 of a component,
+/// in order, paired with the name of the module each one adapts.
+fn module_adapters<'a>(
+    metadata: &'a ComponentMetadata,
+    target: Option<&str>,
+    profile: Option<&str>,
+) -> Result<Vec<(&'a str, Vec<u8>)>> {
+    metadata
+        .section
+        .adapter
+        .modules(target, profile)
+        .iter()
+        .map(|adapter| {
+            let bytes = fs::read(&adapter.path).with_context(|| {
+                format!(
+                    "failed to read the adapter of module `{module}` from `{path}`",
+                    module = adapter.module,
+                    path = adapter.path.display()
+                )
+            })?;
+
+            Ok((adapter.module.as_str(), bytes))
+        })
+        .collect()
+}
+
+/// Gets a hash of the adapters applied to a component, in order.
+fn adapter_hash(adapters: &[(&str, &[u8])]) -> String {
+    match adapters {
+        [] => "none".to_string(),
+        // A lone WASI adapter hashes as it did before additional adapters
+        [(WASI_ADAPTER_NAME, adapter)] => HashAlgorithm::Sha256.digest(adapter).to_string(),
+        adapters => {
+            let mut hasher = Sha256::new();
+            for (name, adapter) in adapters {
+                hasher.update((name.len() as u64).to_le_bytes());
+                hasher.update(name.as_bytes());
+                hasher.update((adapter.len() as u64).to_le_bytes());
+                hasher.update(adapter);
+            }
+            AnyHash::from(Hash::<Sha256>::from(hasher.finalize())).to_string()
+        }
+    }
+}
+
+/// Gets the directory in which the components created at the given output
+/// path are cached.
+///
+/// The cache is kept in the temporary directory when one is set, in a
+/// subdirectory keyed by a hash of the output directory so that the outputs
+/// of different targets, profiles, and workspaces do not share it. Otherwise,
+/// it is kept next to the output.
+fn component_cache_dir(path: &Path, cargo_args: &CargoArguments) -> Result<PathBuf> {
+    let parent = path
+        .parent()
+        .context("output path has no parent directory")?;
+    Ok(match cargo_args.temp_dir() {
+        Some(dir) => {
+            let hash = HashAlgorithm::Sha256
+                .digest(parent.to_string_lossy().as_bytes())
+                .to_string();
+            let hash = hash.split_once(':').map_or(hash.as_str(), |(_, h)| h);
+            dir.join(COMPONENT_CACHE_DIR).join(&hash[..16])
+        }
+        None => parent.join(COMPONENT_CACHE_DIR),
+    })
+}
+
+/// Gets the cached core module of the component at the given output path if
+/// the component was created with a different adapter.
+///
+/// Returns `None` if the adapter is unchanged or nothing is cached.
+fn cached_module_for_adapter(
+    dir: &Path,
     path: &Path,
-    binary: bool,
-) -> Result<()> {
-    // If the compilation output is not a WebAssembly module, then do nothing
-    // Note: due to the way cargo currently works on macOS, it will overwrite
-    // a previously generated component on an up-to-date build.
-    //
-    // As a result, users on macOS will see a "creating component" message
-    // even if the build is up-to-date.
-    //
-    // See: https://github.com/rust-lang/cargo/blob/99ad42deb4b0be0cdb062d333d5e63460a94c33c/crates/cargo-util/src/paths.rs#L542-L550
-    if !is_wasm_module(path)? {
-        ::log::debug!(
-            "output file `{path}` is already a WebAssembly component",
+    adapters: &[(&str, &[u8])],
+) -> Option<Vec<u8>> {
+    let cached = dir.join(path.file_name()?);
+    let previous = fs::read_to_string(cached.with_extension("adapter")).ok()?;
+    if previous == adapter_hash(adapters) {
+        return None;
+    }
+
+    fs::read(staged_module_path(dir, path)).ok()
+}
+
+/// Encodes a component from a core module and the adapters to apply to it,
+/// in order, each paired with the name of the module it adapts.
+///
+/// The encoded component is cached in the given directory, keyed by a hash of
+/// the module and adapters, so that it is reused when the module is rebuilt
+/// without changes. The module and a hash of the adapters are cached with it
+/// so that the component can be recreated when only the adapters change.
+fn encode_component(
+    dir: &Path,
+    path: &Path,
+    module: &[u8],
+    adapters: &[(&str, &[u8])],
+) -> Result<Vec<u8>> {
+    let adapter_hash = adapter_hash(adapters);
+    let mut hasher = Sha256::new();
+    hasher.update((module.len() as u64).to_le_bytes());
+    hasher.update(module);
+    hasher.update(adapter_hash.as_bytes());
+    let hash = AnyHash::from(Hash::<Sha256>::from(hasher.finalize())).to_string();
+
+    let cached = dir.join(path.file_name().context("output path has no file name")?);
+    let hash_path = cached.with_extension("hash");
+    if fs::read_to_string(&hash_path).ok().as_deref() == Some(hash.as_str()) {
+        if let Ok(component) = fs::read(&cached) {
+            log::debug!(
+                "reusing cached component `{cached}` for module `{path}`",
+                cached = cached.display(),
+                path = path.display()
+            );
+            return Ok(component);
+        }
+    }
+
+    let mut encoder = ComponentEncoder::default().module(module)?.validate(true);
+    for (name, adapter) in adapters {
+        encoder = encoder
+            .adapter(name, adapter)
+            .with_context(|| format!("failed to apply module adapter `{name}`"))?;
+    }
+
+    let component = encoder.encode()?;
+
+    // The hash is removed first so a partially written cache is never used;
+    // failing to cache the component does not fail the build
+    let _ = fs::remove_file(&hash_path);
+    if let Err(e) = fs::create_dir_all(dir)
+        .and_then(|_| fs::write(&cached, &component))
+        .and_then(|_| fs::write(staged_module_path(dir, path), module))
+        .and_then(|_| fs::write(cached.with_extension("adapter"), &adapter_hash))
+        .and_then(|_| fs::write(&hash_path, &hash))
+    {
+        log::debug!(
+            "failed to cache component `{cached}`: {e}",
+            cached = cached.display()
+        );
+    }
+
+    Ok(component)
+}
+
+/// Gets the path of the core module kept in a temporary directory for the
+/// given output path.
+fn staged_module_path(dir: &Path, path: &Path) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    dir.join(format!("{stem}.core.wasm"))
+}
+
+/// Writes an output component by way of a temporary directory.
+///
+/// The core module the component was created from is kept in the directory
+/// as `<name>.core.wasm` and the component is written to the directory before
+/// being copied to the output path.
+fn write_staged_component(dir: &Path, path: &Path, module: &[u8], component: &[u8]) -> Result<()> {
+    fs::create_dir_all(dir).with_context(|| {
+        format!(
+            "failed to create temporary directory `{dir}`",
+            dir = dir.display()
+        )
+    })?;
+
+    let name = path.file_name().context("output path has no file name")?;
+    let core = staged_module_path(dir, path);
+    fs::write(&core, module).with_context(|| {
+        format!(
+            "failed to write core module `{path}`",
+            path = core.display()
+        )
+    })?;
+
+    let staged = dir.join(name);
+    fs::write(&staged, component).with_context(|| {
+        format!(
+            "failed to write component `{path}`",
+            path = staged.display()
+        )
+    })?;
+
+    // The directory may be on a different file system, so copy rather than rename
+    fs::copy(&staged, path).with_context(|| {
+        format!(
+            "failed to copy component `{staged}` to `{path}`",
+            staged = staged.display(),
+            path = path.display()
+        )
+    })?;
+
+    Ok(())
+}
+
+fn write_type_component(config: &Config, path: &Path, encoded: &[u8]) -> Result<()> {
+    config.terminal().status(
+        "Creating",
+        format!("type component {path}", path = path.display()),
+    )?;
+
+    fs::create_dir_all(path.parent().unwrap()).with_context(|| {
+        format!(
+            "failed to create output directory for `{path}`",
+            path = path.display()
+        )
+    })?;
+
+    fs::write(path, encoded).with_context(|| {
+        format!(
+            "failed to write type component `{path}`",
             path = path.display()
+        )
+    })
+}
+
+/// Represents the build number embedded in a component.
+struct BuildNumber<'a> {
+    /// The build number.
+    number: String,
+    /// The build number file to record the number in once the component is
+    /// written.
+    file: Option<&'a Path>,
+}
+
+impl BuildNumber<'_> {
+    /// Records the build number in the build number file, if any.
+    fn record(&self) -> Result<()> {
+        let path = match self.file {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+
+        fs::write(path, format!("{number}\n", number = self.number)).with_context(|| {
+            format!(
+                "failed to write build number file `{path}`",
+                path = path.display()
+            )
+        })
+    }
+}
+
+/// Gets the Rust path of the `wit-bindgen` runtime module used by the
+/// generated bindings of a component, if it is redirected.
+///
+/// The `CARGO_COMPONENT_RUNTIME_PATH` environment variable takes precedence
+/// over the `package.metadata.component.runtime-path` setting.
+fn runtime_path(metadata: &ComponentMetadata) -> Option<String> {
+    std::env::var(RUNTIME_PATH_ENV_VAR)
+        .ok()
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty())
+        .or_else(|| metadata.section.runtime_path.clone())
+}
+
+/// Gets the build number to embed in the component, if any.
+///
+/// The `CARGO_COMPONENT_BUILD_NUMBER` environment variable takes precedence
+/// over the `package.metadata.component.build-number-file` setting, in which
+/// case the build number is one more than the number in the file.
+fn build_number(metadata: &ComponentMetadata) -> Result<Option<BuildNumber<'_>>> {
+    if let Ok(number) = std::env::var(BUILD_NUMBER_ENV_VAR) {
+        let number = number.trim();
+        if !number.is_empty() {
+            return Ok(Some(BuildNumber {
+                number: number.to_string(),
+                file: None,
+            }));
+        }
+    }
+
+    let path = match &metadata.section.build_number_file {
+        Some(path) => path,
+        None => return Ok(None),
+    };
+
+    let number = if path.is_file() {
+        let contents = fs::read_to_string(path).with_context(|| {
+            format!(
+                "failed to read build number file `{path}`",
+                path = path.display()
+            )
+        })?;
+
+        contents.trim().parse::<u64>().with_context(|| {
+            format!(
+                "invalid build number in file `{path}`",
+                path = path.display()
+            )
+        })? + 1
+    } else {
+        1
+    };
+
+    Ok(Some(BuildNumber {
+        number: number.to_string(),
+        file: Some(path),
+    }))
+}
+
+/// Gets the version to embed in the component.
+///
+/// The `package.metadata.component.version` setting takes precedence over
+/// the version of the cargo package.
+///
+/// An incompatible version is an error if `strict` is set.
+fn component_version<'a>(
+    config: &Config,
+    metadata: &'a ComponentMetadata,
+    strict: bool,
+) -> Result<&'a Version> {
+    let version = match &metadata.section.version {
+        Some(version) => version,
+        None => return Ok(&metadata.version),
+    };
+
+    // Warn when the versions are not semver compatible
+    let compatible = version.major == metadata.version.major
+        && (version.major != 0 || version.minor == metadata.version.minor);
+    if !compatible {
+        let message = format!(
+            "component version {version} of package `{name}` is not compatible with package version {package_version}",
+            name = metadata.name,
+            package_version = metadata.version
         );
+
+        if strict {
+            bail!(message);
+        }
+
+        config.terminal().warn(message)?;
+    }
+
+    Ok(version)
+}
+
+/// Appends a custom section to the given component.
+fn add_custom_section(component: &[u8], name: &str, data: &[u8]) -> Vec<u8> {
+    let mut bytes = component.to_vec();
+    CustomSection {
+        name: name.into(),
+        data: data.into(),
+    }
+    .append_to_component(&mut bytes);
+    bytes
+}
+
+/// Removes the custom sections for which `keep` returns `false` from a
+/// WebAssembly binary, including those of its nested modules and components.
+fn filter_custom_sections(bytes: &[u8], keep: &dyn Fn(&str) -> bool) -> Result<Vec<u8>> {
+    let mut output = Vec::new();
+    let mut parser = wasmparser::Parser::new(0);
+    let mut offset = 0;
+    loop {
+        let payload = match parser.parse(&bytes[offset..], true)? {
+            wasmparser::Chunk::Parsed { consumed, payload } => {
+                offset += consumed;
+                payload
+            }
+            // The whole binary is available, so more data is never needed
+            wasmparser::Chunk::NeedMoreData(_) => unreachable!(),
+        };
+
+        let section = payload.as_section();
+        match payload {
+            wasmparser::Payload::Version { range, .. } => output.extend_from_slice(&bytes[range]),
+            wasmparser::Payload::End(_) => break,
+            wasmparser::Payload::CustomSection(reader) if !keep(reader.name()) => {}
+            wasmparser::Payload::ModuleSection { range, .. }
+            | wasmparser::Payload::ComponentSection { range, .. } => {
+                // The nested binary is skipped by the parser and filtered separately
+                offset = range.end;
+                let (id, _) = section.unwrap();
+                let data = filter_custom_sections(&bytes[range], keep)?;
+                RawSection { id, data: &data }.append_to(&mut output);
+            }
+            payload => {
+                if let wasmparser::Payload::CodeSectionStart { range, .. } = &payload {
+                    // The function bodies are copied with the section
+                    parser.skip_section();
+                    offset = range.end;
+                }
+
+                if let Some((id, range)) = section {
+                    RawSection {
+                        id,
+                        data: &bytes[range],
+                    }
+                    .append_to(&mut output);
+                }
+            }
+        }
+    }
+
+    Ok(output)
+}
+
+fn check_forbidden_imports(metadata: &ComponentMetadata, component: &[u8]) -> Result<()> {
+    let forbidden = &metadata.section.forbidden_imports;
+    if forbidden.is_empty() {
         return Ok(());
     }
 
-    ::log::debug!(
-        "componentizing WebAssembly module `{path}` as a {kind} component",
-        path = path.display(),
-        kind = if binary { "command" } else { "reactor" },
-    );
+    let (resolve, world) = match wit_component::decode(component)
+        .context("failed to decode the imports of the output component")?
+    {
+        DecodedWasm::Component(resolve, world) => (resolve, world),
+        DecodedWasm::WitPackage(..) => unreachable!("expected a component"),
+    };
+
+    for key in resolve.worlds[world].imports.keys() {
+        let name = match key {
+            WorldKey::Name(name) => name.clone(),
+            WorldKey::Interface(id) => match resolve.id_of(*id) {
+                Some(name) => name,
+                None => continue,
+            },
+        };
+
+        // Compare without the version so `wasi:random/insecure` also
+        // matches `wasi:random/insecure@0.2.0`
+        let unversioned = name.split('@').next().unwrap();
+        if let Some(entry) = forbidden.iter().find(|entry| {
+            let entry = entry.as_str();
+            name == entry || unversioned == entry || unversioned.starts_with(&format!("{entry}/"))
+        }) {
+            bail!(
+                "component `{component}` imports `{name}`, which is forbidden by `{entry}` in \
+                 `package.metadata.component.forbidden-imports` of manifest `{path}`",
+                component = metadata.name,
+                path = metadata.manifest_path.display()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the type of a component, as computed by the component encoder, to
+/// the given path as WIT.
+///
+/// The world bound by the core module, before the encoder merges the worlds
+/// of its adapters into it, is written next to it with a `before-merge.wit`
+/// extension so that the two may be compared.
+fn dump_component_type(
+    config: &Config,
+    module: &[u8],
+    component: &[u8],
+    path: &Path,
+) -> Result<()> {
+    let (_, bindgen) = wit_component::metadata::decode(module)
+        .context("failed to decode the component type of the output module")?;
+    let before = path.with_extension("before-merge.wit");
+    write_world_package(&bindgen.resolve, bindgen.world, &before)?;
+
+    let (resolve, world) = decode_component(component)?;
+    write_world_package(&resolve, world, path)?;
+
+    config.terminal().status(
+        "Dumped",
+        format!(
+            "component type to `{path}` (before merging adapters: `{before}`)",
+            path = path.display(),
+            before = before.display()
+        ),
+    )
+}
+
+fn write_world_package(resolve: &Resolve, world: WorldId, path: &Path) -> Result<()> {
+    let package = resolve.worlds[world]
+        .package
+        .context("the world of the output component has no package")?;
+
+    let wit = WitPrinter::default()
+        .print(resolve, package)
+        .context("failed to print the type of the output component")?;
+
+    fs::write(path, wit).with_context(|| {
+        format!(
+            "failed to write component type `{path}`",
+            path = path.display()
+        )
+    })
+}
+
+fn decode_component(component: &[u8]) -> Result<(Resolve, WorldId)> {
+    match wit_component::decode(component).context("failed to decode the output component")? {
+        DecodedWasm::Component(resolve, world) => Ok((resolve, world)),
+        DecodedWasm::WitPackage(..) => unreachable!("expected a component"),
+    }
+}
+
+/// Prints the core module export symbol of each function exported by a
+/// component.
+fn print_export_symbols(config: &Config, module: &[u8], component: &[u8]) -> Result<()> {
+    let mut symbols = HashSet::new();
+    for payload in wasmparser::Parser::new(0).parse_all(module) {
+        if let wasmparser::Payload::ExportSection(reader) =
+            payload.context("failed to parse the output module")?
+        {
+            for export in reader {
+                symbols.insert(export.context("failed to parse a module export")?.name);
+            }
+        }
+    }
+
+    let (resolve, world) = decode_component(component)?;
+    for (key, item) in &resolve.worlds[world].exports {
+        let name = bindings::world_key_name(&resolve, key);
+        let functions: Vec<_> = match item {
+            WorldItem::Function(_) => vec![(format!("function `{name}`"), name.clone())],
+            WorldItem::Interface(id) => resolve.interfaces[*id]
+                .functions
+                .keys()
+                .map(|func| {
+                    (
+                        format!("function `{func}` of `{name}`"),
+                        format!("{name}#{func}"),
+                    )
+                })
+                .collect(),
+            WorldItem::Type(_) => continue,
+        };
+
+        for (export, symbol) in functions {
+            if !symbols.contains(symbol.as_str()) {
+                config.terminal().warn(format!(
+                    "{export} has no export `{symbol}` in the core module"
+                ))?;
+                continue;
+            }
+
+            let post_return = format!("cabi_post_{symbol}");
+            if symbols.contains(post_return.as_str()) {
+                config.terminal().status(
+                    "Symbol",
+                    format!("{export} is `{symbol}` (post-return `{post_return}`)"),
+                )?;
+            } else {
+                config
+                    .terminal()
+                    .status("Symbol", format!("{export} is `{symbol}`"))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Represents options for a build operation.
+#[derive(Default)]
+pub struct BuildOptions<'a> {
+    /// The path to the manifest of the package or workspace to build.
+    ///
+    /// Defaults to the manifest found from the current directory.
+    pub manifest_path: Option<&'a Path>,
+    /// The packages to build.
+    ///
+    /// Defaults to the packages cargo selects for the manifest.
+    pub packages: &'a [CargoPackageSpec],
+    /// Whether to build all packages in the workspace.
+    pub workspace: bool,
+    /// The cargo profile to build with.
+    ///
+    /// Defaults to the `dev` profile.
+    pub profile: Option<&'a str>,
+    /// The target triple to build for.
+    ///
+    /// Defaults to `wasm32-wasi`.
+    pub target: Option<&'a str>,
+}
+
+/// Represents a component built by a build operation.
+#[derive(Debug, Clone)]
+pub struct BuiltComponent {
+    /// The name of the cargo package the component was built from.
+    pub package: String,
+    /// The path to the built component.
+    pub path: PathBuf,
+    /// The name of the world targeted by the component, if known.
+    pub world: Option<String>,
+}
+
+/// Represents the output of a build operation.
+#[derive(Debug, Clone, Default)]
+pub struct BuildOutput {
+    /// The components that were built.
+    pub components: Vec<BuiltComponent>,
+}
+
+/// Builds the components of the packages selected by the given options.
+///
+/// This performs the same build as `cargo component build`, but a failing
+/// cargo build is returned as a [`CargoCommandError`] rather than exiting the
+/// process.
+pub async fn build(config: &Config, options: &BuildOptions<'_>) -> Result<BuildOutput> {
+    let mut args = vec!["build".to_string()];
+
+    if let Some(path) = options.manifest_path {
+        args.push("--manifest-path".to_string());
+        args.push(path.to_string_lossy().into_owned());
+    }
+
+    for spec in options.packages {
+        args.push("--package".to_string());
+        args.push(spec.to_string());
+    }
 
-    let module = fs::read(path).with_context(|| {
-        format!(
-            "failed to read output module `{path}`",
-            path = path.display()
-        )
-    })?;
+    if options.workspace {
+        args.push("--workspace".to_string());
+    }
 
-    config.terminal().status(
-        "Creating",
-        format!("component {path}", path = path.display()),
-    )?;
+    if let Some(profile) = options.profile {
+        args.push("--profile".to_string());
+        args.push(profile.to_string());
+    }
 
-    let encoder = ComponentEncoder::default()
-        .module(&module)?
-        .adapter("wasi_snapshot_preview1", &adapter_bytes(metadata, binary)?)?
-        .validate(true);
+    if let Some(target) = options.target {
+        args.push("--target".to_string());
+        args.push(target.to_string());
+    }
 
-    let mut producers = wasm_metadata::Producers::empty();
-    producers.add(
-        "processed-by",
-        env!("CARGO_PKG_NAME"),
-        option_env!("CARGO_VERSION_INFO").unwrap_or(env!("CARGO_PKG_VERSION")),
-    );
+    let cargo_args = CargoArguments::parse_from(args.iter())?;
+    let metadata = load_metadata(options.manifest_path)?;
+    let packages =
+        load_component_metadata(&metadata, cargo_args.packages.iter(), cargo_args.workspace)?;
+
+    let outputs = spawn_cargo_command(
+        config,
+        &metadata,
+        &packages,
+        Some("build"),
+        &cargo_args,
+        &args,
+        &[],
+    )
+    .await?;
 
-    let component = producers.add_to_wasm(&encoder.encode()?).with_context(|| {
-        format!(
-            "failed to add metadata to output component `{path}`",
-            path = path.display()
-        )
-    })?;
+    let bindings_dir = metadata.target_directory.join("bindings");
+    let components = outputs
+        .into_iter()
+        .filter_map(|(id, path)| {
+            let (package, metadata) = packages.iter().find_map(|p| {
+                let metadata = p.metadata.as_ref()?;
+                (p.package.id == id).then_some((p.package, metadata))
+            })?;
 
-    fs::write(path, component).with_context(|| {
-        format!(
-            "failed to write output component `{path}`",
-            path = path.display()
-        )
-    })
+            Some(BuiltComponent {
+                package: package.name.clone(),
+                world: component_world(package, metadata, bindings_dir.as_std_path()),
+                path,
+            })
+        })
+        .collect();
+
+    Ok(BuildOutput { components })
 }
 
 /// Represents options for a publish operation.
@@ -597,17 +3196,332 @@ fn add_registry_metadata(package: &Package, bytes: &[u8], path: &Path) -> Result
     })
 }
 
-/// Publish a component for the given workspace and publish options.
-pub async fn publish(config: &Config, options: &PublishOptions<'_>) -> Result<()> {
-    if options.dry_run {
-        config
-            .terminal()
-            .warn("not publishing component to the registry due to the --dry-run option")?;
-        return Ok(());
+fn print_publish_summary(
+    config: &Config,
+    options: &PublishOptions<'_>,
+    bytes: &[u8],
+) -> Result<()> {
+    let terminal = config.terminal();
+    terminal.status(
+        "Would publish",
+        format!(
+            "package `{id}` v{version} to registry `{url}`",
+            id = options.id,
+            version = options.version,
+            url = options.registry_url
+        ),
+    )?;
+
+    terminal.status(
+        "Component",
+        format!(
+            "{path} ({len} bytes)",
+            path = options.path.display(),
+            len = bytes.len()
+        ),
+    )?;
+
+    if options.init {
+        terminal.status("Init", "the package will be initialized in the registry")?;
     }
 
-    let client = create_client(config.warg(), options.registry_url, config.terminal())?;
+    let package = options.package;
+    if !package.authors.is_empty() {
+        terminal.status("Authors", package.authors.join(", "))?;
+    }
+
+    if let Some(description) = &package.description {
+        terminal.status("Description", description)?;
+    }
+
+    if let Some(license) = &package.license {
+        terminal.status("License", license)?;
+    }
+
+    for (kind, link) in [
+        ("Documentation", &package.documentation),
+        ("Homepage", &package.homepage),
+        ("Repository", &package.repository),
+    ] {
+        if let Some(link) = link {
+            terminal.status(kind, link)?;
+        }
+    }
+
+    let producers = wasm_metadata::Producers::from_wasm(bytes)?;
+    if let Some(producers) = &producers {
+        for (field, values) in producers.iter() {
+            for (name, version) in values.iter() {
+                terminal.status("Producer", format!("{field}: {name} {version}"))?;
+            }
+        }
+    }
+
+    let processed = producers
+        .as_ref()
+        .and_then(|p| p.get("processed-by"))
+        .map(|values| {
+            values
+                .iter()
+                .any(|(name, _)| name == env!("CARGO_PKG_NAME"))
+        })
+        .unwrap_or(false);
+    if !processed {
+        terminal.warn(format!(
+            "component has no `processed-by` producer for `{name}`",
+            name = env!("CARGO_PKG_NAME")
+        ))?;
+    }
+
+    Ok(())
+}
+
+/// Builds the component packages selected by the given cargo arguments and
+/// tests the built components.
+///
+/// Each component is validated and then, if its package configures a
+/// `test-runner`, the runner is run with the path of the component as its
+/// last argument. A runner exiting with a failure fails the tests.
+pub async fn test_components(
+    config: &Config,
+    metadata: &Metadata,
+    packages: &[PackageComponentMetadata<'_>],
+    cargo_args: &CargoArguments,
+) -> Result<()> {
+    let args = component_build_args(cargo_args);
+    let build_args = CargoArguments::parse_from(args.iter())?;
+    let outputs = spawn_cargo_command(
+        config,
+        metadata,
+        packages,
+        Some("build"),
+        &build_args,
+        &args,
+        &[],
+    )
+    .await
+    .inspect_err(|e| {
+        if let Some(e) = e.downcast_ref::<CargoCommandError>() {
+            std::process::exit(e.code().unwrap_or(1));
+        }
+    })?;
+
+    for (id, path) in &outputs {
+        let bytes = fs::read(path)
+            .with_context(|| format!("failed to read component `{path}`", path = path.display()))?;
+        validate_component(&bytes, path)?;
+        config.terminal().status(
+            "Validated",
+            format!("component {path}", path = path.display()),
+        )?;
+
+        let metadata = match packages
+            .iter()
+            .find(|p| &p.package.id == id)
+            .and_then(|p| p.metadata.as_ref())
+        {
+            Some(metadata) if !metadata.section.test_runner.is_empty() => metadata,
+            _ => continue,
+        };
+
+        let runner = &metadata.section.test_runner;
+        config.terminal().status(
+            "Testing",
+            format!(
+                "component {path} with `{runner}`",
+                path = path.display(),
+                runner = runner.join(" ")
+            ),
+        )?;
+
+        let mut cmd = Command::new(&runner[0]);
+        cmd.args(&runner[1..]).arg(path);
+        if let Some(dir) = metadata.manifest_path.parent() {
+            cmd.current_dir(dir);
+        }
+
+        let status = cmd.status().with_context(|| {
+            format!(
+                "failed to spawn test runner `{program}`",
+                program = runner[0]
+            )
+        })?;
+        if !status.success() {
+            bail!(
+                "test runner `{runner}` failed for component `{path}` ({status})",
+                runner = runner.join(" "),
+                path = path.display()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Gets the arguments of a `build` command for the packages, targets,
+/// profile, and features selected by the given cargo arguments.
+fn component_build_args(cargo_args: &CargoArguments) -> Vec<String> {
+    let mut args = vec!["build".to_string()];
+
+    for spec in &cargo_args.packages {
+        args.push("--package".to_string());
+        args.push(spec.to_string());
+    }
+
+    if cargo_args.workspace {
+        args.push("--workspace".to_string());
+    }
+
+    if let Some(path) = &cargo_args.manifest_path {
+        args.push("--manifest-path".to_string());
+        args.push(path.to_string_lossy().into_owned());
+    }
+
+    for target in &cargo_args.targets {
+        args.push("--target".to_string());
+        args.push(target.clone());
+    }
+
+    match &cargo_args.profile {
+        Some(profile) => {
+            args.push("--profile".to_string());
+            args.push(profile.clone());
+        }
+        None if cargo_args.release => args.push("--release".to_string()),
+        None => {}
+    }
+
+    if !cargo_args.features.is_empty() {
+        args.push("--features".to_string());
+        args.push(cargo_args.features.join(","));
+    }
+
+    for (set, flag) in [
+        (cargo_args.all_features, "--all-features"),
+        (cargo_args.no_default_features, "--no-default-features"),
+        (cargo_args.frozen, "--frozen"),
+        (cargo_args.locked, "--locked"),
+        (cargo_args.offline, "--offline"),
+        (cargo_args.quiet, "--quiet"),
+    ] {
+        if set {
+            args.push(flag.to_string());
+        }
+    }
+
+    args.extend(std::iter::repeat_n("-v".to_string(), cargo_args.verbose));
+
+    if let Some(color) = cargo_args.color {
+        args.push("--color".to_string());
+        args.push(color.to_string());
+    }
 
+    if let Some(cargo) = &cargo_args.cargo {
+        args.push(CARGO_ARG.to_string());
+        args.push(cargo.to_string_lossy().into_owned());
+    }
+
+    args
+}
+
+/// Validates that the given bytes are a valid component.
+fn validate_component(bytes: &[u8], path: &Path) -> Result<()> {
+    wasmparser::Validator::new_with_features(wasmparser::WasmFeatures {
+        component_model: true,
+        ..Default::default()
+    })
+    .validate_all(bytes)
+    .with_context(|| format!("component `{path}` is not valid", path = path.display()))?;
+
+    if !wasmparser::Parser::is_component(bytes) {
+        bail!(
+            "`{path}` is a WebAssembly module and not a component",
+            path = path.display()
+        );
+    }
+
+    Ok(())
+}
+
+/// Checks that a built component conforms to the target world of its
+/// package.
+///
+/// Every export of the target world must be exported by the component with
+/// the same types, and every import of the component must be provided by the
+/// target world, except for the WASI imports of the adapter.
+pub async fn check_target_conformance(
+    config: &Config,
+    metadata: &Metadata,
+    package: &PackageComponentMetadata<'_>,
+    path: &Path,
+    cargo_args: &CargoArguments,
+) -> Result<()> {
+    // The target world may be selected by the enabled features
+    let packages = apply_feature_worlds(std::slice::from_ref(package), cargo_args, None)?;
+    let package = &packages[0];
+    let map = resolve_package(config, metadata, package, cargo_args.network_allowed()).await?;
+    let resolution = package_resolution(&map, package)?;
+
+    let encoder = BindingsEncoder::new(resolution)?;
+    let world_name = encoder.world_name().to_string();
+    let (target, target_world) = match wit_component::decode(&encoder.encode()?)
+        .context("failed to decode the target world")?
+    {
+        DecodedWasm::WitPackage(resolve, pkg) => {
+            let world = resolve.select_world(pkg, Some(&world_name))?;
+            (resolve, world)
+        }
+        DecodedWasm::Component(..) => unreachable!("expected a WIT package"),
+    };
+
+    let bytes = fs::read(path)
+        .with_context(|| format!("failed to read component `{path}`", path = path.display()))?;
+    let (component, component_world) = decode_component(&bytes)?;
+
+    let is_wasi = |key: &WorldKey| match key {
+        WorldKey::Interface(id) => component.interfaces[*id]
+            .package
+            .is_some_and(|pkg| component.packages[pkg].name.namespace == "wasi"),
+        WorldKey::Name(_) => false,
+    };
+
+    let mut unmet: Vec<_> =
+        conformance::missing_exports(&target, target_world, &component, component_world)
+            .into_iter()
+            .map(|e| format!("missing export {e}"))
+            .collect();
+    unmet.extend(
+        conformance::unmet_items(
+            &component,
+            component.worlds[component_world]
+                .imports
+                .iter()
+                .filter(|(key, _)| !is_wasi(key)),
+            &target,
+            &target.worlds[target_world].imports,
+        )
+        .into_iter()
+        .map(|i| format!("unexpected import {i}")),
+    );
+
+    if !unmet.is_empty() {
+        bail!(
+            "component `{path}` does not conform to target world `{world_name}` of package `{name}`:\n{unmet}",
+            path = path.display(),
+            name = package.package.name,
+            unmet = unmet
+                .iter()
+                .map(|u| format!("  {u}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+
+    Ok(())
+}
+
+/// Publish a component for the given workspace and publish options.
+pub async fn publish(config: &Config, options: &PublishOptions<'_>) -> Result<()> {
     let bytes = fs::read(options.path).with_context(|| {
         format!(
             "failed to read component `{path}`",
@@ -616,6 +3530,17 @@ pub async fn publish(config: &Config, options: &PublishOptions<'_>) -> Result<()
     })?;
 
     let bytes = add_registry_metadata(options.package, &bytes, options.path)?;
+    validate_component(&bytes, options.path)?;
+
+    if options.dry_run {
+        print_publish_summary(config, options, &bytes)?;
+        config
+            .terminal()
+            .warn("not publishing component to the registry due to the --dry-run option")?;
+        return Ok(());
+    }
+
+    let client = create_client(config.warg(), options.registry_url, config.terminal())?;
 
     let content = client
         .content()
@@ -668,18 +3593,20 @@ pub async fn publish(config: &Config, options: &PublishOptions<'_>) -> Result<()
 /// Update the dependencies in the lock file.
 ///
 /// This updates only `Cargo-component.lock`.
+///
+/// If `update` is not empty, only the given registry dependencies are
+/// updated and all other dependencies remain at their locked versions.
+#[allow(clippy::too_many_arguments)]
 pub async fn update_lockfile(
     config: &Config,
     metadata: &Metadata,
     packages: &[PackageComponentMetadata<'_>],
+    update: &[PackageId],
     network_allowed: bool,
     lock_update_allowed: bool,
     locked: bool,
     dry_run: bool,
 ) -> Result<()> {
-    // Read the current lock file and generate a new one
-    let map = create_resolution_map(config, packages, None, network_allowed).await?;
-
     let file_lock = acquire_lock_file_ro(config.terminal(), metadata)?;
     let orig_lock_file = file_lock
         .as_ref()
@@ -694,13 +3621,115 @@ pub async fn update_lockfile(
         .transpose()?
         .unwrap_or_default();
 
-    let new_lock_file = map.to_lock_file();
+    for id in update {
+        if !orig_lock_file
+            .packages
+            .iter()
+            .chain(&orig_lock_file.components)
+            .any(|p| &p.id == id)
+        {
+            bail!("package `{id}` is not a locked registry dependency");
+        }
+    }
 
-    for old_pkg in &orig_lock_file.packages {
-        let new_pkg = match new_lock_file
+    // Keep the locked versions of the dependencies not being updated
+    let kept = LockFile::new(
+        orig_lock_file
             .packages
+            .iter()
+            .filter(|p| !update.contains(&p.id))
+            .cloned()
+            .collect::<Vec<_>>(),
+    )
+    .with_components(
+        orig_lock_file
+            .components
+            .iter()
+            .filter(|p| !update.contains(&p.id))
+            .cloned()
+            .collect::<Vec<_>>(),
+    );
+    let resolver = (!update.is_empty()).then(|| LockFileResolver::new(&kept));
+
+    // Read the current lock file and generate a new one
+    let map = create_resolution_map(config, packages, resolver, network_allowed).await?;
+
+    let new_lock_file = map.to_lock_file(metadata.workspace_root.as_std_path())?;
+    if orig_lock_file.is_empty() && new_lock_file.is_empty() {
+        return config
+            .terminal()
+            .note("there are no registry dependencies to update");
+    }
+
+    report_lock_changes(
+        config,
+        &orig_lock_file.packages,
+        &new_lock_file.packages,
+        dry_run,
+    )?;
+    report_lock_changes(
+        config,
+        &orig_lock_file.components,
+        &new_lock_file.components,
+        dry_run,
+    )?;
+
+    for path in &new_lock_file.paths {
+        let status = match orig_lock_file.find_path(&path.id, &path.path) {
+            Some(locked) if locked.digest == path.digest => continue,
+            Some(_) if dry_run => "Would update",
+            Some(_) => "Updating",
+            None if dry_run => "Would lock",
+            None => "Locking",
+        };
+
+        config.terminal().status_with_color(
+            status,
+            format!(
+                "path dependency `{id}` at `{path}`",
+                id = path.id,
+                path = path.path
+            ),
+            Colors::Cyan,
+        )?;
+    }
+
+    if dry_run {
+        config
+            .terminal()
+            .warn("not updating component lock file due to --dry-run option")?;
+    } else {
+        // Update the lock file
+        if new_lock_file != orig_lock_file {
+            drop(file_lock);
+            let file_lock =
+                acquire_lock_file_rw(config.terminal(), metadata, lock_update_allowed, locked)?;
+            new_lock_file
+                .write(file_lock.file(), "cargo-component")
+                .with_context(|| {
+                    format!(
+                        "failed to write lock file `{path}`",
+                        path = file_lock.path().display()
+                    )
+                })?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Reports the changes between the old and new locked packages of a
+/// section of the lock file.
+fn report_lock_changes(
+    config: &Config,
+    old: &[LockedPackage],
+    new: &[LockedPackage],
+    dry_run: bool,
+) -> Result<()> {
+    for old_pkg in old {
+        let new_pkg = match new
             .binary_search_by_key(&old_pkg.key(), LockedPackage::key)
-            .map(|index| &new_lock_file.packages[index])
+            .map(|index| &new[index])
         {
             Ok(pkg) => pkg,
             Err(_) => {
@@ -758,11 +3787,10 @@ pub async fn update_lockfile(
         }
     }
 
-    for new_pkg in &new_lock_file.packages {
-        let old_pkg = match orig_lock_file
-            .packages
+    for new_pkg in new {
+        let old_pkg = match old
             .binary_search_by_key(&new_pkg.key(), LockedPackage::key)
-            .map(|index| &orig_lock_file.packages[index])
+            .map(|index| &old[index])
         {
             Ok(pkg) => pkg,
             Err(_) => {
@@ -803,26 +3831,5 @@ pub async fn update_lockfile(
         }
     }
 
-    if dry_run {
-        config
-            .terminal()
-            .warn("not updating component lock file due to --dry-run option")?;
-    } else {
-        // Update the lock file
-        if new_lock_file != orig_lock_file {
-            drop(file_lock);
-            let file_lock =
-                acquire_lock_file_rw(config.terminal(), metadata, lock_update_allowed, locked)?;
-            new_lock_file
-                .write(file_lock.file(), "cargo-component")
-                .with_context(|| {
-                    format!(
-                        "failed to write lock file `{path}`",
-                        path = file_lock.path().display()
-                    )
-                })?;
-        }
-    }
-
     Ok(())
 }
@@ -1,6 +1,6 @@
 //! Module for component metadata representation in `Cargo.toml`.
 
-use anyhow::{bail, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use cargo_component_core::registry::{Dependency, RegistryPackage};
 use cargo_metadata::Package;
 use semver::{Version, VersionReq};
@@ -11,17 +11,21 @@ use serde::{
 use serde_json::from_value;
 use std::{
     borrow::Cow,
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     path::{Path, PathBuf},
     str::FromStr,
     time::SystemTime,
 };
 use url::Url;
 use warg_protocol::registry::PackageId;
+use wit_bindgen_rust_lib::Ownership;
 
 /// The default directory to look for a target WIT file.
 pub const DEFAULT_WIT_DIR: &str = "wit";
 
+/// The local target path that denotes reading the target WIT from stdin.
+pub const STDIN_TARGET_PATH: &str = "-";
+
 /// The target of a component.
 ///
 /// The target defines the world of the component being developed.
@@ -46,13 +50,14 @@ pub enum Target {
         /// The path to the wit document defining the world.
         ///
         /// Defaults to the `wit` directory.
+        ///
+        /// A path of `-` reads the wit document from stdin.
         path: Option<PathBuf>,
         /// The name of the world being targeted.
         ///
-        /// [Resolve::select_world][select-world] will be used
-        /// to select world.
-        ///
-        /// [select-world]: https://docs.rs/wit-parser/latest/wit_parser/struct.Resolve.html#method.select_world
+        /// The name may be fully-qualified as `<package>/<world>` (e.g.
+        /// `component:foo/example`); if not specified, the target package
+        /// must define a single world.
         world: Option<String>,
         /// The dependencies of the wit document being targeted.
         dependencies: HashMap<PackageId, Dependency>,
@@ -61,7 +66,7 @@ pub enum Target {
 
 impl Target {
     /// Gets the dependencies of the target.
-    pub fn dependencies(&self) -> Cow<HashMap<PackageId, Dependency>> {
+    pub fn dependencies(&self) -> Cow<'_, HashMap<PackageId, Dependency>> {
         match self {
             Self::Package { id, package, .. } => Cow::Owned(HashMap::from_iter([(
                 id.clone(),
@@ -77,6 +82,13 @@ impl Target {
             Self::Package { world, .. } | Self::Local { world, .. } => world.as_deref(),
         }
     }
+
+    /// Sets the target world.
+    pub fn set_world(&mut self, name: impl Into<String>) {
+        match self {
+            Self::Package { world, .. } | Self::Local { world, .. } => *world = Some(name.into()),
+        }
+    }
 }
 
 impl Default for Target {
@@ -89,6 +101,28 @@ impl Default for Target {
     }
 }
 
+/// Splits a world name qualified by the package that defines it, given as
+/// `<package>/<world>` (e.g. `my:comp/example`), into the package and the
+/// world name.
+///
+/// The package may include a version (e.g. `my:comp@1.0.0/example`).
+///
+/// Returns `None` if the name is not qualified by a package.
+pub fn split_qualified_world(name: &str) -> Result<Option<(&str, &str)>> {
+    let (package, world) = match name.split_once('/') {
+        Some(parts) => parts,
+        None => return Ok(None),
+    };
+
+    let id = package.split_once('@').map(|(id, _)| id).unwrap_or(package);
+    id.parse::<PackageId>()
+        .with_context(|| format!("invalid package `{package}` of world `{name}`"))?;
+    wit_parser::validate_id(world)
+        .with_context(|| format!("invalid world name `{world}` of world `{name}`"))?;
+
+    Ok(Some((package, world)))
+}
+
 impl FromStr for Target {
     type Err = anyhow::Error;
 
@@ -103,12 +137,8 @@ impl FromStr for Target {
             None => bail!("expected target format `<package-id>[/<world>]@<version>`"),
         };
 
-        let (id, world) = match id.split_once('/') {
-            Some((id, world)) => {
-                wit_parser::validate_id(world)
-                    .with_context(|| format!("invalid target world name `{world}`"))?;
-                (id, Some(world.to_string()))
-            }
+        let (id, world) = match split_qualified_world(id)? {
+            Some((id, world)) => (id, Some(world.to_string())),
             None => (id, None),
         };
 
@@ -212,20 +242,499 @@ impl<'de> Deserialize<'de> for Target {
     }
 }
 
+/// Represents an adapter of an imported module other than WASI preview1.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModuleAdapter {
+    /// The name of the module whose imports are adapted.
+    pub module: String,
+    /// The path of the adapter.
+    pub path: PathBuf,
+}
+
+/// Represents the adapter setting of a component.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub enum Adapter {
+    /// The built-in adapter is used.
+    #[default]
+    Builtin,
+    /// No adapter is used.
+    None,
+    /// A list of adapters.
+    List {
+        /// The paths to try, in order, for the WASI adapter.
+        ///
+        /// The first adapter that can be read is used; the built-in adapter
+        /// is used if there are no paths.
+        paths: Vec<PathBuf>,
+        /// The adapters of other modules, applied in order after the WASI
+        /// adapter.
+        modules: Vec<ModuleAdapter>,
+    },
+    /// The adapter is selected by target triple.
+    ///
+    /// Targets without an entry use the built-in adapter.
+    Targets(HashMap<String, Adapter>),
+    /// The adapter is selected by cargo profile name.
+    ///
+    /// Profiles without an entry use the `default` entry, if present, or the
+    /// built-in adapter.
+    Profiles(HashMap<String, Adapter>),
+}
+
+impl Adapter {
+    /// Gets the adapter setting for the given target triple and cargo
+    /// profile.
+    ///
+    /// If the target or profile is not known, the fallback of the settings
+    /// keyed by target triple or profile is used.
+    pub fn select(&self, target: Option<&str>, profile: Option<&str>) -> &Self {
+        match self {
+            Self::Targets(targets) => target
+                .and_then(|t| targets.get(t))
+                .unwrap_or(&Self::Builtin),
+            Self::Profiles(profiles) => profile
+                .and_then(|p| profiles.get(p))
+                .or_else(|| profiles.get("default"))
+                .unwrap_or(&Self::Builtin),
+            adapter => adapter,
+        }
+    }
+
+    /// Gets the adapters of modules other than WASI preview1 of the setting
+    /// for the given target triple and cargo profile.
+    pub fn modules(&self, target: Option<&str>, profile: Option<&str>) -> &[ModuleAdapter] {
+        match self.select(target, profile) {
+            Self::List { modules, .. } => modules,
+            _ => &[],
+        }
+    }
+
+    fn join_paths(&mut self, dir: &Path) {
+        match self {
+            Self::Builtin | Self::None => {}
+            Self::List { paths, modules } => {
+                for path in paths
+                    .iter_mut()
+                    .chain(modules.iter_mut().map(|m| &mut m.path))
+                {
+                    *path = dir.join(path.as_path());
+                }
+            }
+            Self::Targets(adapters) | Self::Profiles(adapters) => {
+                for adapter in adapters.values_mut() {
+                    adapter.join_paths(dir);
+                }
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Adapter {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Entry {
+            Path(PathBuf),
+            Module(ModuleAdapter),
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Value {
+            Enabled(bool),
+            One(PathBuf),
+            Many(Vec<Entry>),
+            Table(HashMap<String, Value>),
+        }
+
+        fn convert<E: de::Error>(value: Value, nested: bool) -> Result<Adapter, E> {
+            Ok(match value {
+                Value::Enabled(true) => Adapter::Builtin,
+                Value::Enabled(false) => Adapter::None,
+                Value::One(path) => Adapter::List {
+                    paths: vec![path],
+                    modules: Vec::new(),
+                },
+                Value::Many(entries) => {
+                    let mut paths = Vec::new();
+                    let mut modules: Vec<ModuleAdapter> = Vec::new();
+                    for entry in entries {
+                        match entry {
+                            Entry::Path(path) => paths.push(path),
+                            Entry::Module(adapter) => {
+                                if adapter.module == crate::WASI_ADAPTER_NAME {
+                                    return Err(E::custom(format!(
+                                        "the adapter of module `{module}` must be given as a path",
+                                        module = adapter.module
+                                    )));
+                                }
+
+                                if modules.iter().any(|m| m.module == adapter.module) {
+                                    return Err(E::custom(format!(
+                                        "module `{module}` has more than one adapter",
+                                        module = adapter.module
+                                    )));
+                                }
+
+                                modules.push(adapter);
+                            }
+                        }
+                    }
+
+                    Adapter::List { paths, modules }
+                }
+                Value::Table(_) if nested => {
+                    return Err(E::custom(
+                        "the adapter for a target or profile must be a boolean, \
+                         a path, or a list of adapters",
+                    ))
+                }
+                Value::Table(mut table) => {
+                    let key = match (table.len(), table.keys().next().map(String::as_str)) {
+                        (1, Some(key @ ("targets" | "profiles"))) => key.to_string(),
+                        _ => {
+                            return Err(E::custom(
+                                "an adapter table must have either a `targets` table keyed by \
+                                 target triple or a `profiles` table keyed by profile name",
+                            ))
+                        }
+                    };
+
+                    let adapters = match table.remove(&key) {
+                        Some(Value::Table(adapters)) => adapters
+                            .into_iter()
+                            .map(|(key, value)| Ok((key, convert(value, true)?)))
+                            .collect::<Result<_, E>>()?,
+                        _ => return Err(E::custom(format!("the adapter `{key}` must be a table"))),
+                    };
+
+                    if key == "targets" {
+                        Adapter::Targets(adapters)
+                    } else {
+                        Adapter::Profiles(adapters)
+                    }
+                }
+            })
+        }
+
+        convert(Value::deserialize(deserializer)?, false)
+    }
+}
+
+/// Represents the panic strategy of a component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PanicStrategy {
+    /// Panics abort, trapping the component instance.
+    Abort,
+    /// Panics unwind the stack.
+    Unwind,
+}
+
+impl std::fmt::Display for PanicStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Abort => write!(f, "abort"),
+            Self::Unwind => write!(f, "unwind"),
+        }
+    }
+}
+
+/// Represents the custom sections stripped from a built component.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Strip {
+    /// No sections are stripped.
+    #[default]
+    None,
+    /// The DWARF debug sections and the `name` sections of the core modules
+    /// are stripped.
+    Debug,
+    /// All custom sections other than `producers` are stripped.
+    AllButProducers,
+}
+
+impl Strip {
+    /// Determines if a custom section with the given name is kept.
+    pub fn keeps(&self, section: &str) -> bool {
+        match self {
+            Self::None => true,
+            Self::Debug => !section.starts_with(".debug_") && section != "name",
+            Self::AllButProducers => section == "producers",
+        }
+    }
+}
+
+/// Represents the strip setting of a component.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum StripSetting {
+    /// The sections stripped for every cargo profile.
+    All(Strip),
+    /// The sections stripped, selected by cargo profile name.
+    ///
+    /// Profiles without an entry use the `default` entry, if present, or
+    /// strip nothing.
+    Profiles(HashMap<String, Strip>),
+}
+
+impl Default for StripSetting {
+    fn default() -> Self {
+        Self::All(Strip::None)
+    }
+}
+
+impl StripSetting {
+    /// Gets the sections stripped for the given cargo profile.
+    pub fn select(&self, profile: Option<&str>) -> Strip {
+        match self {
+            Self::All(strip) => *strip,
+            Self::Profiles(profiles) => profile
+                .and_then(|p| profiles.get(p))
+                .or_else(|| profiles.get("default"))
+                .copied()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Represents the producers fields written to a component.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct ProducersFields {
+    /// Whether to write the `processed-by` field.
+    ///
+    /// Defaults to `true`.
+    pub processed_by: bool,
+    /// Whether to write the `language` field.
+    ///
+    /// Defaults to `false`.
+    pub language: bool,
+    /// Whether to write the `source` field with the name and version of the
+    /// crate the component was built from.
+    ///
+    /// Defaults to `false`.
+    pub source: bool,
+}
+
+impl Default for ProducersFields {
+    fn default() -> Self {
+        Self {
+            processed_by: true,
+            language: false,
+            source: false,
+        }
+    }
+}
+
+/// Represents the default options of the `generate!` macro for a package.
+///
+/// Options given to the macro itself override these defaults.
+#[derive(Default, Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct BindingsOptions {
+    /// The path of the type implementing the exports of the world.
+    pub implementor: Option<String>,
+    /// The ownership model of the types of imported function parameters.
+    pub ownership: Option<String>,
+    /// Whether imported resource handle types implement `Send` and `Sync`.
+    pub thread_safe_resources: Option<bool>,
+    /// Whether strings are represented as `Vec<u8>` instead of `String`.
+    pub raw_strings: Option<bool>,
+    /// The lints to allow in the generated bindings module.
+    pub allow: Vec<String>,
+    /// Whether generated enums are marked as `#[non_exhaustive]`.
+    pub non_exhaustive: Option<bool>,
+    /// The paths of additional derive macros applied to generated types.
+    pub additional_derives: Vec<String>,
+}
+
+impl BindingsOptions {
+    fn validate(&self) -> Result<()> {
+        let parse_path = |option: &str, path: &str| {
+            syn::parse_str::<syn::Path>(path)
+                .with_context(|| format!("invalid path `{path}` for bindings option `{option}`"))
+        };
+
+        if let Some(implementor) = &self.implementor {
+            let path = parse_path("implementor", implementor)?;
+            if let Some(segment) = path.segments.first() {
+                if segment.ident == "self" || segment.ident == "crate" {
+                    bail!("cannot use `self` or `crate` as the implementor path");
+                }
+            }
+        }
+
+        if let Some(ownership) = &self.ownership {
+            Ownership::from_str(ownership).map_err(|e| {
+                anyhow!("invalid ownership `{ownership}` for bindings option `ownership`: {e}")
+            })?;
+        }
+
+        for lint in &self.allow {
+            parse_path("allow", lint)?;
+        }
+
+        for derive in &self.additional_derives {
+            parse_path("additional-derives", derive)?;
+        }
+
+        Ok(())
+    }
+
+    /// Formats the options as the options of the `generate!` macro.
+    pub fn to_macro_options(&self) -> String {
+        let mut options = Vec::new();
+
+        if let Some(implementor) = &self.implementor {
+            options.push(format!("implementor: {implementor}"));
+        }
+
+        if let Some(ownership) = &self.ownership {
+            options.push(format!("ownership: {ownership:?}"));
+        }
+
+        for (name, value) in [
+            ("thread_safe_resources", self.thread_safe_resources),
+            ("raw_strings", self.raw_strings),
+            ("non_exhaustive", self.non_exhaustive),
+        ] {
+            if let Some(value) = value {
+                options.push(format!("{name}: {value}"));
+            }
+        }
+
+        if !self.allow.is_empty() {
+            options.push(format!(
+                "allow: [{lints}]",
+                lints = self
+                    .allow
+                    .iter()
+                    .map(|lint| format!("{lint:?}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        if !self.additional_derives.is_empty() {
+            options.push(format!(
+                "additional_derives: [{derives}]",
+                derives = self.additional_derives.join(", ")
+            ));
+        }
+
+        options.join(",\n")
+    }
+}
+
+/// Represents the component settings of a cargo feature.
+#[derive(Default, Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct FeatureSettings {
+    /// The world targeted by the component when the feature is enabled.
+    ///
+    /// This overrides the world of the `target` setting.
+    pub world: Option<String>,
+}
+
 /// Represents the `package.metadata.component` section in `Cargo.toml`.
 #[derive(Default, Debug, Clone, Deserialize)]
-#[serde(default, deny_unknown_fields)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
 pub struct ComponentSection {
     /// The package id of the component, for publishing.
     pub package: Option<PackageId>,
     /// The world targeted by the component.
     pub target: Target,
-    /// The path to the WASI adapter to use.
-    pub adapter: Option<PathBuf>,
+    /// The adapters to use.
+    ///
+    /// In the manifest, this is either a boolean, a single path, a list of
+    /// paths to try in order for the WASI adapter and `{ module, path }`
+    /// tables of adapters of other modules, or a `targets` or `profiles`
+    /// table of these keyed by target triple or by cargo profile name.
+    pub adapter: Adapter,
     /// The dependencies of the component.
     pub dependencies: HashMap<PackageId, Dependency>,
     /// The registries to use for the component.
     pub registries: HashMap<String, Url>,
+    /// The required version of `wit-bindgen` used to generate bindings.
+    ///
+    /// If specified, the build fails when the effective `wit-bindgen`
+    /// version does not satisfy the requirement.
+    pub wit_bindgen: Option<VersionReq>,
+    /// The Rust path of the `wit-bindgen` runtime module used by the
+    /// generated bindings (e.g. `::wit_bindgen::rt`).
+    ///
+    /// Defaults to the runtime re-exported by `cargo-component-bindings`.
+    pub runtime_path: Option<String>,
+    /// The default options of the `generate!` macro.
+    pub bindings: BindingsOptions,
+    /// The name written to the name section of the component.
+    ///
+    /// Defaults to the crate name.
+    pub module_name: Option<String>,
+    /// The file name, without the `.wasm` extension, of the built component.
+    ///
+    /// Defaults to the crate name.
+    pub output_name: Option<String>,
+    /// The producers fields to write to the component.
+    pub producers: ProducersFields,
+    /// The custom sections stripped from the built component.
+    ///
+    /// In the manifest, this is either a single mode or a table of modes
+    /// keyed by cargo profile name.
+    pub strip: StripSetting,
+    /// The imports the component is not allowed to have.
+    ///
+    /// Each entry is either an interface (e.g. `wasi:random/insecure`) or a
+    /// package (e.g. `wasi:random`) to forbid all interfaces of the package.
+    pub forbidden_imports: Vec<String>,
+    /// The custom sections always kept in the built component.
+    pub keep_sections: Vec<String>,
+    /// The custom sections removed from the built component and the modules
+    /// it contains.
+    pub drop_sections: Vec<String>,
+    /// The version embedded in the component.
+    ///
+    /// Defaults to the version of the cargo package.
+    pub version: Option<Version>,
+    /// Custom key-value pairs to embed in the component.
+    ///
+    /// The pairs are written as a JSON object to a `metadata` custom section.
+    pub metadata: BTreeMap<String, String>,
+    /// The worlds targeted by specific cargo targets of the package.
+    ///
+    /// Maps the name of a `[lib]` or `[[bin]]` target to the name of a world
+    /// in the target package; other cargo targets use the `target` world.
+    pub target_worlds: HashMap<String, String>,
+    /// The component settings of the cargo features of the package.
+    ///
+    /// The settings of a feature apply when the feature is enabled for a
+    /// build.
+    pub features: HashMap<String, FeatureSettings>,
+    /// The path to a file holding the build number of the component.
+    ///
+    /// The number in the file is incremented for every component built.
+    pub build_number_file: Option<PathBuf>,
+    /// Additional flags passed to `rustc` when building for a wasm target.
+    pub rustflags: Vec<String>,
+    /// The panic strategy used when building for a wasm target.
+    ///
+    /// Defaults to the strategy of the cargo profile.
+    pub panic: Option<PanicStrategy>,
+    /// The command run by `cargo component test` to test the built component.
+    ///
+    /// The first element is the program to run and the rest are its
+    /// arguments; the path of the component is appended as the last argument.
+    pub test_runner: Vec<String>,
+    /// Additional arguments passed to the linker when building for a wasm
+    /// target.
+    ///
+    /// Each argument is passed to `rustc` as `-C link-arg=<arg>`.
+    pub link_args: Vec<String>,
 }
 
 /// Represents cargo metadata for a WebAssembly component.
@@ -287,26 +796,57 @@ impl ComponentMetadata {
         } = &mut section.target
         {
             if let Some(path) = path {
-                *path = manifest_dir.join(path.as_path());
+                if path != Path::new(STDIN_TARGET_PATH) {
+                    *path = manifest_dir.join(path.as_path());
+                }
             }
 
             for dependency in dependencies.values_mut() {
-                if let Dependency::Local(path) = dependency {
-                    *path = manifest_dir.join(path.as_path());
+                if let Dependency::Local(local) = dependency {
+                    local.path = manifest_dir.join(local.path.as_path());
                 }
             }
         }
 
         for dependency in section.dependencies.values_mut() {
-            if let Dependency::Local(path) = dependency {
-                *path = manifest_dir.join(path.as_path());
+            if let Dependency::Local(local) = dependency {
+                local.path = manifest_dir.join(local.path.as_path());
             }
         }
 
-        if let Some(adapter) = section.adapter.as_mut() {
-            *adapter = manifest_dir.join(adapter.as_path());
+        section.adapter.join_paths(manifest_dir);
+
+        if let Some(path) = section.build_number_file.as_mut() {
+            *path = manifest_dir.join(path.as_path());
+        }
+
+        if let Some(name) = section
+            .keep_sections
+            .iter()
+            .find(|name| section.drop_sections.contains(name))
+        {
+            bail!(
+                "custom section `{name}` cannot be both kept and dropped in manifest `{path}`",
+                path = package.manifest_path
+            );
+        }
+
+        if let Some(name) = &section.output_name {
+            if name.is_empty() || name.contains(['/', '\\']) || name == "." || name == ".." {
+                bail!(
+                    "invalid component output name `{name}` in manifest `{path}`: the name must be a file name without directories",
+                    path = package.manifest_path
+                );
+            }
         }
 
+        section.bindings.validate().with_context(|| {
+            format!(
+                "invalid bindings options in manifest `{path}`",
+                path = package.manifest_path
+            )
+        })?;
+
         Ok(Some(Self {
             name: package.name.clone(),
             version: package.version.clone(),
@@ -316,11 +856,38 @@ impl ComponentMetadata {
         }))
     }
 
+    /// Determines if a custom section with the given name is kept in the
+    /// built component when stripping with the given mode.
+    ///
+    /// The `keep-sections` and `drop-sections` settings take precedence over
+    /// the strip mode.
+    pub fn keeps_section(&self, strip: Strip, name: &str) -> bool {
+        if self.section.keep_sections.iter().any(|s| s == name) {
+            return true;
+        }
+
+        !self.section.drop_sections.iter().any(|s| s == name) && strip.keeps(name)
+    }
+
+    /// Gets the file name, without the `.wasm` extension, of the built
+    /// component.
+    pub fn output_name(&self) -> &str {
+        self.section.output_name.as_deref().unwrap_or(&self.name)
+    }
+
+    /// Determines if the local target is read from stdin.
+    pub fn target_from_stdin(&self) -> bool {
+        matches!(
+            &self.section.target,
+            Target::Local { path: Some(path), .. } if path == Path::new(STDIN_TARGET_PATH)
+        )
+    }
+
     /// Gets the path to a local target.
     ///
     /// Returns `None` if the target is a registry package or
     /// if a path is not specified and the default path does not exist.
-    pub fn target_path(&self) -> Option<Cow<Path>> {
+    pub fn target_path(&self) -> Option<Cow<'_, Path>> {
         match &self.section.target {
             Target::Local {
                 path: Some(path), ..
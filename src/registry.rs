@@ -1,14 +1,19 @@
 //! Module for interacting with component registries.
 
 use crate::{config::Config, metadata::ComponentMetadata};
-use anyhow::Result;
+use anyhow::{Context, Result};
 use cargo_component_core::{
-    lock::{LockFile, LockFileResolver, LockedPackage, LockedPackageVersion},
+    lock::{LockFile, LockFileResolver, LockedPackage, LockedPackageVersion, LockedPath},
     registry::{DependencyResolution, DependencyResolutionMap, DependencyResolver},
 };
 use semver::Version;
-use std::collections::HashMap;
-use warg_crypto::hash::AnyHash;
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::Read,
+    path::Path,
+};
+use warg_crypto::hash::{AnyHash, Digest, Hash, Sha256};
 use warg_protocol::registry::PackageId;
 
 /// Represents a resolution of dependencies for a Cargo package.
@@ -126,62 +131,172 @@ impl<'a> PackageResolutionMap<'a> {
     }
 
     /// Converts the resolution map into a lock file.
-    pub fn to_lock_file(&self) -> LockFile {
-        type PackageKey = (PackageId, Option<String>);
-        type VersionsMap = HashMap<String, (Version, AnyHash)>;
-        let mut packages: HashMap<PackageKey, VersionsMap> = HashMap::new();
-
-        for resolution in self.0.values() {
-            for (_, dep) in resolution.all() {
-                match dep.key() {
-                    Some((id, registry)) => {
-                        let pkg = match dep {
-                            DependencyResolution::Registry(pkg) => pkg,
-                            DependencyResolution::Local(_) => unreachable!(),
-                        };
-
-                        let prev = packages
-                            .entry((id.clone(), registry.map(str::to_string)))
-                            .or_default()
-                            .insert(
-                                pkg.requirement.to_string(),
-                                (pkg.version.clone(), pkg.digest.clone()),
-                            );
-
-                        if let Some((prev, _)) = prev {
-                            // The same requirements should resolve to the same version
-                            assert!(prev == pkg.version)
-                        }
-                    }
-                    None => continue,
+    ///
+    /// Target (WIT) dependencies and component dependencies are locked in
+    /// separate sections of the lock file; local path dependencies are locked
+    /// with a digest of their contents, relative to the given root directory.
+    pub fn to_lock_file(&self, root: &Path) -> Result<LockFile> {
+        Ok(LockFile::new(locked_packages(
+            self.0.values().flat_map(|r| r.target_resolutions.values()),
+        ))
+        .with_components(locked_packages(
+            self.0.values().flat_map(|r| r.resolutions.values()),
+        ))
+        .with_paths(locked_paths(
+            self.0.values().flat_map(|r| r.all().map(|(_, d)| d)),
+            root,
+        )?))
+    }
+}
+
+/// Creates the sorted list of locked packages for the given resolutions.
+fn locked_packages<'a>(
+    resolutions: impl Iterator<Item = &'a DependencyResolution>,
+) -> Vec<LockedPackage> {
+    type PackageKey = (PackageId, Option<String>);
+    type VersionsMap = HashMap<String, (Version, AnyHash)>;
+    let mut packages: HashMap<PackageKey, VersionsMap> = HashMap::new();
+
+    for dep in resolutions {
+        match dep.key() {
+            Some((id, registry)) => {
+                let pkg = match dep {
+                    DependencyResolution::Registry(pkg) => pkg,
+                    DependencyResolution::Local(_) => unreachable!(),
+                };
+
+                let prev = packages
+                    .entry((id.clone(), registry.map(str::to_string)))
+                    .or_default()
+                    .insert(
+                        pkg.requirement.to_string(),
+                        (pkg.version.clone(), pkg.digest.clone()),
+                    );
+
+                if let Some((prev, _)) = prev {
+                    // The same requirements should resolve to the same version
+                    assert!(prev == pkg.version)
                 }
             }
+            None => continue,
         }
+    }
+
+    let mut packages: Vec<_> = packages
+        .into_iter()
+        .map(|((id, registry), versions)| {
+            let mut versions: Vec<LockedPackageVersion> = versions
+                .into_iter()
+                .map(|(requirement, (version, digest))| LockedPackageVersion {
+                    requirement,
+                    version,
+                    digest,
+                })
+                .collect();
 
-        let mut packages: Vec<_> = packages
-            .into_iter()
-            .map(|((id, registry), versions)| {
-                let mut versions: Vec<LockedPackageVersion> = versions
-                    .into_iter()
-                    .map(|(requirement, (version, digest))| LockedPackageVersion {
-                        requirement,
-                        version,
-                        digest,
-                    })
-                    .collect();
-
-                versions.sort_by(|a, b| a.key().cmp(b.key()));
-
-                LockedPackage {
-                    id,
-                    registry,
-                    versions,
+            versions.sort_by(|a, b| a.key().cmp(b.key()));
+
+            LockedPackage {
+                id,
+                registry,
+                versions,
+            }
+        })
+        .collect();
+
+    packages.sort_by(|a, b| a.key().cmp(&b.key()));
+    packages
+}
+
+/// Creates the sorted list of locked paths for the given resolutions.
+fn locked_paths<'a>(
+    resolutions: impl Iterator<Item = &'a DependencyResolution>,
+    root: &Path,
+) -> Result<Vec<LockedPath>> {
+    let mut paths = Vec::new();
+    for dep in resolutions {
+        let local = match dep {
+            DependencyResolution::Local(local) => local,
+            DependencyResolution::Registry(_) => continue,
+        };
+
+        let path = local
+            .path
+            .strip_prefix(root)
+            .unwrap_or(&local.path)
+            .to_str()
+            .with_context(|| {
+                format!(
+                    "path `{path}` of dependency `{id}` is not valid UTF-8",
+                    path = local.path.display(),
+                    id = local.id
+                )
+            })?
+            .replace('\\', "/");
+
+        paths.push(LockedPath {
+            id: local.id.clone(),
+            path,
+            digest: path_digest(&local.path).with_context(|| {
+                format!(
+                    "failed to hash path `{path}` of dependency `{id}`",
+                    path = local.path.display(),
+                    id = local.id
+                )
+            })?,
+        });
+    }
+
+    paths.sort_by(|a, b| a.key().cmp(&b.key()));
+    paths.dedup();
+    Ok(paths)
+}
+
+/// Computes a digest of the contents of a file or, recursively, of the files
+/// of a directory.
+///
+/// The files are read in chunks rather than all at once.
+fn path_digest(path: &Path) -> Result<AnyHash> {
+    fn add(path: &Path, relative: &Path, hasher: &mut Sha256) -> Result<()> {
+        if !path.is_dir() {
+            let mut file = File::open(path)
+                .with_context(|| format!("failed to open `{path}`", path = path.display()))?;
+            let len = file
+                .metadata()
+                .with_context(|| format!("failed to read `{path}`", path = path.display()))?
+                .len();
+            let name = relative.to_string_lossy().replace('\\', "/");
+            hasher.update((name.len() as u64).to_le_bytes());
+            hasher.update(name.as_bytes());
+            hasher.update(len.to_le_bytes());
+
+            let mut buf = [0; 8192];
+            loop {
+                let n = file
+                    .read(&mut buf)
+                    .with_context(|| format!("failed to read `{path}`", path = path.display()))?;
+                if n == 0 {
+                    return Ok(());
                 }
-            })
-            .collect();
 
-        packages.sort_by(|a, b| a.key().cmp(&b.key()));
+                hasher.update(&buf[..n]);
+            }
+        }
+
+        let mut entries = fs::read_dir(path)
+            .with_context(|| format!("failed to read directory `{path}`", path = path.display()))?
+            .map(|e| Ok(e?.file_name()))
+            .collect::<Result<Vec<_>>>()?;
+        entries.sort();
 
-        LockFile::new(packages)
+        for name in entries {
+            add(&path.join(&name), &relative.join(&name), hasher)?;
+        }
+
+        Ok(())
     }
+
+    let mut hasher = Sha256::new();
+    add(path, Path::new(""), &mut hasher)?;
+    Ok(Hash::<Sha256>::from(hasher.finalize()).into())
 }
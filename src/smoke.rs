@@ -0,0 +1,91 @@
+//! Module for smoke testing built components with wasmtime.
+
+use crate::bindings::world_key_name;
+use anyhow::{bail, Context, Result};
+use std::{fs, path::Path};
+use wasmtime::{
+    component::{Component, Linker, LinkerInstance, ResourceType},
+    Config, Engine,
+};
+use wit_component::DecodedWasm;
+use wit_parser::{Function, Resolve, TypeDefKind, TypeId, WorldItem};
+
+/// Instantiates the component at the given path without executing it.
+///
+/// Every import of the component is provided by a stub that traps when
+/// called, so this catches compilation and linking errors only.
+pub fn instantiate(path: &Path) -> Result<()> {
+    let bytes = fs::read(path)
+        .with_context(|| format!("failed to read component `{path}`", path = path.display()))?;
+
+    let (resolve, world) = match wit_component::decode(&bytes)
+        .with_context(|| format!("failed to decode component `{path}`", path = path.display()))?
+    {
+        DecodedWasm::Component(resolve, world) => (resolve, world),
+        DecodedWasm::WitPackage(..) => return Ok(()),
+    };
+
+    let mut config = Config::new();
+    config.wasm_component_model(true);
+    let engine = Engine::new(&config)?;
+    let component = Component::new(&engine, &bytes).with_context(|| {
+        format!(
+            "failed to compile component `{path}`",
+            path = path.display()
+        )
+    })?;
+
+    let mut linker = Linker::<()>::new(&engine);
+    for (key, item) in &resolve.worlds[world].imports {
+        let name = world_key_name(&resolve, key);
+        match item {
+            WorldItem::Interface(id) => {
+                let interface = &resolve.interfaces[*id];
+                let mut instance = linker.instance(&name)?;
+                for (name, ty) in &interface.types {
+                    define_resource(&resolve, &mut instance, name, *ty)?;
+                }
+
+                for func in interface.functions.values() {
+                    define_function(&component, &mut instance, func)?;
+                }
+            }
+            WorldItem::Function(func) => define_function(&component, &mut linker.root(), func)?,
+            WorldItem::Type(ty) => define_resource(&resolve, &mut linker.root(), &name, *ty)?,
+        }
+    }
+
+    linker.instantiate_pre(&component).with_context(|| {
+        format!(
+            "failed to instantiate component `{path}`",
+            path = path.display()
+        )
+    })?;
+
+    Ok(())
+}
+
+fn define_resource(
+    resolve: &Resolve,
+    instance: &mut LinkerInstance<()>,
+    name: &str,
+    ty: TypeId,
+) -> Result<()> {
+    if let TypeDefKind::Resource = resolve.types[ty].kind {
+        instance.resource(name, ResourceType::host::<()>(), |_, _| Ok(()))?;
+    }
+
+    Ok(())
+}
+
+fn define_function(
+    component: &Component,
+    instance: &mut LinkerInstance<()>,
+    func: &Function,
+) -> Result<()> {
+    // Resource functions are already named `[method]res.name`, etc.
+    let import = func.name.clone();
+    instance.func_new(component, &func.name, move |_, _, _| {
+        bail!("imported function `{import}` is not implemented by the smoke test host")
+    })
+}
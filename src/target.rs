@@ -1,4 +1,6 @@
-use anyhow::{bail, Result};
+//! Module for managing the Rust toolchain used for building components.
+
+use anyhow::{bail, Context, Result};
 use std::{
     env,
     path::PathBuf,
@@ -7,40 +9,78 @@ use std::{
 
 use crate::config::Config;
 
-pub fn install_wasm32_wasi(config: &Config) -> Result<()> {
-    let sysroot = get_sysroot()?;
-    if sysroot.join("lib/rustlib/wasm32-wasi").exists() {
+/// Determines if the given target is installed.
+pub fn is_target_installed(target: &str) -> Result<bool> {
+    Ok(get_sysroot()?.join("lib/rustlib").join(target).exists())
+}
+
+/// Installs the given target if it is not already installed.
+pub fn install_target(config: &Config, target: &str) -> Result<()> {
+    if is_target_installed(target)? {
         return Ok(());
     }
 
     if env::var_os("RUSTUP_TOOLCHAIN").is_none() {
         bail!(
-            "failed to find the `wasm32-wasi` target \
+            "failed to find the `{target}` target \
              and `rustup` is not available. If you're using rustup \
              make sure that it's correctly installed; if not, make sure to \
-             install the `wasm32-wasi` target before using this command"
+             install the `{target}` target before using this command"
         );
     }
 
     config
         .terminal()
-        .status("Installing", "wasm32-wasi target")?;
+        .status("Installing", format!("{target} target"))?;
 
     let output = Command::new("rustup")
         .arg("target")
         .arg("add")
-        .arg("wasm32-wasi")
+        .arg(target)
         .stderr(Stdio::inherit())
         .stdout(Stdio::inherit())
         .output()?;
 
     if !output.status.success() {
-        bail!("failed to install the `wasm32-wasi` target");
+        bail!("failed to install the `{target}` target");
     }
 
     Ok(())
 }
 
+/// Finds the path to the `rust-lld` linker shipped with the Rust toolchain.
+pub fn find_rust_lld() -> Result<PathBuf> {
+    let output = Command::new("rustc").arg("-vV").output()?;
+    if !output.status.success() {
+        bail!(
+            "failed to execute `rustc -vV`, \
+                 command exited with error: {output}",
+            output = String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    let stdout = String::from_utf8(output.stdout)?;
+    let host = stdout
+        .lines()
+        .find_map(|l| l.strip_prefix("host: "))
+        .context("failed to determine the host triple from `rustc -vV`")?;
+
+    let path = get_sysroot()?
+        .join("lib/rustlib")
+        .join(host)
+        .join("bin/rust-lld")
+        .with_extension(env::consts::EXE_EXTENSION);
+
+    if !path.is_file() {
+        bail!(
+            "failed to find `rust-lld` at `{path}`",
+            path = path.display()
+        );
+    }
+
+    Ok(path)
+}
+
 fn get_sysroot() -> Result<PathBuf> {
     let output = Command::new("rustc")
         .arg("--print")
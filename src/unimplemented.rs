@@ -0,0 +1,182 @@
+//! Module for detecting unimplemented exports in the source of a component.
+
+use crate::bindings::ExportedFunction;
+use anyhow::{Context, Result};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+use syn::{Block, Expr, ImplItem, Item, Stmt, UseTree};
+
+/// The macros that mark a function body as unimplemented.
+const UNIMPLEMENTED_MACROS: &[&str] = &["todo", "unimplemented"];
+
+/// Finds the exported functions whose trait method bodies consist solely of
+/// a `todo!()` or `unimplemented!()` invocation.
+///
+/// The source of the crate is parsed starting from the given root source
+/// file, following out-of-line modules. Generated trait implementations are
+/// matched to exports by trait name, method name, and module path: a trait
+/// path through an `exports` module (e.g. `exports::foo::bar::baz::Guest`)
+/// matches the exports of the interface named by its last module, and any
+/// other trait path matches the functions exported by the world itself. The
+/// `use` declarations of a module are followed to resolve the trait path.
+///
+/// Returns the WIT export names of the unimplemented functions.
+pub fn find_unimplemented_exports<'a>(
+    root: &Path,
+    exports: &'a [ExportedFunction],
+) -> Result<Vec<&'a str>> {
+    let mut found = Vec::new();
+    let dir = root.parent().unwrap_or(Path::new("."));
+    visit_file(root, dir, exports, &mut found)?;
+    found.sort();
+    found.dedup();
+    Ok(found)
+}
+
+fn visit_file<'a>(
+    path: &Path,
+    dir: &Path,
+    exports: &'a [ExportedFunction],
+    found: &mut Vec<&'a str>,
+) -> Result<()> {
+    let source = fs::read_to_string(path)
+        .with_context(|| format!("failed to read source file `{path}`", path = path.display()))?;
+    let file = syn::parse_file(&source).with_context(|| {
+        format!(
+            "failed to parse source file `{path}`",
+            path = path.display()
+        )
+    })?;
+
+    visit_items(&file.items, dir, exports, found)
+}
+
+fn visit_items<'a>(
+    items: &[Item],
+    dir: &Path,
+    exports: &'a [ExportedFunction],
+    found: &mut Vec<&'a str>,
+) -> Result<()> {
+    let mut uses = HashMap::new();
+    for item in items {
+        if let Item::Use(item) = item {
+            collect_uses(&item.tree, &mut Vec::new(), &mut uses);
+        }
+    }
+
+    for item in items {
+        match item {
+            Item::Impl(item) => {
+                let path = match &item.trait_ {
+                    Some((_, path, _)) => path,
+                    None => continue,
+                };
+
+                let mut segments: Vec<_> =
+                    path.segments.iter().map(|s| s.ident.to_string()).collect();
+                if let Some(path) = uses.get(&segments[0]) {
+                    segments.splice(..1, path.iter().cloned());
+                }
+
+                let trait_name = match segments.pop() {
+                    Some(name) if name.starts_with("Guest") => name,
+                    _ => continue,
+                };
+
+                // Only traits under an `exports` module belong to an interface
+                let module = match segments.iter().rposition(|s| s == "exports") {
+                    Some(pos) if pos + 1 < segments.len() => segments.last().cloned(),
+                    _ => None,
+                };
+
+                for item in &item.items {
+                    let func = match item {
+                        ImplItem::Fn(func) if is_unimplemented(&func.block) => func,
+                        _ => continue,
+                    };
+
+                    let method = func.sig.ident.to_string();
+                    found.extend(
+                        exports
+                            .iter()
+                            .filter(|e| {
+                                e.trait_name == trait_name
+                                    && e.method == method
+                                    && e.module == module
+                            })
+                            .map(|e| e.export.as_str()),
+                    );
+                }
+            }
+            Item::Mod(item) => {
+                let name = item.ident.to_string();
+                match &item.content {
+                    Some((_, items)) => visit_items(items, &dir.join(&name), exports, found)?,
+                    None => {
+                        if let Some(path) = module_file(dir, &name) {
+                            visit_file(&path, &dir.join(&name), exports, found)?;
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Collects the paths imported by a `use` tree, keyed by the name they are
+/// imported as.
+fn collect_uses(tree: &UseTree, prefix: &mut Vec<String>, uses: &mut HashMap<String, Vec<String>>) {
+    match tree {
+        UseTree::Path(path) => {
+            prefix.push(path.ident.to_string());
+            collect_uses(&path.tree, prefix, uses);
+            prefix.pop();
+        }
+        UseTree::Name(name) => {
+            let mut path = prefix.clone();
+            path.push(name.ident.to_string());
+            uses.insert(name.ident.to_string(), path);
+        }
+        UseTree::Rename(rename) => {
+            let mut path = prefix.clone();
+            path.push(rename.ident.to_string());
+            uses.insert(rename.rename.to_string(), path);
+        }
+        UseTree::Group(group) => {
+            for tree in &group.items {
+                collect_uses(tree, prefix, uses);
+            }
+        }
+        UseTree::Glob(_) => {}
+    }
+}
+
+/// Finds the source file of an out-of-line module.
+fn module_file(dir: &Path, name: &str) -> Option<PathBuf> {
+    [
+        dir.join(format!("{name}.rs")),
+        dir.join(name).join("mod.rs"),
+    ]
+    .into_iter()
+    .find(|path| path.is_file())
+}
+
+fn is_unimplemented(block: &Block) -> bool {
+    let mac = match block.stmts.as_slice() {
+        [Stmt::Macro(stmt)] => &stmt.mac,
+        [Stmt::Expr(Expr::Macro(expr), _)] => &expr.mac,
+        _ => return false,
+    };
+
+    mac.path
+        .segments
+        .last()
+        .map(|s| UNIMPLEMENTED_MACROS.contains(&s.ident.to_string().as_str()))
+        .unwrap_or(false)
+}
@@ -170,3 +170,31 @@ fn validate_add_from_path() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn replaces_an_existing_dependency_with_force() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["dependencies"]["foo:bar"] = value("1.2.3");
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("add --path foo/bar foo:bar")
+        .assert()
+        .stderr(contains("use `--force` to replace it"))
+        .failure();
+
+    project
+        .cargo_component("add --force --path foo/bar foo:bar")
+        .assert()
+        .stderr(contains("Added dependency `foo:bar` from path `foo/bar`"))
+        .success();
+
+    let manifest = fs::read_to_string(project.root().join("Cargo.toml"))?;
+    assert!(contains(r#""foo:bar" = { path = "foo/bar" }"#).eval(&manifest));
+    assert!(!contains("1.2.3").eval(&manifest));
+
+    Ok(())
+}
@@ -0,0 +1,172 @@
+use crate::support::*;
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use predicates::{prelude::PredicateBooleanExt, str::contains};
+use std::fs;
+use toml_edit::{value, Array};
+
+mod support;
+
+#[test]
+fn it_prints_the_generated_bindings() -> Result<()> {
+    let project = Project::new("foo")?;
+
+    project
+        .cargo_component("bindings")
+        .assert()
+        .stdout(contains("pub trait Guest").and(contains("fn hello_world()")))
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn it_writes_the_generated_bindings_to_a_file() -> Result<()> {
+    let project = Project::new("foo")?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "package component:foo
+
+world example {
+    export iface: interface {
+        resource res {
+            constructor()
+        }
+    }
+}",
+    )?;
+
+    let path = project.root().join("bindings.rs");
+    project
+        .cargo_component("bindings --out bindings.rs")
+        .assert()
+        .stderr(contains("Generated bindings for foo"))
+        .success();
+
+    let bindings = fs::read_to_string(path)?;
+    assert!(bindings.contains("pub trait GuestRes"));
+    assert!(bindings.contains("super::Res"));
+
+    Ok(())
+}
+
+#[test]
+fn it_warns_that_manifest_bindings_options_are_not_applied() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        doc["package"]["metadata"]["component"]["bindings"]["ownership"] = value("borrowing");
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("bindings")
+        .assert()
+        .stdout(contains("pub trait Guest"))
+        .stderr(contains(
+            "are not applied to the printed bindings; use `cargo component expand`",
+        ))
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn it_prints_the_bindings_of_an_enabled_feature_world() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["features"]["full"] = value(Array::new());
+        doc["package"]["metadata"]["component"]["target"]["world"] = value("example");
+        doc["package"]["metadata"]["component"]["features"]["full"]["world"] = value("full");
+        Ok(doc)
+    })?;
+
+    fs::write(
+        project.root().join("wit/full.wit"),
+        "world full {
+    export hello-world: func() -> string
+    export goodbye-world: func() -> string
+}",
+    )?;
+
+    project
+        .cargo_component("bindings")
+        .assert()
+        .stdout(contains("fn hello_world()").and(contains("fn goodbye_world()").not()))
+        .success();
+
+    project
+        .cargo_component("bindings --features full")
+        .assert()
+        .stdout(contains("fn hello_world()").and(contains("fn goodbye_world()")))
+        .success();
+
+    project
+        .cargo_component("bindings --all-features")
+        .assert()
+        .stdout(contains("fn goodbye_world()"))
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn it_prints_the_bindings_of_a_workspace() -> Result<()> {
+    let project = project()?
+        .file(
+            "Cargo.toml",
+            r#"[workspace]
+members = ["foo", "bar", "baz"]
+"#,
+        )?
+        .file(
+            "baz/Cargo.toml",
+            r#"[package]
+name = "baz"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+"#,
+        )?
+        .file("baz/src/lib.rs", "")?
+        .build();
+
+    project
+        .cargo_component("new --reactor foo")
+        .assert()
+        .stderr(contains("Updated manifest of package `foo`"))
+        .success();
+
+    project
+        .cargo_component("new --reactor bar")
+        .assert()
+        .stderr(contains("Updated manifest of package `bar`"))
+        .success();
+
+    fs::write(
+        project.root().join("bar/wit/world.wit"),
+        "package component:bar
+
+world example {
+    export goodbye-world: func() -> string
+}",
+    )?;
+
+    project
+        .cargo_component("bindings --workspace")
+        .assert()
+        .stdout(contains("fn hello_world()").and(contains("fn goodbye_world()")))
+        .success();
+
+    project
+        .cargo_component("bindings -p foo -p bar --out bindings.rs")
+        .assert()
+        .stderr(contains(
+            "`--out` cannot be used when generating the bindings of multiple packages",
+        ))
+        .failure();
+
+    Ok(())
+}
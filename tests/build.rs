@@ -1,9 +1,12 @@
 use crate::support::*;
 use anyhow::{Context, Result};
 use assert_cmd::prelude::*;
-use predicates::{prelude::PredicateBooleanExt, str::contains};
+use predicates::{
+    prelude::PredicateBooleanExt,
+    str::{contains, is_match},
+};
 use std::fs;
-use toml_edit::{value, Item, Table};
+use toml_edit::{value, Array, InlineTable, Item, Table};
 
 mod support;
 
@@ -49,6 +52,25 @@ fn it_builds_a_bin_project() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn it_builds_with_a_toolchain_override() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("+stable build")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    Ok(())
+}
+
 #[test]
 fn it_builds_a_workspace() -> Result<()> {
     let project = project()?
@@ -219,6 +241,179 @@ fn it_regenerates_target_if_wit_changed() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn it_selects_a_fully_qualified_target_world() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    fs::write(
+        project.root().join("wit/other.wit"),
+        "world other { export hello-world: func() -> string }",
+    )?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(
+            contains("package `component:foo` contains multiple worlds")
+                .and(contains("`component:foo/example`"))
+                .and(contains("`component:foo/other`")),
+        )
+        .failure();
+
+    project.update_manifest(|mut doc| {
+        doc["package"]["metadata"]["component"]["target"]["world"] = value("component:foo/missing");
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains(
+            "world `missing` was not found in package `component:foo`",
+        ))
+        .failure();
+
+    project.update_manifest(|mut doc| {
+        doc["package"]["metadata"]["component"]["target"]["world"] = value("component:foo/other");
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    project.update_manifest(|mut doc| {
+        doc["package"]["metadata"]["component"]["target"]["world"] = value("component:foo/example");
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build --explain-encoding")
+        .assert()
+        .stderr(contains(
+            "because the target world changed from `component:foo/other` to `component:foo/example`",
+        ))
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn it_selects_the_target_world_of_an_enabled_feature() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["features"]["full"] = value(Array::new());
+        doc["package"]["metadata"]["component"]["target"]["world"] = value("example");
+        doc["package"]["metadata"]["component"]["features"]["full"]["world"] = value("full");
+        Ok(doc)
+    })?;
+
+    fs::write(
+        project.root().join("wit/full.wit"),
+        "world full {
+    export hello-world: func() -> string
+    export goodbye-world: func() -> string
+}",
+    )?;
+
+    fs::write(
+        project.root().join("src/lib.rs"),
+        r#"cargo_component_bindings::generate!();
+use bindings::Guest;
+
+struct Component;
+
+impl Guest for Component {
+    fn hello_world() -> String {
+        "Hello, World!".to_string()
+    }
+
+    #[cfg(feature = "full")]
+    fn goodbye_world() -> String {
+        "Goodbye, World!".to_string()
+    }
+}
+"#,
+    )?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    project
+        .cargo_component("build --features full")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    project.update_manifest(|mut doc| {
+        doc["package"]["metadata"]["component"]["features"]["full"]["world"] = value("missing");
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build --features full")
+        .assert()
+        .stderr(contains(
+            "world `missing` was not found in package `component:foo`",
+        ))
+        .failure();
+
+    Ok(())
+}
+
+#[test]
+fn it_explains_why_a_target_is_encoded() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build --explain-encoding")
+        .assert()
+        .stderr(contains("because the target has not been encoded yet"))
+        .success();
+
+    project
+        .cargo_component("build --explain-encoding")
+        .assert()
+        .stderr(contains("target for package `foo` is up-to-date"))
+        .success();
+
+    let path = project.root().join("wit/world.wit");
+    let mut contents = fs::read_to_string(&path)?;
+    contents.push_str("\n// changed\n");
+    fs::write(&path, contents)?;
+
+    project
+        .cargo_component("build --explain-encoding")
+        .assert()
+        .stderr(contains(format!(
+            "because the target source file `{path}` was modified",
+            path = path.display()
+        )))
+        .success();
+
+    Ok(())
+}
+
 #[test]
 fn it_builds_with_local_wit_deps() -> Result<()> {
     let project = Project::new("foo")?;
@@ -310,29 +505,73 @@ impl Qux for Component {
 }
 
 #[test]
-fn it_builds_with_a_specified_implementor() -> Result<()> {
+fn it_builds_with_a_local_wit_dep_directory_tree() -> Result<()> {
     let project = Project::new("foo")?;
     project.update_manifest(|mut doc| {
         redirect_bindings_crate(&mut doc);
+        let mut dependencies = Table::new();
+        dependencies["foo:bar"]["path"] = value("wit/deps/foo-bar");
+
+        let target =
+            doc["package"]["metadata"]["component"]["target"].or_insert(Item::Table(Table::new()));
+        target["dependencies"] = Item::Table(dependencies);
         Ok(doc)
     })?;
 
+    // The transitive packages are only found in the `deps` tree of `foo:bar`
+    fs::create_dir_all(project.root().join("wit/deps/foo-bar/deps/baz-qux/deps"))?;
     fs::write(
-        project.root().join("src/lib.rs"),
-        r#"cargo_component_bindings::generate!({
-    implementor: CustomImplementor
-});
+        project
+            .root()
+            .join("wit/deps/foo-bar/deps/baz-qux/deps/qux-quux.wit"),
+        "package qux:quux
 
-use bindings::Guest;
+interface types {
+    type ty = u32
+}",
+    )?;
 
-struct CustomImplementor;
+    fs::write(
+        project.root().join("wit/deps/foo-bar/deps/baz-qux/qux.wit"),
+        "package baz:qux
 
-impl Guest for CustomImplementor {
-    fn hello_world() -> String {
+interface qux {
+    use qux:quux/types.{ty}
+}",
+    )?;
+
+    fs::write(
+        project.root().join("wit/deps/foo-bar/bar.wit"),
+        "package foo:bar
+
+interface baz {
+    use baz:qux/qux.{ty}
+    baz: func() -> ty
+}",
+    )?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "package component:foo
+
+world example {
+    export foo:bar/baz
+}",
+    )?;
+
+    fs::write(
+        project.root().join("src/lib.rs"),
+        "cargo_component_bindings::generate!();
+use bindings::exports::foo::bar::baz::{Guest, Ty};
+
+struct Component;
+
+impl Guest for Component {
+    fn baz() -> Ty {
         todo!()
     }
 }
-"#,
+",
     )?;
 
     project
@@ -343,55 +582,282 @@ impl Guest for CustomImplementor {
 
     validate_component(&project.debug_wasm("foo"))?;
 
+    // A package missing from the tree is named in the error
+    fs::remove_file(
+        project
+            .root()
+            .join("wit/deps/foo-bar/deps/baz-qux/deps/qux-quux.wit"),
+    )?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains(
+            "package `qux:quux` used by package `baz:qux` of target dependency `foo:bar` was not found",
+        ))
+        .failure();
+
     Ok(())
 }
 
 #[test]
-fn empty_world_with_dep_valid() -> Result<()> {
-    let project = Project::new("dep")?;
+fn it_builds_with_a_renamed_local_wit_dep() -> Result<()> {
+    let project = Project::new("foo")?;
     project.update_manifest(|mut doc| {
         redirect_bindings_crate(&mut doc);
+        let mut dependencies = Table::new();
+        dependencies["my:alias"]["path"] = value("wit/deps/vendored.wit");
+        dependencies["my:alias"]["package"] = value("their:name");
+
+        let target =
+            doc["package"]["metadata"]["component"]["target"].or_insert(Item::Table(Table::new()));
+        target["dependencies"] = Item::Table(dependencies);
         Ok(doc)
     })?;
 
+    fs::create_dir_all(project.root().join("wit/deps"))?;
     fs::write(
-        project.root().join("wit/world.wit"),
-        "
-            package foo:bar
+        project.root().join("wit/deps/vendored.wit"),
+        "package their:name
 
-            world the-world {
-                flags foo {
-                    bar
-                }
+interface iface {
+    greet: func() -> string
+}",
+    )?;
 
-                export hello: func() -> foo
-            }
-        ",
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "package component:foo
+
+world example {
+    export my:alias/iface
+}",
     )?;
 
     fs::write(
         project.root().join("src/lib.rs"),
-        "
-            cargo_component_bindings::generate!();
-            use bindings::{Guest, Foo};
-            struct Component;
+        r#"cargo_component_bindings::generate!();
+use bindings::exports::my::alias::iface::Guest;
 
-            impl Guest for Component {
-                fn hello() -> Foo {
-                    Foo::BAR
-                }
-            }
-        ",
+struct Component;
+
+impl Guest for Component {
+    fn greet() -> String {
+        "Hello, World!".to_string()
+    }
+}
+"#,
     )?;
 
-    project.cargo_component("build").assert().success();
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
 
-    let dep = project.debug_wasm("dep");
-    validate_component(&dep)?;
+    validate_component(&project.debug_wasm("foo"))?;
 
-    let project = Project::with_root(project.root().parent().unwrap(), "main", "")?;
-    project.update_manifest(|mut doc| {
-        redirect_bindings_crate(&mut doc);
+    Ok(())
+}
+
+#[test]
+fn it_locks_local_path_dependencies() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        let mut dependencies = Table::new();
+        dependencies["foo:bar"]["path"] = value("wit/deps/foo-bar.wit");
+
+        let target =
+            doc["package"]["metadata"]["component"]["target"].or_insert(Item::Table(Table::new()));
+        target["dependencies"] = Item::Table(dependencies);
+        Ok(doc)
+    })?;
+
+    fs::create_dir_all(project.root().join("wit/deps"))?;
+    fs::write(
+        project.root().join("wit/deps/foo-bar.wit"),
+        "package foo:bar
+
+interface baz {
+    qux: func() -> string
+}",
+    )?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "package component:foo
+
+world example {
+    export foo:bar/baz
+}",
+    )?;
+
+    fs::write(
+        project.root().join("src/lib.rs"),
+        r#"cargo_component_bindings::generate!();
+use bindings::exports::foo::bar::baz::Guest;
+
+struct Component;
+
+impl Guest for Component {
+    fn qux() -> String {
+        "Hello, World!".to_string()
+    }
+}
+"#,
+    )?;
+
+    // Path dependencies alone create a lock file
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    let lock_path = project.root().join("Cargo-component.lock");
+    let lock_file = fs::read_to_string(&lock_path)?;
+    assert!(lock_file.contains("[[path]]"));
+    assert!(lock_file.contains(r#"id = "foo:bar""#));
+    assert!(lock_file.contains(r#"path = "wit/deps/foo-bar.wit""#));
+    assert!(lock_file.contains(r#"digest = "sha256:"#));
+
+    // Change the dependency's contents without changing its interface
+    fs::write(
+        project.root().join("wit/deps/foo-bar.wit"),
+        "package foo:bar
+
+/// A documented interface.
+interface baz {
+    qux: func() -> string
+}",
+    )?;
+
+    // A changed path dependency does not require updating a locked build
+    project
+        .cargo_component("build --locked")
+        .assert()
+        .stderr(contains(
+            "path dependency `foo:bar` at `wit/deps/foo-bar.wit` has changed since it was locked",
+        ))
+        .success();
+
+    // The build keeps warning until the dependency is locked again
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(
+            contains(
+                "path dependency `foo:bar` at `wit/deps/foo-bar.wit` has changed since it was locked; \
+                 run `cargo component update` to lock its current contents",
+            )
+            .and(contains("Finished dev [unoptimized + debuginfo] target(s)")),
+        )
+        .success();
+    assert_eq!(fs::read_to_string(&lock_path)?, lock_file);
+
+    project
+        .cargo_component("update")
+        .assert()
+        .stderr(contains(
+            "Updating path dependency `foo:bar` at `wit/deps/foo-bar.wit`",
+        ))
+        .success();
+    assert_ne!(fs::read_to_string(&lock_path)?, lock_file);
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("has changed since it was locked").not())
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn it_builds_with_a_specified_implementor() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    fs::write(
+        project.root().join("src/lib.rs"),
+        r#"cargo_component_bindings::generate!({
+    implementor: CustomImplementor
+});
+
+use bindings::Guest;
+
+struct CustomImplementor;
+
+impl Guest for CustomImplementor {
+    fn hello_world() -> String {
+        todo!()
+    }
+}
+"#,
+    )?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    Ok(())
+}
+
+#[test]
+fn empty_world_with_dep_valid() -> Result<()> {
+    let project = Project::new("dep")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["package"] = value("foo:bar");
+        Ok(doc)
+    })?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "
+            package foo:bar
+
+            world the-world {
+                flags foo {
+                    bar
+                }
+
+                export hello: func() -> foo
+            }
+        ",
+    )?;
+
+    fs::write(
+        project.root().join("src/lib.rs"),
+        "
+            cargo_component_bindings::generate!();
+            use bindings::{Guest, Foo};
+            struct Component;
+
+            impl Guest for Component {
+                fn hello() -> Foo {
+                    Foo::BAR
+                }
+            }
+        ",
+    )?;
+
+    project.cargo_component("build").assert().success();
+
+    let dep = project.debug_wasm("dep");
+    validate_component(&dep)?;
+
+    let project = Project::with_root(project.root().parent().unwrap(), "main", "")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
         let table = doc["package"]["metadata"]["component"]
             .as_table_mut()
             .unwrap();
@@ -423,11 +889,38 @@ fn empty_world_with_dep_valid() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn it_fails_with_a_mismatched_wit_package_name() -> Result<()> {
+    let project = Project::new("foo")?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "
+            package foo:bar
+
+            world example {
+                export hello: func() -> string
+            }
+        ",
+    )?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains(
+            "component package name `component:foo` does not match WIT package `foo:bar`",
+        ))
+        .failure();
+
+    Ok(())
+}
+
 #[test]
 fn it_builds_with_resources() -> Result<()> {
     let project = Project::new("foo")?;
     project.update_manifest(|mut doc| {
         redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["package"] = value("foo:bar");
         Ok(doc)
     })?;
 
@@ -487,7 +980,7 @@ fn it_builds_with_resources() -> Result<()> {
 }
 
 #[test]
-fn it_builds_with_resources_with_custom_implementor() -> Result<()> {
+fn it_builds_with_empty_results() -> Result<()> {
     let project = Project::new("foo")?;
     project.update_manifest(|mut doc| {
         redirect_bindings_crate(&mut doc);
@@ -496,69 +989,58 @@ fn it_builds_with_resources_with_custom_implementor() -> Result<()> {
 
     fs::write(
         project.root().join("wit/world.wit"),
-        "
-            package foo:bar
+        "package component:foo
 
-            world bar {
-                export baz: interface {
-                    resource keyed-integer {
-                        constructor(x: u32)
-                        get: func() -> u32
-                        set: func(x: u32)
-                        key: static func() -> string
-                    }
-                }
-            }
-        ",
+world example {
+    export nothing: func()
+    export empty-result: func() -> result
+    export empty-ok: func() -> result<_, string>
+    export empty-err: func() -> result<u32>
+}",
     )?;
 
     fs::write(
         project.root().join("src/lib.rs"),
-        r#"
-            cargo_component_bindings::generate!({
-                resources: {
-                    "baz/keyed-integer": MyKeyedInteger
-                }
-            });
-
-            use std::cell::Cell;
-            use bindings::exports::baz::GuestKeyedInteger;
+        r#"cargo_component_bindings::generate!();
+use bindings::Guest;
 
-            pub struct MyKeyedInteger(Cell<u32>);
+struct Component;
 
-            impl GuestKeyedInteger for MyKeyedInteger {
-                fn new(x: u32) -> Self {
-                    Self(Cell::new(x))
-                }
+impl Guest for Component {
+    fn nothing() {}
 
-                fn get(&self) -> u32 {
-                    self.0.get()
-                }
+    fn empty_result() -> Result<(), ()> {
+        Ok(())
+    }
 
-                fn set(&self, x: u32) {
-                    self.0.set(x);
-                }
+    fn empty_ok() -> Result<(), String> {
+        Err("error".to_string())
+    }
 
-                fn key() -> String {
-                    "my-key".to_string()
-                }
-            }
-        "#,
+    fn empty_err() -> Result<u32, ()> {
+        Ok(1)
+    }
+}
+"#,
     )?;
 
-    project.cargo_component("build").assert().success();
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
 
-    let dep = project.debug_wasm("foo");
-    validate_component(&dep)?;
+    validate_component(&project.debug_wasm("foo"))?;
 
     Ok(())
 }
 
 #[test]
-fn it_builds_resources_with_specified_ownership_model() -> Result<()> {
+fn it_builds_with_borrowed_imported_resources() -> Result<()> {
     let project = Project::new("foo")?;
     project.update_manifest(|mut doc| {
         redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["package"] = value("foo:bar");
         Ok(doc)
     })?;
 
@@ -568,14 +1050,15 @@ fn it_builds_resources_with_specified_ownership_model() -> Result<()> {
             package foo:bar
 
             world bar {
-                export baz: interface {
+                import baz: interface {
                     resource keyed-integer {
                         constructor(x: u32)
-                        get: func() -> u32
-                        set: func(x: u32)
-                        key: static func() -> string
                     }
+
+                    describe: func(x: borrow<keyed-integer>) -> string
                 }
+
+                export describe-twice: func() -> string
             }
         ",
     )?;
@@ -583,17 +1066,226 @@ fn it_builds_resources_with_specified_ownership_model() -> Result<()> {
     fs::write(
         project.root().join("src/lib.rs"),
         r#"
-            cargo_component_bindings::generate!({
-                ownership: "borrowing-duplicate-if-necessary"
-            });
+            cargo_component_bindings::generate!();
 
-            use std::cell::Cell;
+            use bindings::{baz::{describe, KeyedInteger}, Guest};
 
-            pub struct KeyedInteger(Cell<u32>);
+            struct Component;
 
-            impl bindings::exports::baz::GuestKeyedInteger for KeyedInteger {
-                fn new(x: u32) -> Self {
-                    Self(Cell::new(x))
+            impl Guest for Component {
+                fn describe_twice() -> String {
+                    // Borrowing the handle does not transfer ownership
+                    let x = KeyedInteger::new(42);
+                    format!("{} {}", describe(&x), describe(&x))
+                }
+            }
+        "#,
+    )?;
+
+    project.cargo_component("build").assert().success();
+
+    let dep = project.debug_wasm("foo");
+    validate_component(&dep)?;
+
+    Ok(())
+}
+
+#[test]
+fn it_builds_with_resources_with_custom_implementor() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["package"] = value("foo:bar");
+        Ok(doc)
+    })?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "
+            package foo:bar
+
+            world bar {
+                export baz: interface {
+                    resource keyed-integer {
+                        constructor(x: u32)
+                        get: func() -> u32
+                        set: func(x: u32)
+                        key: static func() -> string
+                    }
+                }
+            }
+        ",
+    )?;
+
+    fs::write(
+        project.root().join("src/lib.rs"),
+        r#"
+            cargo_component_bindings::generate!({
+                resources: {
+                    "baz/keyed-integer": MyKeyedInteger
+                }
+            });
+
+            use std::cell::Cell;
+            use bindings::exports::baz::GuestKeyedInteger;
+
+            pub struct MyKeyedInteger(Cell<u32>);
+
+            impl GuestKeyedInteger for MyKeyedInteger {
+                fn new(x: u32) -> Self {
+                    Self(Cell::new(x))
+                }
+
+                fn get(&self) -> u32 {
+                    self.0.get()
+                }
+
+                fn set(&self, x: u32) {
+                    self.0.set(x);
+                }
+
+                fn key() -> String {
+                    "my-key".to_string()
+                }
+            }
+        "#,
+    )?;
+
+    project.cargo_component("build").assert().success();
+
+    let dep = project.debug_wasm("foo");
+    validate_component(&dep)?;
+
+    Ok(())
+}
+
+#[test]
+fn it_rejects_an_unknown_resource_mapping() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["package"] = value("foo:bar");
+        Ok(doc)
+    })?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "
+            package foo:bar
+
+            world bar {
+                export baz: interface {
+                    resource keyed-integer {
+                        constructor(x: u32)
+                    }
+                }
+            }
+        ",
+    )?;
+
+    fs::write(
+        project.root().join("src/lib.rs"),
+        r#"
+            cargo_component_bindings::generate!({
+                resources: {
+                    "baz/keyed-int": MyKeyedInteger
+                }
+            });
+        "#,
+    )?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains(
+            "resource `baz/keyed-int` is not exported by the target world `bar`; the exported resources are: `baz/keyed-integer`",
+        ))
+        .failure();
+
+    Ok(())
+}
+
+#[test]
+fn it_rejects_an_unused_implementor() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["package"] = value("foo:bar");
+        Ok(doc)
+    })?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "
+            package foo:bar
+
+            world bar {
+                import hello: func() -> string
+            }
+        ",
+    )?;
+
+    fs::write(
+        project.root().join("src/lib.rs"),
+        r#"
+            cargo_component_bindings::generate!({
+                implementor: MyComponent,
+            });
+        "#,
+    )?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains(
+            "implementor `MyComponent` is not used as the target world `bar` does not export any functions or interfaces",
+        ))
+        .failure();
+
+    Ok(())
+}
+
+#[test]
+fn it_builds_resources_with_specified_ownership_model() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["package"] = value("foo:bar");
+        Ok(doc)
+    })?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "
+            package foo:bar
+
+            world bar {
+                export baz: interface {
+                    resource keyed-integer {
+                        constructor(x: u32)
+                        get: func() -> u32
+                        set: func(x: u32)
+                        key: static func() -> string
+                    }
+                }
+            }
+        ",
+    )?;
+
+    fs::write(
+        project.root().join("src/lib.rs"),
+        r#"
+            cargo_component_bindings::generate!({
+                ownership: "borrowing-duplicate-if-necessary"
+            });
+
+            use std::cell::Cell;
+
+            pub struct KeyedInteger(Cell<u32>);
+
+            impl bindings::exports::baz::GuestKeyedInteger for KeyedInteger {
+                fn new(x: u32) -> Self {
+                    Self(Cell::new(x))
                 }
 
                 fn get(&self) -> u32 {
@@ -604,155 +1296,3326 @@ fn it_builds_resources_with_specified_ownership_model() -> Result<()> {
                     self.0.set(x);
                 }
 
-                fn key() -> String {
-                    "my-key".to_string()
-                }
-            }
-        "#,
+                fn key() -> String {
+                    "my-key".to_string()
+                }
+            }
+        "#,
+    )?;
+
+    project.cargo_component("build").assert().success();
+
+    let dep = project.debug_wasm("foo");
+    validate_component(&dep)?;
+
+    Ok(())
+}
+
+#[test]
+fn it_builds_with_additional_derives() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["package"] = value("foo:bar");
+        Ok(doc)
+    })?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "
+            package foo:bar
+
+            world bar {
+                export baz: interface {
+                    record seed {
+                        value: u32,
+                    }
+
+                    variant shape {
+                        circle(u32),
+                        square(u32),
+                    }
+
+                    hash: func(seed: seed, shape: shape) -> u64
+                }
+            }
+        ",
+    )?;
+
+    // The record and variant are hashable only with the derive
+    fs::write(
+        project.root().join("src/lib.rs"),
+        r#"
+            cargo_component_bindings::generate!({
+                additional_derives: [Hash]
+            });
+
+            use bindings::exports::baz::{Guest, Seed, Shape};
+            use std::{collections::hash_map::DefaultHasher, hash::{Hash, Hasher}};
+
+            struct Component;
+
+            impl Guest for Component {
+                fn hash(seed: Seed, shape: Shape) -> u64 {
+                    let mut hasher = DefaultHasher::new();
+                    seed.hash(&mut hasher);
+                    shape.hash(&mut hasher);
+                    hasher.finish()
+                }
+            }
+        "#,
+    )?;
+
+    project.cargo_component("build").assert().success();
+    validate_component(&project.debug_wasm("foo"))?;
+
+    Ok(())
+}
+
+#[test]
+fn it_builds_with_only_included_interfaces() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["package"] = value("foo:bar");
+        Ok(doc)
+    })?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "
+            package foo:bar
+
+            interface types {
+                record key {
+                    id: u32,
+                }
+            }
+
+            interface keyed-integer {
+                use types.{key}
+                get: func(key: key) -> s64
+            }
+
+            interface other {
+                run: func()
+            }
+
+            world bar {
+                export keyed-integer
+                export other
+            }
+        ",
+    )?;
+
+    // Only the included interface is implemented; the `types` interface is
+    // kept as its type is used by the included interface
+    fs::write(
+        project.root().join("src/lib.rs"),
+        r#"
+            cargo_component_bindings::generate!({
+                include: ["foo:bar/keyed-integer"]
+            });
+
+            use bindings::exports::foo::bar::keyed_integer::{Guest, Key};
+
+            struct Component;
+
+            impl Guest for Component {
+                fn get(key: Key) -> i64 {
+                    key.id.into()
+                }
+            }
+        "#,
+    )?;
+
+    project.cargo_component("build").assert().success();
+    validate_component(&project.debug_wasm("foo"))?;
+
+    fs::write(
+        project.root().join("src/lib.rs"),
+        r#"
+            cargo_component_bindings::generate!({
+                include: ["foo:bar/missing"]
+            });
+        "#,
+    )?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains(
+            "interface `foo:bar/missing` is not imported or exported by the target world",
+        ))
+        .failure();
+
+    Ok(())
+}
+
+#[test]
+fn it_builds_borrowed_import_parameters_with_a_lifetime() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["package"] = value("foo:bar");
+        Ok(doc)
+    })?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "
+            package foo:bar
+
+            world bar {
+                import logger: interface {
+                    record entry {
+                        message: string,
+                        tags: list<string>,
+                    }
+
+                    log: func(entry: entry)
+                }
+
+                export run: func(message: string)
+            }
+        ",
+    )?;
+
+    // The entry borrows the message and tags rather than cloning them
+    fs::write(
+        project.root().join("src/lib.rs"),
+        r#"
+            cargo_component_bindings::generate!({
+                ownership: "borrowing"
+            });
+
+            use bindings::{logger::{self, Entry}, Guest};
+
+            struct Component;
+
+            impl Guest for Component {
+                fn run(message: String) {
+                    let tags: Vec<&str> = message.split(' ').collect();
+                    let entry: Entry<'_> = Entry {
+                        message: &message,
+                        tags: &tags,
+                    };
+                    logger::log(entry);
+                }
+            }
+        "#,
+    )?;
+
+    project.cargo_component("build").assert().success();
+
+    let dep = project.debug_wasm("foo");
+    validate_component(&dep)?;
+
+    Ok(())
+}
+
+#[test]
+fn it_builds_with_a_component_dependency() -> Result<()> {
+    let root = create_root()?;
+
+    let comp1 = Project::with_root(&root, "comp1", "")?;
+    comp1.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["package"] = value("my:comp1");
+        Ok(doc)
+    })?;
+
+    fs::write(
+        comp1.root().join("wit/world.wit"),
+        "
+package my:comp1
+
+interface types {
+    record seed {
+        value: u32,
+    }
+}
+
+world random-generator {
+    use types.{seed}
+    export rand: func(seed: seed) -> u32
+}
+",
+    )?;
+
+    fs::write(
+        comp1.root().join("src/lib.rs"),
+        r#"
+cargo_component_bindings::generate!();
+
+use bindings::{Guest, Seed};
+
+struct Component;
+
+impl Guest for Component {
+    fn rand(seed: Seed) -> u32 {
+        seed.value + 1
+    }
+}
+"#,
+    )?;
+
+    comp1
+        .cargo_component("build --release")
+        .assert()
+        .stderr(contains("Finished release [optimized] target(s)"))
+        .success();
+
+    let dep = comp1.release_wasm("comp1");
+    validate_component(&dep)?;
+
+    let comp2 = Project::with_root(&root, "comp2", "")?;
+    comp2.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["package"] = value("my:comp2");
+        doc["package"]["metadata"]["component"]["dependencies"]["my:comp1"]["path"] =
+            value(dep.display().to_string());
+        Ok(doc)
+    })?;
+
+    fs::write(
+        comp2.root().join("wit/world.wit"),
+        "
+package my:comp2
+
+world random-generator {
+    export rand: func() -> u32
+}
+",
+    )?;
+
+    fs::write(
+        comp2.root().join("src/lib.rs"),
+        r#"
+cargo_component_bindings::generate!();
+
+use bindings::{Guest, comp1};
+
+struct Component;
+
+impl Guest for Component {
+    fn rand() -> u32 {
+        comp1::rand(comp1::Seed { value: 1 })
+    }
+}
+"#,
+    )?;
+
+    comp2
+        .cargo_component("build --release")
+        .assert()
+        .stderr(contains("Finished release [optimized] target(s)"))
+        .success();
+
+    let path: std::path::PathBuf = comp2.release_wasm("comp2");
+    validate_component(&path)?;
+
+    Ok(())
+}
+
+#[test]
+fn it_builds_with_a_reexported_component_dependency_type() -> Result<()> {
+    let root = create_root()?;
+
+    let comp1 = Project::with_root(&root, "comp1", "")?;
+    comp1.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["package"] = value("my:comp1");
+        Ok(doc)
+    })?;
+
+    fs::write(
+        comp1.root().join("wit/world.wit"),
+        "
+package my:comp1
+
+interface types {
+    record seed {
+        value: u32,
+    }
+}
+
+world random-generator {
+    use types.{seed}
+    export rand: func(seed: seed) -> u32
+}
+",
+    )?;
+
+    fs::write(
+        comp1.root().join("src/lib.rs"),
+        r#"
+cargo_component_bindings::generate!();
+
+use bindings::{Guest, Seed};
+
+struct Component;
+
+impl Guest for Component {
+    fn rand(seed: Seed) -> u32 {
+        seed.value + 1
+    }
+}
+"#,
+    )?;
+
+    comp1
+        .cargo_component("build --release")
+        .assert()
+        .stderr(contains("Finished release [optimized] target(s)"))
+        .success();
+
+    let dep = comp1.release_wasm("comp1");
+    validate_component(&dep)?;
+
+    // The dependency is both a target dependency (for its types) and a
+    // component dependency (for its exports)
+    let comp2 = Project::with_root(&root, "comp2", "")?;
+    comp2.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["package"] = value("my:comp2");
+        doc["package"]["metadata"]["component"]["dependencies"]["my:comp1"]["path"] =
+            value(dep.display().to_string());
+
+        let mut dependencies = Table::new();
+        dependencies["my:comp1"]["path"] = value(dep.display().to_string());
+
+        let target =
+            doc["package"]["metadata"]["component"]["target"].or_insert(Item::Table(Table::new()));
+        target["dependencies"] = Item::Table(dependencies);
+        Ok(doc)
+    })?;
+
+    fs::write(
+        comp2.root().join("wit/world.wit"),
+        "
+package my:comp2
+
+interface generator {
+    use my:comp1/types.{seed}
+    rand: func(seed: seed) -> u32
+}
+
+world random-generator {
+    export generator
+}
+",
+    )?;
+
+    // The seed of the exported interface is passed to the dependency as-is,
+    // which only compiles if both refer to the same type
+    fs::write(
+        comp2.root().join("src/lib.rs"),
+        r#"
+cargo_component_bindings::generate!();
+
+use bindings::{comp1, exports::my::comp2::generator::{Guest, Seed}};
+
+struct Component;
+
+impl Guest for Component {
+    fn rand(seed: Seed) -> u32 {
+        comp1::rand(seed)
+    }
+}
+"#,
+    )?;
+
+    comp2
+        .cargo_component("build --release --dump-component-type component.wit")
+        .assert()
+        .stderr(contains("Finished release [optimized] target(s)"))
+        .success();
+
+    let path = comp2.release_wasm("comp2");
+    validate_component(&path)?;
+
+    // The dependency's type is used rather than redefined by the component
+    let path = comp2.root().join("component.wit");
+    let wit = fs::read_to_string(&path)
+        .with_context(|| format!("failed to read `{path}`", path = path.display()))?;
+    assert!(wit.contains("use my:comp1/types.{seed}"));
+    assert!(!wit.contains("record seed"));
+
+    Ok(())
+}
+
+#[test]
+fn it_builds_for_the_wasip1_target() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build --target wasm32-wasip1")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    let path = project
+        .build_dir()
+        .join("wasm32-wasip1")
+        .join("debug")
+        .join("foo.wasm");
+    validate_component(&path)?;
+    assert!(!project.debug_wasm("foo").exists());
+
+    project
+        .cargo_component("wit --built --target wasm32-wasip1")
+        .assert()
+        .stdout(contains("package root:component"))
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn it_rejects_an_unsupported_wasm_target() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build --target wasm32-wasip2")
+        .assert()
+        .stderr(contains(
+            "WebAssembly target `wasm32-wasip2` is not supported; supported targets are `wasm32-wasi`, `wasm32-wasip1`, `wasm32-unknown-unknown`",
+        ))
+        .failure();
+
+    Ok(())
+}
+
+#[test]
+fn it_builds_with_adapter() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["adapter"] = value("not-a-valid-path");
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("error: failed to read module adapter"))
+        .failure();
+
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["adapter"] = value(format!(
+            "../../../../../adapters/{version}/wasi_snapshot_preview1.reactor.wasm",
+            version = env!("WASI_ADAPTER_VERSION")
+        ));
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    Ok(())
+}
+
+#[test]
+fn it_selects_the_adapter_by_profile() -> Result<()> {
+    let adapter = format!(
+        "../../../../../adapters/{version}/wasi_snapshot_preview1.reactor.wasm",
+        version = env!("WASI_ADAPTER_VERSION")
+    );
+
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        let mut profiles = Table::new();
+        profiles["dev"] = value("not-a-valid-path");
+        profiles["default"] = value(adapter.as_str());
+        doc["package"]["metadata"]["component"]["adapter"]["profiles"] = Item::Table(profiles);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("error: failed to read module adapter"))
+        .failure();
+
+    // The release profile has no entry and uses the default adapter
+    project
+        .cargo_component("build --release")
+        .assert()
+        .stderr(contains("Finished release [optimized] target(s)"))
+        .success();
+
+    validate_component(&project.release_wasm("foo"))?;
+
+    project.update_manifest(|mut doc| {
+        doc["package"]["metadata"]["component"]["adapter"]["profiles"]["dev"] =
+            value(adapter.as_str());
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    Ok(())
+}
+
+#[test]
+fn it_renders_short_message_format_errors() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "package component:foo
+
+world example {
+    export hello-world: func() -> not-a-type
+}",
+    )?;
+
+    let output = project
+        .cargo_component("build --message-format=short")
+        .output()?;
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8(output.stderr)?;
+    let lines: Vec<_> = stderr.lines().filter(|l| l.contains("error")).collect();
+    assert_eq!(lines.len(), 1, "expected a single error line: {stderr}");
+    assert!(
+        lines[0].contains("world.wit:4:"),
+        "unexpected error line: {stderr}"
+    );
+    assert!(
+        lines[0].contains(": error: "),
+        "unexpected error line: {stderr}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn it_reports_the_wit_bindgen_version() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build -v")
+        .assert()
+        .stderr(contains(
+            "bindings for package `foo` are generated with wit-bindgen v",
+        ))
+        .success();
+
+    project.update_manifest(|mut doc| {
+        doc["package"]["metadata"]["component"]["wit-bindgen"] = value("=0.0.1");
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains(
+            "package `foo` requires wit-bindgen version `=0.0.1`",
+        ))
+        .failure();
+
+    Ok(())
+}
+
+#[test]
+fn it_builds_with_a_target_from_stdin() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["target"]["path"] = value("-");
+        Ok(doc)
+    })?;
+
+    fs::remove_dir_all(project.root().join("wit"))?;
+
+    assert_cmd::Command::from_std(project.cargo_component("build"))
+        .write_stdin(
+            "package component:foo
+
+world example {
+    export hello-world: func() -> string
+}",
+        )
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    Ok(())
+}
+
+#[test]
+fn it_filters_producers_fields() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["producers"]["processed-by"] = value(false);
+        doc["package"]["metadata"]["component"]["producers"]["language"] = value(true);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build --release")
+        .assert()
+        .stderr(contains("Finished release [optimized] target(s)"))
+        .success();
+
+    let path = project.release_wasm("foo");
+
+    validate_component(&path)?;
+
+    let wasm = fs::read(&path)
+        .with_context(|| format!("failed to read wasm file `{path}`", path = path.display()))?;
+    let section = wasm_metadata::Producers::from_wasm(&wasm)?.expect("missing producers section");
+
+    assert!(section
+        .get("processed-by")
+        .map(|f| f.get(env!("CARGO_PKG_NAME")).is_none())
+        .unwrap_or(true));
+    assert!(section
+        .get("language")
+        .expect("missing language field")
+        .get("Rust")
+        .is_some());
+
+    Ok(())
+}
+
+#[test]
+fn it_builds_with_the_linker_wrapper() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    // Generate the bindings for the target world
+    project
+        .cargo_component("check")
+        .assert()
+        .stderr(contains("Checking foo v0.1.0"))
+        .success();
+
+    project.file(
+        ".cargo/config.toml",
+        &format!(
+            "[target.wasm32-wasi]\nlinker = {linker:?}\n",
+            linker = bin_path("cargo-component-ld").display().to_string()
+        ),
+    )?;
+
+    let status = std::process::Command::new("cargo")
+        .args(["build", "--target", "wasm32-wasi"])
+        .current_dir(project.root())
+        .status()?;
+    assert!(status.success());
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    Ok(())
+}
+
+#[test]
+fn it_selects_the_target_adapter_with_the_linker_wrapper() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    // Generate the bindings for the target world
+    project
+        .cargo_component("check")
+        .assert()
+        .stderr(contains("Checking foo v0.1.0"))
+        .success();
+
+    project.update_manifest(|mut doc| {
+        doc["package"]["metadata"]["component"]["adapter"]["targets"]["wasm32-wasi"] =
+            value("missing-adapter.wasm");
+        Ok(doc)
+    })?;
+
+    project.file(
+        ".cargo/config.toml",
+        &format!(
+            "[target.wasm32-wasi]\nlinker = {linker:?}\n",
+            linker = bin_path("cargo-component-ld").display().to_string()
+        ),
+    )?;
+
+    // The adapter for the target is used, so the missing adapter is an error
+    let output = std::process::Command::new("cargo")
+        .args(["build", "--target", "wasm32-wasi"])
+        .current_dir(project.root())
+        .output()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("missing-adapter.wasm"));
+
+    Ok(())
+}
+
+#[test]
+fn it_fails_with_a_forbidden_import() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        let mut forbidden = toml_edit::Array::new();
+        forbidden.push("wasi:random/insecure");
+        doc["package"]["metadata"]["component"]["forbidden-imports"] = value(forbidden);
+
+        let mut dependencies = Table::new();
+        dependencies["wasi:random"]["path"] = value("wit/deps/random");
+
+        let target =
+            doc["package"]["metadata"]["component"]["target"].or_insert(Item::Table(Table::new()));
+        target["dependencies"] = Item::Table(dependencies);
+        Ok(doc)
+    })?;
+
+    fs::create_dir_all(project.root().join("wit/deps/random"))?;
+    fs::write(
+        project.root().join("wit/deps/random/insecure.wit"),
+        "package wasi:random
+
+interface insecure {
+    get-insecure-random-u64: func() -> u64
+}",
+    )?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "package component:foo
+
+world example {
+    import wasi:random/insecure
+
+    export hello-world: func() -> string
+}",
+    )?;
+
+    fs::write(
+        project.root().join("src/lib.rs"),
+        r#"cargo_component_bindings::generate!();
+
+use bindings::{wasi::random::insecure::get_insecure_random_u64, Guest};
+
+struct Component;
+
+impl Guest for Component {
+    fn hello_world() -> String {
+        format!("Hello, {}!", get_insecure_random_u64())
+    }
+}
+"#,
+    )?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains(
+            "imports `wasi:random/insecure`, which is forbidden by `wasi:random/insecure`",
+        ))
+        .failure();
+
+    Ok(())
+}
+
+#[test]
+fn it_respects_the_color_option() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build --color never")
+        .assert()
+        .stderr(contains("Creating component").and(contains("\u{1b}[").not()))
+        .success();
+
+    project
+        .cargo_component("build --color always")
+        .assert()
+        .stderr(contains("\u{1b}["))
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn it_builds_targets_with_different_worlds() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["target"]["world"] = value("library");
+        doc["package"]["metadata"]["component"]["target-worlds"]["foo-cli"] = value("cli");
+        Ok(doc)
+    })?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "package component:foo
+
+world library {
+    export hello-world: func() -> string
+}
+
+world cli {
+    import get-name: func() -> string
+}",
+    )?;
+
+    fs::create_dir_all(project.root().join("src/bin"))?;
+    fs::write(
+        project.root().join("src/bin/foo-cli.rs"),
+        r#"cargo_component_bindings::generate!();
+
+fn main() {
+    println!("Hello, {}!", bindings::get_name());
+}
+"#,
+    )?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+    validate_component(&project.debug_wasm("foo-cli"))?;
+
+    Ok(())
+}
+
+#[test]
+fn it_builds_with_raw_strings() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "package component:foo
+
+world example {
+    export echo: func(s: string) -> string
+}",
+    )?;
+
+    fs::write(
+        project.root().join("src/lib.rs"),
+        r#"cargo_component_bindings::generate!({
+    raw_strings: true
+});
+
+use bindings::Guest;
+
+struct Component;
+
+impl Guest for Component {
+    fn echo(s: Vec<u8>) -> Vec<u8> {
+        s
+    }
+}
+"#,
+    )?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn it_uses_a_custom_cache_dir() -> Result<()> {
+    let root = create_root()?;
+    let (_server, config) = spawn_server(&root).await?;
+    config.write_to_file(&root.join("warg-config.json"))?;
+
+    publish_wit(
+        &config,
+        "foo:bar",
+        "1.0.0",
+        r#"package foo:bar@1.0.0
+world foo {
+    export bar: func() -> string
+}"#,
+        true,
+    )
+    .await?;
+
+    let project = Project::with_root(&root, "component", "--target foo:bar@1.0.0")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    let cache_dir = root.join("cache");
+    project
+        .cargo_component("build")
+        .env("CARGO_COMPONENT_CACHE_DIR", &cache_dir)
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    validate_component(&project.debug_wasm("component"))?;
+
+    assert!(cache_dir.join("registries").is_dir());
+    assert!(fs::read_dir(cache_dir.join("content"))?.next().is_some());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn it_fails_a_locked_build_with_a_drifted_lock_file() -> Result<()> {
+    let root = create_root()?;
+    let (_server, config) = spawn_server(&root).await?;
+    config.write_to_file(&root.join("warg-config.json"))?;
+
+    publish_wit(
+        &config,
+        "foo:bar",
+        "1.0.0",
+        r#"package foo:bar@1.0.0
+world foo {
+    export bar: func() -> string
+}"#,
+        true,
+    )
+    .await?;
+
+    let project = Project::with_root(&root, "component", "--target foo:bar@1.0.0")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    // A lock file is required for registry dependencies
+    project
+        .cargo_component("build --locked")
+        .assert()
+        .stderr(contains("needs to be updated but --locked was passed"))
+        .failure();
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    project
+        .cargo_component("build --locked")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    publish_wit(
+        &config,
+        "foo:bar",
+        "1.1.0",
+        r#"package foo:bar@1.1.0
+world foo {
+    export bar: func() -> string
+}"#,
+        false,
+    )
+    .await?;
+
+    project.update_manifest(|mut doc| {
+        doc["package"]["metadata"]["component"]["target"] = value("foo:bar@1.1.0");
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build --locked")
+        .assert()
+        .stderr(contains("needs to be updated but --locked was passed"))
+        .failure();
+
+    // A frozen build also may not access the network to resolve the update
+    project.cargo_component("build --frozen").assert().failure();
+
+    let lock_file = fs::read_to_string(project.root().join("Cargo-component.lock"))?;
+    assert!(lock_file.contains(r#"version = "1.0.0""#));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn it_reuses_the_lock_file_of_another_project() -> Result<()> {
+    let root = create_root()?;
+    let (_server, config) = spawn_server(&root).await?;
+    config.write_to_file(&root.join("warg-config.json"))?;
+
+    publish_wit(
+        &config,
+        "foo:bar",
+        "1.0.0",
+        r#"package foo:bar@1.0.0
+world foo {
+    export bar: func() -> string
+}"#,
+        true,
+    )
+    .await?;
+
+    let first = Project::with_root(&root, "first", "--target foo:bar@1.0.0")?;
+    first.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["target"] = value("foo:bar@1");
+        Ok(doc)
+    })?;
+
+    first
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    publish_wit(
+        &config,
+        "foo:bar",
+        "1.1.0",
+        r#"package foo:bar@1.1.0
+world foo {
+    export bar: func() -> string
+    export baz: func() -> string
+}"#,
+        false,
+    )
+    .await?;
+
+    // The second project resolves the version locked by the first project
+    // rather than the latest version
+    let second = Project::with_root(&root, "second", "--target foo:bar@1.0.0")?;
+    second.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["target"] = value("foo:bar@1");
+        Ok(doc)
+    })?;
+
+    let lock_file = first.root().join("Cargo-component.lock");
+    second
+        .cargo_component(&format!(
+            "build --reuse-lock-from {path}",
+            path = lock_file.display()
+        ))
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    validate_component(&second.debug_wasm("second"))?;
+
+    let lock_file = fs::read_to_string(second.root().join("Cargo-component.lock"))?;
+    assert!(lock_file.contains("version = \"1.0.0\""));
+    assert!(!lock_file.contains("1.1.0"));
+
+    Ok(())
+}
+
+#[test]
+fn it_warns_about_deprecated_items() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "package component:foo
+
+world example {
+    import iface: interface {
+        /// Greets the caller.
+        ///
+        /// @deprecated use greet2 instead
+        greet: func() -> string
+
+        greet2: func() -> string
+    }
+
+    export hello-world: func() -> string
+}",
+    )?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains(
+            "function `greet` of interface `iface` is deprecated: use greet2 instead",
+        ))
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn it_reports_deprecated_items_at_the_end_of_the_build() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "package component:foo
+
+world example {
+    import iface: interface {
+        /// @deprecated
+        record greeting {
+            text: string,
+        }
+
+        /// @deprecated use greet2 instead
+        greet: func() -> greeting
+
+        greet2: func() -> string
+    }
+
+    export hello-world: func() -> string
+}",
+    )?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(
+            is_match(
+                "(?s)Finished dev .*the target worlds of this build use 2 deprecated WIT items",
+            )?
+            .and(contains(
+                "package `foo`:
+  type `greeting` of interface `iface` is deprecated
+  function `greet` of interface `iface` is deprecated: use greet2 instead",
+            )),
+        )
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    Ok(())
+}
+
+#[test]
+fn it_embeds_the_component_version() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["version"] = value("2.1.0");
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains(
+            "component version 2.1.0 of package `foo` is not compatible with package version 0.1.0",
+        ))
+        .success();
+
+    let bytes = fs::read(project.debug_wasm("foo"))?;
+    let mut version = None;
+    for payload in wasmparser::Parser::new(0).parse_all(&bytes) {
+        if let wasmparser::Payload::CustomSection(reader) = payload? {
+            if reader.name() == "version" {
+                version = Some(std::str::from_utf8(reader.data())?.to_string());
+            }
+        }
+    }
+
+    assert_eq!(version.as_deref(), Some("2.1.0"));
+
+    Ok(())
+}
+
+#[test]
+fn it_embeds_custom_metadata() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["metadata"]["build-id"] = value("1234");
+        doc["package"]["metadata"]["component"]["metadata"]["pipeline"] =
+            value("https://example.com/pipelines/1");
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    let bytes = fs::read(project.debug_wasm("foo"))?;
+    let mut metadata = None;
+    for payload in wasmparser::Parser::new(0).parse_all(&bytes) {
+        if let wasmparser::Payload::CustomSection(reader) = payload? {
+            if reader.name() == "metadata" {
+                metadata = Some(serde_json::from_slice::<serde_json::Value>(reader.data())?);
+            }
+        }
+    }
+
+    let metadata = metadata.context("component has no metadata section")?;
+    assert_eq!(metadata["build-id"], "1234");
+    assert_eq!(metadata["pipeline"], "https://example.com/pipelines/1");
+
+    Ok(())
+}
+
+#[test]
+fn it_keeps_and_drops_custom_sections() -> Result<()> {
+    fn custom_sections(path: &std::path::Path) -> Result<Vec<String>> {
+        let bytes = fs::read(path)?;
+        let mut names = Vec::new();
+        for payload in wasmparser::Parser::new(0).parse_all(&bytes) {
+            if let wasmparser::Payload::CustomSection(reader) = payload? {
+                names.push(reader.name().to_string());
+            }
+        }
+
+        Ok(names)
+    }
+
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["keep-sections"] =
+            value(toml_edit::Array::from_iter(["name"]));
+        doc["package"]["metadata"]["component"]["drop-sections"] =
+            value(toml_edit::Array::from_iter(["version", "producers"]));
+        Ok(doc)
+    })?;
+
+    project.cargo_component("build").assert().success();
+    validate_component(&project.debug_wasm("foo"))?;
+
+    let sections = custom_sections(&project.debug_wasm("foo"))?;
+    assert!(sections.iter().any(|s| s == "name"));
+    assert!(sections.iter().any(|s| s.starts_with(".debug_")));
+    assert!(!sections.iter().any(|s| s == "version" || s == "producers"));
+
+    project.update_manifest(|mut doc| {
+        doc["package"]["metadata"]["component"]["drop-sections"] =
+            value(toml_edit::Array::from_iter(["name"]));
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains(
+            "custom section `name` cannot be both kept and dropped",
+        ))
+        .failure();
+
+    Ok(())
+}
+
+#[test]
+fn it_names_the_component() -> Result<()> {
+    fn component_name(path: &std::path::Path) -> Result<Option<String>> {
+        let bytes = fs::read(path)?;
+        for payload in wasmparser::Parser::new(0).parse_all(&bytes) {
+            if let wasmparser::Payload::CustomSection(reader) = payload? {
+                if reader.name() != "component-name" {
+                    continue;
+                }
+
+                let names = wasmparser::ComponentNameSectionReader::new(
+                    reader.data(),
+                    reader.data_offset(),
+                );
+                for name in names {
+                    if let wasmparser::ComponentName::Component { name, .. } = name? {
+                        return Ok(Some(name.to_string()));
+                    }
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+    assert_eq!(
+        component_name(&project.debug_wasm("foo"))?.as_deref(),
+        Some("foo")
+    );
+
+    let project = Project::new("bar")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["module-name"] = value("my-component");
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    validate_component(&project.debug_wasm("bar"))?;
+    assert_eq!(
+        component_name(&project.debug_wasm("bar"))?.as_deref(),
+        Some("my-component")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn it_builds_a_type_component() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "package component:foo
+
+interface types {
+    record point {
+        x: u32,
+        y: u32,
+    }
+}
+",
+    )?;
+
+    fs::write(project.root().join("src/lib.rs"), "")?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Creating type component").and(contains("Finished dev")))
+        .success();
+
+    let path = project.debug_wasm("foo");
+    validate_component(&path)?;
+
+    match wit_component::decode(&fs::read(path)?)? {
+        wit_component::DecodedWasm::WitPackage(resolve, pkg) => {
+            let package = &resolve.packages[pkg];
+            assert_eq!(package.name.to_string(), "component:foo");
+            assert!(package.interfaces.contains_key("types"));
+            assert!(package.worlds.is_empty());
+        }
+        _ => panic!("expected a WIT package"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn it_builds_with_rustflags() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["rustflags"] =
+            value(toml_edit::Array::from_iter(["--cfg", "component_flag"]));
+        Ok(doc)
+    })?;
+
+    // The component flags are layered over the ambient flags
+    project
+        .cargo_component("build -v")
+        .env("RUSTFLAGS", "--cfg ambient_flag")
+        .assert()
+        .stderr(
+            contains("adding component rustflags to the wasm compilation: `--cfg component_flag`")
+                .and(contains("--cfg ambient_flag"))
+                .and(contains("--cfg component_flag"))
+                .and(contains("Finished dev")),
+        )
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    // Changing the flags recompiles the package
+    project.update_manifest(|mut doc| {
+        doc["package"]["metadata"]["component"]["rustflags"] =
+            value(toml_edit::Array::from_iter(["--cfg", "other_flag"]));
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .env("RUSTFLAGS", "--cfg ambient_flag")
+        .assert()
+        .stderr(contains("Compiling foo").and(contains("Finished dev")))
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn it_builds_with_link_args() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["link-args"] =
+            value(toml_edit::Array::from_iter(["--strip-debug"]));
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build -v")
+        .assert()
+        .stderr(contains("-Clink-arg=--strip-debug").and(contains("Finished dev")))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    Ok(())
+}
+
+#[test]
+fn it_builds_with_a_panic_strategy() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["panic"] = value("abort");
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build -v")
+        .assert()
+        .stderr(contains("-Cpanic=abort").and(contains("Finished dev")))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    project.update_manifest(|mut doc| {
+        doc["package"]["metadata"]["component"]["panic"] = value("crash");
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("unknown variant `crash`"))
+        .failure();
+
+    Ok(())
+}
+
+#[test]
+fn it_fails_strict_builds_with_unused_wit_deps() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        let mut dependencies = Table::new();
+        dependencies["foo:unused"]["path"] = value("wit/deps/unused.wit");
+
+        let target =
+            doc["package"]["metadata"]["component"]["target"].or_insert(Item::Table(Table::new()));
+        target["dependencies"] = Item::Table(dependencies);
+        Ok(doc)
+    })?;
+
+    fs::create_dir_all(project.root().join("wit/deps"))?;
+    fs::write(
+        project.root().join("wit/deps/unused.wit"),
+        "package foo:unused
+
+interface unused {
+    type ty = u32
+}",
+    )?;
+
+    project
+        .cargo_component("build --strict")
+        .assert()
+        .stderr(contains(
+            "the target of package `foo` does not use the following target dependencies: `foo:unused`",
+        ))
+        .failure();
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    Ok(())
+}
+
+#[test]
+fn it_fails_with_conflicting_interfaces_in_deps() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        let mut dependencies = Table::new();
+        dependencies["foo:bar"]["path"] = value("wit/deps/bar.wasm");
+        dependencies["foo:baz"]["path"] = value("wit/deps/baz.wasm");
+
+        let target =
+            doc["package"]["metadata"]["component"]["target"].or_insert(Item::Table(Table::new()));
+        target["dependencies"] = Item::Table(dependencies);
+        Ok(doc)
+    })?;
+
+    // Encode two WIT packages that each define `baz:qux/qux` differently
+    let encode = |qux: &str, source: &str| -> Result<Vec<u8>> {
+        let mut resolve = wit_parser::Resolve::default();
+        resolve.push(wit_parser::UnresolvedPackage::parse(
+            "qux.wit".as_ref(),
+            qux,
+        )?)?;
+        let pkg = resolve.push(wit_parser::UnresolvedPackage::parse(
+            "pkg.wit".as_ref(),
+            source,
+        )?)?;
+        wit_component::encode(&resolve, pkg)
+    };
+
+    fs::create_dir_all(project.root().join("wit/deps"))?;
+    fs::write(
+        project.root().join("wit/deps/bar.wasm"),
+        encode(
+            "package baz:qux\ninterface qux {\n    type ty = u32\n}",
+            "package foo:bar\ninterface bar {\n    use baz:qux/qux.{ty}\n    bar: func() -> ty\n}",
+        )?,
+    )?;
+    fs::write(
+        project.root().join("wit/deps/baz.wasm"),
+        encode(
+            "package baz:qux\ninterface qux {\n    type ty = string\n}",
+            "package foo:baz\ninterface baz {\n    use baz:qux/qux.{ty}\n    baz: func() -> ty\n}",
+        )?,
+    )?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "package component:foo
+
+world example {
+    export foo:bar/bar
+    export foo:baz/baz
+}",
+    )?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(
+            contains("conflicting definitions of interface `baz:qux/qux` found")
+                .and(contains("definition from target dependency `foo:bar`"))
+                .and(contains("definition from target dependency `foo:baz`")),
+        )
+        .failure();
+
+    Ok(())
+}
+
+#[test]
+fn it_builds_with_allowed_lints() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    // The generated bindings contain unsafe code
+    fs::write(
+        project.root().join("src/lib.rs"),
+        r#"#![deny(unsafe_code)]
+
+cargo_component_bindings::generate!({
+    allow: ["unsafe_code", "clippy::all"]
+});
+
+use bindings::Guest;
+
+struct Component;
+
+impl Guest for Component {
+    fn hello_world() -> String {
+        "Hello, World!".to_string()
+    }
+}
+"#,
+    )?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    Ok(())
+}
+
+#[test]
+fn it_builds_with_a_custom_bindings_module() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    // The crate's own `bindings` module does not collide with the bindings
+    fs::write(
+        project.root().join("src/lib.rs"),
+        r#"cargo_component_bindings::generate!({
+    path: my_wit
+});
+
+mod bindings {
+    pub fn greeting() -> String {
+        "Hello, World!".to_string()
+    }
+}
+
+use my_wit::Guest;
+
+struct Component;
+
+impl Guest for Component {
+    fn hello_world() -> String {
+        bindings::greeting()
+    }
+}
+"#,
+    )?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    fs::write(
+        project.root().join("src/lib.rs"),
+        r#"cargo_component_bindings::generate!({
+    path: crate
+});
+"#,
+    )?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains(
+            "expected the name of the bindings module to be a valid module identifier",
+        ))
+        .failure();
+
+    Ok(())
+}
+
+#[test]
+fn it_builds_with_the_async_option_for_a_world_without_async_functions() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    // The world has no async functions, so the exports are unchanged
+    fs::write(
+        project.root().join("src/lib.rs"),
+        r#"cargo_component_bindings::generate!({
+    async: true
+});
+
+use bindings::Guest;
+
+struct Component;
+
+impl Guest for Component {
+    fn hello_world() -> String {
+        "Hello, World!".to_string()
+    }
+}
+"#,
+    )?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    Ok(())
+}
+
+#[test]
+fn it_rejects_an_invalid_qualified_target_world() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["target"]["world"] = value("my:comp/Not-A-World");
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains(
+            "invalid world name `Not-A-World` of world `my:comp/Not-A-World`",
+        ))
+        .failure();
+
+    Ok(())
+}
+
+#[test]
+fn it_builds_targeting_a_dependency_world() -> Result<()> {
+    let root = create_root()?;
+
+    let comp1 = Project::with_root(&root, "comp1", "")?;
+    comp1.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["package"] = value("my:comp1");
+        Ok(doc)
+    })?;
+
+    fs::write(
+        comp1.root().join("wit/world.wit"),
+        "
+package my:comp1
+
+world random-generator {
+    export rand: func(seed: u32) -> u32
+}
+",
+    )?;
+
+    fs::write(
+        comp1.root().join("src/lib.rs"),
+        r#"
+cargo_component_bindings::generate!();
+
+use bindings::Guest;
+
+struct Component;
+
+impl Guest for Component {
+    fn rand(seed: u32) -> u32 {
+        seed + 1
+    }
+}
+"#,
+    )?;
+
+    comp1
+        .cargo_component("build --release")
+        .assert()
+        .stderr(contains("Finished release [optimized] target(s)"))
+        .success();
+
+    let dep = comp1.release_wasm("comp1");
+    validate_component(&dep)?;
+
+    // Implement the world of `comp1` without a local target
+    let comp2 = Project::with_root(&root, "comp2", "")?;
+    comp2.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["dependencies"]["my:comp1"]["path"] =
+            value(dep.display().to_string());
+        doc["package"]["metadata"]["component"]["target"]["world"] =
+            value("my:comp1/random-generator");
+        Ok(doc)
+    })?;
+
+    fs::remove_dir_all(comp2.root().join("wit"))?;
+    fs::write(
+        comp2.root().join("src/lib.rs"),
+        r#"
+cargo_component_bindings::generate!();
+
+use bindings::Guest;
+
+struct Component;
+
+impl Guest for Component {
+    fn rand(seed: u32) -> u32 {
+        seed * 2
+    }
+}
+"#,
+    )?;
+
+    comp2
+        .cargo_component("build --release")
+        .assert()
+        .stderr(contains("Finished release [optimized] target(s)"))
+        .success();
+
+    validate_component(&comp2.release_wasm("comp2"))?;
+
+    Ok(())
+}
+
+#[test]
+fn it_builds_with_a_fallback_adapter() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["adapter"] = value(toml_edit::Array::from_iter([
+            "not-a-valid-path",
+            "also-not-a-valid-path",
+        ]));
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(
+            contains("failed to read a module adapter from any of the configured paths")
+                .and(contains("/not-a-valid-path`"))
+                .and(contains("also-not-a-valid-path")),
+        )
+        .failure();
+
+    // The first adapter is missing, so the second is used
+    project.update_manifest(|mut doc| {
+        doc["package"]["metadata"]["component"]["adapter"] = value(toml_edit::Array::from_iter([
+            "not-a-valid-path".to_string(),
+            format!(
+                "../../../../../adapters/{version}/wasi_snapshot_preview1.reactor.wasm",
+                version = env!("WASI_ADAPTER_VERSION")
+            ),
+        ]));
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    Ok(())
+}
+
+#[test]
+fn it_adds_the_source_crate_producers_field() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["package"] = value("my:component");
+        doc["package"]["metadata"]["component"]["producers"]["source"] = value(true);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build --release")
+        .assert()
+        .stderr(contains("Finished release [optimized] target(s)"))
+        .success();
+
+    let path = project.release_wasm("foo");
+
+    validate_component(&path)?;
+
+    let wasm = fs::read(&path)
+        .with_context(|| format!("failed to read wasm file `{path}`", path = path.display()))?;
+    let section = wasm_metadata::Producers::from_wasm(&wasm)?.expect("missing producers section");
+
+    assert_eq!(
+        section
+            .get("source")
+            .expect("missing source field")
+            .get("foo")
+            .expect("missing source crate"),
+        "0.1.0"
+    );
+
+    // The processed-by field is still written
+    assert!(section.get("processed-by").is_some());
+
+    Ok(())
+}
+
+#[test]
+fn it_builds_with_a_type_shared_by_interfaces() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "package component:foo
+
+interface types {
+    record point {
+        x: u32,
+        y: u32,
+    }
+}
+
+interface producer {
+    use types.{point}
+    get: func() -> point
+}
+
+interface consumer {
+    use types.{point}
+    put: func(p: point) -> u32
+}
+
+world example {
+    export producer
+    export consumer
+}",
+    )?;
+
+    // The exports must use the single `Point` type generated for `types`
+    fs::write(
+        project.root().join("src/lib.rs"),
+        r#"cargo_component_bindings::generate!();
+
+use bindings::component::foo::types::Point;
+use bindings::exports::component::foo::{consumer::Guest as Consumer, producer::Guest as Producer};
+
+struct Component;
+
+impl Producer for Component {
+    fn get() -> Point {
+        Point { x: 1, y: 2 }
+    }
+}
+
+impl Consumer for Component {
+    fn put(p: Point) -> u32 {
+        p.x + p.y
+    }
+}
+
+pub fn round_trip() -> u32 {
+    <Component as Consumer>::put(<Component as Producer>::get())
+}
+"#,
+    )?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    Ok(())
+}
+
+#[test]
+fn it_embeds_a_build_number() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .env("CARGO_COMPONENT_BUILD_NUMBER", "42")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    let path = project.debug_wasm("foo");
+    validate_component(&path)?;
+
+    let wasm = fs::read(&path)
+        .with_context(|| format!("failed to read wasm file `{path}`", path = path.display()))?;
+    let section = wasm_metadata::Producers::from_wasm(&wasm)?.expect("missing producers section");
+
+    assert_eq!(
+        section
+            .get("build-number")
+            .expect("missing build-number field")
+            .get("foo")
+            .expect("missing build number"),
+        "42"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn it_increments_the_build_number_file_only_when_writing_a_component() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["build-number-file"] = value("build-number");
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Creating component"))
+        .success();
+
+    assert_eq!(
+        fs::read_to_string(project.root().join("build-number"))?,
+        "1\n"
+    );
+
+    // An up-to-date build does not write the component again
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Creating component").not())
+        .success();
+
+    assert_eq!(
+        fs::read_to_string(project.root().join("build-number"))?,
+        "1\n"
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "smoke-test")]
+#[test]
+fn it_instantiates_a_component_as_a_smoke_test() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build --smoke-test")
+        .assert()
+        .stderr(contains("Instantiated component").and(contains("Finished dev")))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    Ok(())
+}
+
+#[test]
+fn it_builds_with_separate_import_and_export_modules() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "package component:foo
+
+interface logging {
+    log: func(message: string)
+}
+
+interface greeter {
+    greet: func() -> string
+}
+
+world example {
+    import logging
+    import now: func() -> u64
+    export greeter
+}",
+    )?;
+
+    fs::write(
+        project.root().join("src/lib.rs"),
+        r#"cargo_component_bindings::generate!();
+
+use bindings::exports::component::foo::greeter::Guest;
+use bindings::imports::{component::foo::logging, now};
+
+struct Component;
+
+impl Guest for Component {
+    fn greet() -> String {
+        logging::log("greeting");
+        format!("Hello at {}", now())
+    }
+}
+"#,
+    )?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    Ok(())
+}
+
+#[test]
+fn it_rejects_an_adapter_table_without_targets_or_profiles() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["adapter"]["wasm32-wasi"] = value(false);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains(
+            "an adapter table must have either a `targets` table keyed by target triple \
+             or a `profiles` table keyed by profile name",
+        ))
+        .failure();
+
+    Ok(())
+}
+
+#[test]
+fn it_selects_the_adapter_by_target() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        let mut adapters = Table::new();
+        adapters["wasm32-wasi"] = value("not-a-valid-path");
+        adapters["wasm32-unknown-unknown"] = value(false);
+        doc["package"]["metadata"]["component"]["adapter"]["targets"] = Item::Table(adapters);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("error: failed to read module adapter"))
+        .failure();
+
+    // The adapter for `wasm32-wasi` is not used for other targets
+    project
+        .cargo_component("build --target wasm32-unknown-unknown")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    validate_component(
+        &project
+            .build_dir()
+            .join("wasm32-unknown-unknown")
+            .join("debug")
+            .join("foo.wasm"),
+    )?;
+
+    project.update_manifest(|mut doc| {
+        doc["package"]["metadata"]["component"]["adapter"]["targets"]["wasm32-wasi"] =
+            value(format!(
+                "../../../../../adapters/{version}/wasi_snapshot_preview1.reactor.wasm",
+                version = env!("WASI_ADAPTER_VERSION")
+            ));
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    Ok(())
+}
+
+#[test]
+fn it_builds_with_a_redirected_runtime() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["runtime-path"] = value("::missing::rt");
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("missing"))
+        .failure();
+
+    // Create a local runtime crate that re-exports the runtime
+    fs::create_dir_all(project.root().join("runtime/src"))?;
+    fs::write(
+        project.root().join("runtime/Cargo.toml"),
+        r#"[package]
+name = "runtime"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+cargo-component-bindings = { path = "../../../../../../crates/bindings" }
+"#,
+    )?;
+    fs::write(
+        project.root().join("runtime/src/lib.rs"),
+        "pub use cargo_component_bindings::rt;\n",
+    )?;
+
+    project.update_manifest(|mut doc| {
+        doc["dependencies"]["runtime"] = value(InlineTable::from_iter([("path", "runtime")]));
+        doc["package"]["metadata"]["component"]["runtime-path"] = value("::runtime::rt");
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    Ok(())
+}
+
+#[test]
+fn it_overrides_the_runtime_from_the_environment() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .env("CARGO_COMPONENT_RUNTIME_PATH", "::missing::rt")
+        .assert()
+        .stderr(contains("missing"))
+        .failure();
+
+    // The bindings are regenerated once the override is removed
+    project
+        .cargo_component("build --explain-encoding")
+        .env_remove("CARGO_COMPONENT_RUNTIME_PATH")
+        .assert()
+        .stderr(
+            contains("the runtime path changed from `::missing::rt` to ``")
+                .and(contains("Finished dev [unoptimized + debuginfo] target(s)")),
+        )
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    Ok(())
+}
+
+#[test]
+fn it_prints_export_symbols() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build --export-symbols")
+        .assert()
+        .stderr(
+            contains("function `hello-world` is `hello-world`")
+                .and(contains("post-return `cabi_post_hello-world`")),
+        )
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    Ok(())
+}
+
+#[test]
+fn it_dumps_the_component_type() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build --dump-component-type component.wit")
+        .assert()
+        .stderr(contains("Dumped component type to `component.wit`"))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    let path = project.root().join("component.wit");
+    let wit = fs::read_to_string(&path)
+        .with_context(|| format!("failed to read `{path}`", path = path.display()))?;
+    assert!(wit.contains("export hello-world: func() -> string"));
+
+    // The adapter's imports are only merged into the component's type
+    let path = project.root().join("component.before-merge.wit");
+    let before = fs::read_to_string(&path)
+        .with_context(|| format!("failed to read `{path}`", path = path.display()))?;
+    assert!(before.contains("export hello-world: func() -> string"));
+    assert!(!before.contains("wasi:"));
+    assert!(wit.contains("wasi:"));
+
+    Ok(())
+}
+
+#[test]
+fn it_keeps_intermediate_artifacts() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Keeping").not())
+        .success();
+
+    let dir = project
+        .build_dir()
+        .join("wasm32-wasi/debug/component-debug");
+    assert!(!dir.exists());
+
+    project
+        .cargo_component("build --keep-temps")
+        .assert()
+        .stderr(contains("Keeping"))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+    validate_component(&dir.join("foo.component.wasm"))?;
+
+    // Both the module and the adapter are core modules
+    for name in ["foo.core.wasm", "foo.adapter.wasm"] {
+        let bytes = fs::read(dir.join(name))?;
+        assert_eq!(&bytes[..8], b"\0asm\x01\0\0\0");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn it_warns_about_unimplemented_exports() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["package"] = value("foo:bar");
+        Ok(doc)
+    })?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "
+            package foo:bar
+
+            world bar {
+                export hello: func() -> string
+                export qux: func()
+                export baz: interface {
+                    qux: func()
+                    done: func() -> u32
+                }
+            }
+        ",
+    )?;
+
+    // The trait of the interface is imported under another name, and the
+    // world's `qux` must not be confused with the interface's `qux`
+    fs::write(
+        project.root().join("src/lib.rs"),
+        r#"
+            cargo_component_bindings::generate!();
+
+            use bindings::exports::baz::Guest as BazGuest;
+
+            struct Component;
+
+            impl bindings::Guest for Component {
+                fn hello() -> String {
+                    todo!()
+                }
+
+                fn qux() {}
+            }
+
+            impl BazGuest for Component {
+                fn qux() {
+                    unimplemented!("later");
+                }
+
+                fn done() -> u32 {
+                    42
+                }
+            }
+        "#,
+    )?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("unimplemented export").not())
+        .success();
+
+    project
+        .cargo_component("build --warn-unimplemented")
+        .assert()
+        .stderr(
+            contains("package `foo` has 2 unimplemented exports")
+                .and(contains("`hello`"))
+                .and(contains("`baz#qux`"))
+                .and(contains("`qux`").not())
+                .and(contains("`baz#done`").not()),
+        )
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    Ok(())
+}
+
+#[test]
+fn it_builds_with_an_output_name() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["output-name"] = value("my-comp1");
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Renamed"))
+        .success();
+
+    validate_component(&project.debug_wasm("my-comp1"))?;
+    assert!(!project.debug_wasm("foo").exists());
+
+    // An explicit output path still wins
+    project
+        .cargo_component("build -o dist/component.wasm")
+        .assert()
+        .success();
+
+    validate_component(&project.root().join("dist/component.wasm"))?;
+    validate_component(&project.debug_wasm("my-comp1"))?;
+
+    Ok(())
+}
+
+#[test]
+fn it_fails_with_an_invalid_output_name() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["output-name"] = value("dist/foo");
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("invalid component output name `dist/foo`"))
+        .failure();
+
+    Ok(())
+}
+
+#[test]
+fn it_prints_json_component_messages() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    let output = project
+        .cargo_component("build --message-format=json")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let messages = std::str::from_utf8(&output)?
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<Vec<serde_json::Value>, _>>()?;
+
+    // Cargo's own messages are still printed
+    assert!(messages.iter().any(|m| m["reason"] == "compiler-artifact"));
+
+    let message = messages
+        .iter()
+        .find(|m| m["reason"] == "component-artifact")
+        .context("no component message was printed")?;
+
+    validate_component(&project.debug_wasm("foo"))?;
+    assert_eq!(message["name"], "foo");
+    assert_eq!(message["target"], "wasm32-wasi");
+    let path = message["component"]
+        .as_str()
+        .context("path is not a string")?;
+    assert!(std::path::Path::new(path).ends_with("wasm32-wasi/debug/foo.wasm"));
+    assert!(message.get("module").is_none());
+    assert_eq!(message["adapter"], true);
+    assert_eq!(message["world"], "example");
+
+    // The module is reported when it is kept in the temporary directory
+    let project = Project::new("bar")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    let output = project
+        .cargo_component("build --message-format=json --temp-dir intermediates")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let message = std::str::from_utf8(&output)?
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<Vec<serde_json::Value>, _>>()?
+        .into_iter()
+        .find(|m| m["reason"] == "component-artifact")
+        .context("no component message was printed")?;
+
+    let path = message["module"].as_str().context("path is not a string")?;
+    assert!(std::path::Path::new(path).ends_with("intermediates/bar.core.wasm"));
+    assert_eq!(&fs::read(path)?[..8], b"\0asm\x01\0\0\0");
+
+    Ok(())
+}
+
+#[test]
+fn it_copies_the_component_to_the_output_path() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build --release -o dist/foo.component.wasm")
+        .assert()
+        .stderr(contains("Copied component").and(contains("dist/foo.component.wasm")))
+        .success();
+
+    validate_component(&project.release_wasm("foo"))?;
+    validate_component(&project.root().join("dist/foo.component.wasm"))?;
+
+    project
+        .cargo_component(
+            "build --target wasm32-wasi --target wasm32-unknown-unknown --output=foo.wasm",
+        )
+        .assert()
+        .stderr(contains(
+            "cannot write multiple components to output file `foo.wasm`",
+        ))
+        .failure();
+
+    Ok(())
+}
+
+#[test]
+fn it_writes_intermediates_to_the_temp_dir() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build --temp-dir intermediates")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    let dir = project.root().join("intermediates");
+    validate_component(&dir.join("foo.wasm"))?;
+    assert_eq!(
+        &fs::read(dir.join("foo.core.wasm"))?[..8],
+        b"\0asm\x01\0\0\0"
+    );
+
+    // The component cache is kept in the directory rather than the target
+    assert!(dir.join("component-cache").is_dir());
+    assert!(!project
+        .build_dir()
+        .join("wasm32-wasi/debug/component-cache")
+        .exists());
+
+    // The directory may also be set from the environment
+    let project = Project::new("bar")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    let dir = project.root().join("intermediates");
+    project
+        .cargo_component("build")
+        .env("CARGO_COMPONENT_TEMP_DIR", &dir)
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    validate_component(&project.debug_wasm("bar"))?;
+    validate_component(&dir.join("bar.wasm"))?;
+    assert_eq!(
+        &fs::read(dir.join("bar.core.wasm"))?[..8],
+        b"\0asm\x01\0\0\0"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn it_reuses_the_cached_component_of_an_unchanged_module() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Creating component"))
+        .success();
+
+    let cache = project
+        .build_dir()
+        .join("wasm32-wasi")
+        .join("debug")
+        .join("component-cache");
+    validate_component(&cache.join("foo.wasm"))?;
+    assert!(cache.join("foo.hash").is_file());
+
+    // Rebuild the crate without changing the module
+    let path = project.root().join("src/lib.rs");
+    let mut src = fs::read_to_string(&path)?;
+    src.push_str("\n// A comment that does not change the module\n");
+    fs::write(&path, src)?;
+
+    project
+        .cargo_component("build")
+        .env("CARGO_COMPONENT_LOG", "debug")
+        .assert()
+        .stderr(contains("Compiling foo v0.1.0").and(contains("reusing cached component")))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    Ok(())
+}
+
+// On macOS, cargo overwrites the component of an up-to-date build
+#[cfg(not(target_os = "macos"))]
+#[test]
+fn it_recreates_the_component_when_the_adapter_changes() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["adapter"] = value("adapter.wasm");
+        Ok(doc)
+    })?;
+
+    let adapter = fs::read(format!(
+        "adapters/{version}/wasi_snapshot_preview1.reactor.wasm",
+        version = env!("WASI_ADAPTER_VERSION")
+    ))?;
+    fs::write(project.root().join("adapter.wasm"), &adapter)?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Creating component"))
+        .success();
+
+    // Nothing changed, so the component is not created again
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Creating component").not())
+        .success();
+
+    // Append a custom section to the adapter to change its contents
+    let mut changed = adapter;
+    changed.extend_from_slice(&[0x00, 0x05, 0x04, b't', b'e', b's', b't']);
+    fs::write(project.root().join("adapter.wasm"), changed)?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Creating component"))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Creating component").not())
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn it_fails_with_an_unreadable_module_adapter() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        let mut adapter = InlineTable::new();
+        adapter.insert("module", "logging".into());
+        adapter.insert("path", "adapters/logging.wasm".into());
+        doc["package"]["metadata"]["component"]["adapter"] = value(Array::from_iter([adapter]));
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains(
+            "error: failed to read the adapter of module `logging` from `",
+        ))
+        .failure();
+
+    Ok(())
+}
+
+#[test]
+fn it_rejects_a_module_adapter_of_wasi() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        let mut adapter = InlineTable::new();
+        adapter.insert("module", "wasi_snapshot_preview1".into());
+        adapter.insert("path", "adapters/wasi.wasm".into());
+        doc["package"]["metadata"]["component"]["adapter"] = value(Array::from_iter([adapter]));
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains(
+            "the adapter of module `wasi_snapshot_preview1` must be given as a path",
+        ))
+        .failure();
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn it_runs_the_given_cargo() -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let shim = project.root().join("cargo-shim");
+    fs::write(
+        &shim,
+        format!("#!/bin/sh\necho \"cargo shim: $1\" >&2\nexec \"{cargo}\" \"$@\"\n"),
     )?;
+    fs::set_permissions(&shim, fs::Permissions::from_mode(0o755))?;
 
-    project.cargo_component("build").assert().success();
+    project
+        .cargo_component("build")
+        .env("CARGO", &shim)
+        .assert()
+        .stderr(contains("cargo shim: build").and(contains("Finished dev")))
+        .success();
 
-    let dep = project.debug_wasm("foo");
-    validate_component(&dep)?;
+    validate_component(&project.debug_wasm("foo"))?;
+
+    project
+        .cargo_component(&format!("build --cargo {shim}", shim = shim.display()))
+        .env_remove("CARGO")
+        .assert()
+        .stderr(contains("cargo shim: build"))
+        .success();
 
     Ok(())
 }
 
 #[test]
-fn it_builds_with_a_component_dependency() -> Result<()> {
-    let root = create_root()?;
-
-    let comp1 = Project::with_root(&root, "comp1", "")?;
-    comp1.update_manifest(|mut doc| {
+fn it_builds_with_glob_target_dependencies() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
         redirect_bindings_crate(&mut doc);
+        let mut dependencies = Table::new();
+        dependencies["local:deps"]["path"] = value("wit/deps/*");
+
+        let target =
+            doc["package"]["metadata"]["component"]["target"].or_insert(Item::Table(Table::new()));
+        target["dependencies"] = Item::Table(dependencies);
         Ok(doc)
     })?;
 
+    fs::create_dir_all(project.root().join("wit/deps/foo-baz"))?;
     fs::write(
-        comp1.root().join("wit/world.wit"),
-        "
-package my:comp1
+        project.root().join("wit/deps/foo-bar.wit"),
+        "package foo:bar
 
-interface types {
-    record seed {
-        value: u32,
-    }
-}
+interface bar {
+    bar: func() -> string
+}",
+    )?;
+    fs::write(
+        project.root().join("wit/deps/foo-baz/baz.wit"),
+        "package foo:baz
 
-world random-generator {
-    use types.{seed}
-    export rand: func(seed: seed) -> u32
-}
-",
+interface baz {
+    baz: func() -> string
+}",
     )?;
+    fs::write(project.root().join("wit/deps/README.md"), "not a package")?;
 
     fs::write(
-        comp1.root().join("src/lib.rs"),
-        r#"
-cargo_component_bindings::generate!();
+        project.root().join("wit/world.wit"),
+        "package component:foo
 
-use bindings::{Guest, Seed};
+world example {
+    export foo:bar/bar
+    export foo:baz/baz
+}",
+    )?;
+
+    fs::write(
+        project.root().join("src/lib.rs"),
+        r#"cargo_component_bindings::generate!();
+use bindings::exports::foo::{bar::bar::Guest as Bar, baz::baz::Guest as Baz};
 
 struct Component;
 
-impl Guest for Component {
-    fn rand(seed: Seed) -> u32 {
-        seed.value + 1
+impl Bar for Component {
+    fn bar() -> String {
+        "bar".to_string()
+    }
+}
+
+impl Baz for Component {
+    fn baz() -> String {
+        "baz".to_string()
     }
 }
 "#,
     )?;
 
-    comp1
-        .cargo_component("build --release")
+    project
+        .cargo_component("build")
         .assert()
-        .stderr(contains("Finished release [optimized] target(s)"))
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
         .success();
 
-    let dep = comp1.release_wasm("comp1");
-    validate_component(&dep)?;
+    validate_component(&project.debug_wasm("foo"))?;
 
-    let comp2 = Project::with_root(&root, "comp2", "")?;
-    comp2.update_manifest(|mut doc| {
+    fs::write(
+        project.root().join("wit/deps/other-bar.wit"),
+        "package foo:bar
+
+interface other {}",
+    )?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(
+            contains("package `foo:bar` is defined by both")
+                .and(contains("foo-bar.wit"))
+                .and(contains("other-bar.wit"))
+                .and(contains("matched by dependency `local:deps`")),
+        )
+        .failure();
+
+    Ok(())
+}
+
+#[test]
+fn it_warns_when_a_glob_target_dependency_matches_nothing() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
         redirect_bindings_crate(&mut doc);
-        doc["package"]["metadata"]["component"]["dependencies"]["my:comp1"]["path"] =
-            value(dep.display().to_string());
+        let mut dependencies = Table::new();
+        dependencies["local:deps"]["path"] = value("wit/deps/*");
+
+        let target =
+            doc["package"]["metadata"]["component"]["target"].or_insert(Item::Table(Table::new()));
+        target["dependencies"] = Item::Table(dependencies);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("glob path `").and(contains(
+            "of dependency `local:deps` did not match any WIT packages",
+        )))
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn it_builds_with_bindings_options_from_the_manifest() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["package"] = value("foo:bar");
+        doc["package"]["metadata"]["component"]["bindings"]["implementor"] = value("Other");
+        doc["package"]["metadata"]["component"]["bindings"]["additional-derives"] =
+            value(Array::from_iter(["Hash"]));
         Ok(doc)
     })?;
 
     fs::write(
-        comp2.root().join("wit/world.wit"),
+        project.root().join("wit/world.wit"),
         "
-package my:comp2
+            package foo:bar
 
-world random-generator {
-    export rand: func() -> u32
-}
-",
+            world bar {
+                export baz: interface {
+                    record seed {
+                        value: u32,
+                    }
+
+                    hash: func(seed: seed) -> u64
+                }
+            }
+        ",
     )?;
 
+    // The derive comes from the manifest and the implementor is overridden
     fs::write(
-        comp2.root().join("src/lib.rs"),
+        project.root().join("src/lib.rs"),
         r#"
-cargo_component_bindings::generate!();
+            cargo_component_bindings::generate!({
+                implementor: Component,
+            });
 
-use bindings::{Guest, comp1};
+            use bindings::exports::baz::{Guest, Seed};
+            use std::{collections::hash_map::DefaultHasher, hash::{Hash, Hasher}};
 
-struct Component;
+            struct Component;
 
-impl Guest for Component {
-    fn rand() -> u32 {
-        comp1::rand(comp1::Seed { value: 1 })
-    }
-}
-"#,
+            impl Guest for Component {
+                fn hash(seed: Seed) -> u64 {
+                    let mut hasher = DefaultHasher::new();
+                    seed.hash(&mut hasher);
+                    hasher.finish()
+                }
+            }
+        "#,
     )?;
 
-    comp2
-        .cargo_component("build --release")
-        .assert()
-        .stderr(contains("Finished release [optimized] target(s)"))
-        .success();
-
-    let path: std::path::PathBuf = comp2.release_wasm("comp2");
-    validate_component(&path)?;
+    project.cargo_component("build").assert().success();
+    validate_component(&project.debug_wasm("foo"))?;
 
     Ok(())
 }
 
 #[test]
-fn it_builds_with_adapter() -> Result<()> {
+fn it_fails_with_invalid_bindings_options() -> Result<()> {
     let project = Project::new("foo")?;
     project.update_manifest(|mut doc| {
         redirect_bindings_crate(&mut doc);
-        doc["package"]["metadata"]["component"]["adapter"] = value("not-a-valid-path");
+        doc["package"]["metadata"]["component"]["bindings"]["ownership"] = value("sharing");
         Ok(doc)
     })?;
 
     project
         .cargo_component("build")
         .assert()
-        .stderr(contains("error: failed to read module adapter"))
+        .stderr(
+            contains("invalid bindings options in manifest")
+                .and(contains("invalid ownership `sharing`")),
+        )
         .failure();
 
+    Ok(())
+}
+
+#[test]
+fn it_strips_custom_sections() -> Result<()> {
+    fn custom_sections(path: &std::path::Path) -> Result<Vec<String>> {
+        let bytes = fs::read(path)?;
+        let mut names = Vec::new();
+        for payload in wasmparser::Parser::new(0).parse_all(&bytes) {
+            if let wasmparser::Payload::CustomSection(reader) = payload? {
+                names.push(reader.name().to_string());
+            }
+        }
+
+        Ok(names)
+    }
+
     let project = Project::new("foo")?;
     project.update_manifest(|mut doc| {
         redirect_bindings_crate(&mut doc);
-        doc["package"]["metadata"]["component"]["adapter"] = value(format!(
-            "../../../../../adapters/{version}/wasi_snapshot_preview1.reactor.wasm",
-            version = env!("WASI_ADAPTER_VERSION")
-        ));
         Ok(doc)
     })?;
 
+    project.cargo_component("build").assert().success();
+    let sections = custom_sections(&project.debug_wasm("foo"))?;
+    assert!(sections.iter().any(|s| s.starts_with(".debug_")));
+    assert!(sections.iter().any(|s| s == "name"));
+
+    project.update_manifest(|mut doc| {
+        doc["package"]["metadata"]["component"]["strip"] = value("debug");
+        Ok(doc)
+    })?;
+
+    project.cargo_component("build").assert().success();
+    validate_component(&project.debug_wasm("foo"))?;
+    let sections = custom_sections(&project.debug_wasm("foo"))?;
+    assert!(!sections
+        .iter()
+        .any(|s| s.starts_with(".debug_") || s == "name"));
+    assert!(sections.iter().any(|s| s == "producers"));
+    assert!(sections.iter().any(|s| s == "version"));
+
+    project.update_manifest(|mut doc| {
+        doc["package"]["metadata"]["component"]["strip"] = value("all-but-producers");
+        Ok(doc)
+    })?;
+
+    project.cargo_component("build").assert().success();
+    validate_component(&project.debug_wasm("foo"))?;
+    let sections = custom_sections(&project.debug_wasm("foo"))?;
+    assert!(!sections.is_empty());
+    assert!(sections.iter().all(|s| s == "producers"));
+
+    // Only the release profile strips debug information
+    project.update_manifest(|mut doc| {
+        let mut strip = InlineTable::new();
+        strip.insert("release", "debug".into());
+        doc["package"]["metadata"]["component"]["strip"] = value(strip);
+        Ok(doc)
+    })?;
+
+    project.cargo_component("build").assert().success();
+    let sections = custom_sections(&project.debug_wasm("foo"))?;
+    assert!(sections.iter().any(|s| s.starts_with(".debug_")));
+
+    project
+        .cargo_component("build --release")
+        .assert()
+        .success();
+    let sections = custom_sections(&project.release_wasm("foo"))?;
+    assert!(!sections
+        .iter()
+        .any(|s| s.starts_with(".debug_") || s == "name"));
+    assert!(sections.iter().any(|s| s == "producers"));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn it_builds_with_the_library_api() -> Result<()> {
+    use cargo_component::{build, config::Config, BuildOptions, CargoCommandError};
+    use cargo_component_core::terminal::{Color, Terminal, Verbosity};
+
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    let config = Config::new(Terminal::new(Verbosity::Quiet, Color::Never))?;
+    let manifest_path = project.root().join("Cargo.toml");
+    let output = build(
+        &config,
+        &BuildOptions {
+            manifest_path: Some(&manifest_path),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    assert_eq!(output.components.len(), 1);
+    let component = &output.components[0];
+    assert_eq!(component.package, "foo");
+    assert_eq!(component.path, project.debug_wasm("foo"));
+    assert_eq!(component.world.as_deref(), Some("example"));
+    validate_component(&component.path)?;
+
+    // A failing build is returned as an error instead of exiting
+    fs::write(project.root().join("src/lib.rs"), "not rust")?;
+    let e = build(
+        &config,
+        &BuildOptions {
+            manifest_path: Some(&manifest_path),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect_err("expected the build to fail");
+    assert!(e.downcast_ref::<CargoCommandError>().is_some());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn it_builds_a_hyphenated_package_with_the_library_api() -> Result<()> {
+    use cargo_component::{build, config::Config, BuildOptions};
+    use cargo_component_core::terminal::{Color, Terminal, Verbosity};
+
+    let project = Project::new("foo-bar")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    // The library is written as `foo_bar.wasm`
+    let config = Config::new(Terminal::new(Verbosity::Quiet, Color::Never))?;
+    let manifest_path = project.root().join("Cargo.toml");
+    let output = build(
+        &config,
+        &BuildOptions {
+            manifest_path: Some(&manifest_path),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    assert_eq!(output.components.len(), 1);
+    let component = &output.components[0];
+    assert_eq!(component.package, "foo-bar");
+    assert_eq!(component.path, project.debug_wasm("foo_bar"));
+    validate_component(&component.path)?;
+
+    Ok(())
+}
+
+#[test]
+fn it_fails_with_duplicate_definitions_across_wit_files() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    // Files contributing distinct items to the same package are allowed
+    fs::write(
+        project.root().join("wit/types.wit"),
+        "package component:foo
+
+/// A world named like one in `world.wit` in a comment: world example {}
+interface types {
+    type id = u32
+}",
+    )?;
+
     project
         .cargo_component("build")
         .assert()
         .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
         .success();
 
-    validate_component(&project.debug_wasm("foo"))?;
+    fs::write(
+        project.root().join("wit/other.wit"),
+        "package component:foo
+
+world example {
+    export hello-world: func() -> string
+}",
+    )?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(
+            contains("failed to parse the local target of package `foo` from directory")
+                .and(contains("duplicate item named `example`")),
+        )
+        .failure();
 
     Ok(())
 }
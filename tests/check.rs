@@ -23,6 +23,43 @@ fn it_checks_a_new_project() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn it_checks_for_the_wasm_target_without_creating_components() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("check")
+        .assert()
+        .stderr(
+            contains("Checking foo v0.1.0")
+                .and(contains("Creating component").not())
+                .and(contains("Finished dev [unoptimized + debuginfo] target(s)")),
+        )
+        .success();
+
+    assert!(project
+        .build_dir()
+        .join("wasm32-wasi")
+        .join("debug")
+        .is_dir());
+    assert!(!project.debug_wasm("foo").exists());
+
+    // The bindings generated by the check are reused by the build
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Creating component").and(contains("Encoding target").not()))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    Ok(())
+}
+
 #[test]
 fn it_finds_errors() -> Result<()> {
     let project = Project::new("foo")?;
@@ -99,3 +136,70 @@ edition = "2021"
 
     Ok(())
 }
+
+#[test]
+fn it_checks_thread_safe_resources() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "package component:foo
+
+world example {
+    import iface: interface {
+        resource res {
+            constructor()
+        }
+    }
+
+    export hello-world: func() -> string
+}",
+    )?;
+
+    fs::write(
+        project.root().join("src/lib.rs"),
+        r#"cargo_component_bindings::generate!({
+    thread_safe_resources: true
+});
+
+use bindings::Guest;
+
+struct Component;
+
+impl Guest for Component {
+    fn hello_world() -> String {
+        "Hello, World!".to_string()
+    }
+}
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+pub fn check() {
+    assert_send_sync::<bindings::iface::Res>();
+}
+"#,
+    )?;
+
+    project
+        .cargo_component("check --target wasm32-wasi")
+        .env("RUSTFLAGS", "-C target-feature=+atomics,+bulk-memory")
+        .assert()
+        .stderr(contains("Checking foo v0.1.0"))
+        .success();
+
+    // Without the `atomics` target feature, the handle type is not `Send`
+    project
+        .cargo_component("check --target wasm32-wasi")
+        .env_remove("RUSTFLAGS")
+        .assert()
+        .stderr(contains(
+            "`*const ()` cannot be sent between threads safely",
+        ))
+        .failure();
+
+    Ok(())
+}
@@ -33,3 +33,208 @@ fn it_documents() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn it_documents_non_exhaustive_enums() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "package component:foo
+
+world example {
+    enum color {
+        red,
+        green,
+    }
+
+    export get-color: func() -> color
+}",
+    )?;
+
+    fs::write(
+        project.root().join("src/lib.rs"),
+        "cargo_component_bindings::generate!({
+    non_exhaustive: true
+});
+
+use bindings::Guest;
+pub use bindings::Color;
+
+struct Component;
+
+impl Guest for Component {
+    fn get_color() -> Color {
+        Color::Red
+    }
+}
+",
+    )?;
+
+    project
+        .cargo_component("doc")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    let path = project
+        .build_dir()
+        .join("doc")
+        .join("foo")
+        .join("enum.Color.html");
+    let content = fs::read(&path).with_context(|| {
+        format!(
+            "failed to read generated doc file `{path}`",
+            path = path.display()
+        )
+    })?;
+    assert!(std::str::from_utf8(&content)?.contains("non_exhaustive"));
+
+    Ok(())
+}
+
+#[test]
+fn it_documents_resource_methods_with_their_wit_functions() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "package component:foo
+
+world example {
+    export baz: interface {
+        resource keyed-integer {
+            constructor(x: u32)
+            get: func() -> u32
+        }
+    }
+}",
+    )?;
+
+    fs::write(
+        project.root().join("src/lib.rs"),
+        "cargo_component_bindings::generate!();
+
+use std::cell::Cell;
+
+pub use bindings::exports::baz::GuestKeyedInteger;
+
+pub struct KeyedInteger(Cell<u32>);
+
+impl GuestKeyedInteger for KeyedInteger {
+    fn new(x: u32) -> Self {
+        Self(Cell::new(x))
+    }
+
+    fn get(&self) -> u32 {
+        self.0.get()
+    }
+}
+",
+    )?;
+
+    project
+        .cargo_component("doc")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    let path = project
+        .build_dir()
+        .join("doc")
+        .join("foo")
+        .join("trait.GuestKeyedInteger.html");
+    let content = fs::read(&path).with_context(|| {
+        format!(
+            "failed to read generated doc file `{path}`",
+            path = path.display()
+        )
+    })?;
+    let content = std::str::from_utf8(&content)?;
+    assert!(content.contains("[constructor]keyed-integer"));
+    assert!(content.contains("[method]keyed-integer.get"));
+
+    Ok(())
+}
+
+#[test]
+fn it_documents_resource_traits_with_their_wit_resource() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "package component:foo
+
+world example {
+    export baz: interface {
+        resource keyed-integer {
+            constructor(x: u32)
+            get: func() -> u32
+            set: func(x: u32)
+        }
+    }
+}",
+    )?;
+
+    fs::write(
+        project.root().join("src/lib.rs"),
+        "cargo_component_bindings::generate!();
+
+use std::cell::Cell;
+
+pub use bindings::exports::baz::GuestKeyedInteger;
+
+pub struct KeyedInteger(Cell<u32>);
+
+impl GuestKeyedInteger for KeyedInteger {
+    fn new(x: u32) -> Self {
+        Self(Cell::new(x))
+    }
+
+    fn get(&self) -> u32 {
+        self.0.get()
+    }
+
+    fn set(&self, x: u32) {
+        self.0.set(x);
+    }
+}
+",
+    )?;
+
+    project
+        .cargo_component("doc")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    let path = project
+        .build_dir()
+        .join("doc")
+        .join("foo")
+        .join("trait.GuestKeyedInteger.html");
+    let content = fs::read(&path).with_context(|| {
+        format!(
+            "failed to read generated doc file `{path}`",
+            path = path.display()
+        )
+    })?;
+    let content = std::str::from_utf8(&content)?;
+    assert!(content.contains("Implements the WIT resource <code>baz/keyed-integer</code>"));
+    assert!(content.contains("defaults to <code>KeyedInteger</code>"));
+    assert!(content.contains("[method]keyed-integer.set"));
+
+    Ok(())
+}
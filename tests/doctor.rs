@@ -0,0 +1,76 @@
+use crate::support::*;
+use anyhow::{Context, Result};
+use assert_cmd::prelude::*;
+use predicates::{prelude::PredicateBooleanExt, str::contains};
+use std::fs;
+use toml_edit::value;
+
+mod support;
+
+#[test]
+fn help() {
+    for arg in ["help doctor", "doctor -h", "doctor --help"] {
+        cargo_component(arg)
+            .assert()
+            .stdout(contains(
+                "Diagnose common problems with the setup of component packages",
+            ))
+            .success();
+    }
+}
+
+#[test]
+fn it_finds_no_problems() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("doctor")
+        .assert()
+        .stderr(contains("Finished no problems found"))
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn it_diagnoses_a_misconfigured_project() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        doc["lib"]
+            .as_table_mut()
+            .context("missing lib table")?
+            .remove("crate-type");
+        doc["dependencies"]
+            .as_table_mut()
+            .context("missing dependencies table")?
+            .remove("cargo-component-bindings");
+        doc["package"]["metadata"]["component"]["adapter"] = value("not-a-valid-path");
+        Ok(doc)
+    })?;
+
+    fs::write(project.root().join("src/lib.rs"), "")?;
+    fs::remove_dir_all(project.root().join("wit"))?;
+
+    project
+        .cargo_component("doctor")
+        .assert()
+        .stderr(
+            contains("the library of package `foo` is not a `cdylib`")
+                .and(contains(
+                    "package `foo` does not depend on `cargo-component-bindings`",
+                ))
+                .and(contains(
+                    "does not invoke `cargo_component_bindings::generate!`",
+                ))
+                .and(contains("no module adapter of package `foo` can be read"))
+                .and(contains("package `foo` defines no world to target"))
+                .and(contains("error: found 5 problems with the component setup")),
+        )
+        .failure();
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use crate::support::*;
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use predicates::{prelude::PredicateBooleanExt, str::contains};
+use std::fs;
+
+mod support;
+
+#[test]
+fn help() {
+    for arg in ["help expand", "expand -h", "expand --help"] {
+        cargo_component(arg)
+            .assert()
+            .stdout(contains(
+                "Print the macro-expanded source of a component package",
+            ))
+            .success();
+    }
+}
+
+#[test]
+fn it_prints_the_expanded_source() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("expand")
+        .assert()
+        .stdout(
+            contains("mod bindings")
+                .and(contains("pub trait Guest"))
+                .and(contains("struct Component")),
+        )
+        .success();
+
+    // The expansion should not leave a componentized output behind
+    assert!(!project.debug_wasm("foo").exists());
+
+    Ok(())
+}
+
+#[test]
+fn it_expands_the_implementor_option() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    fs::write(
+        project.root().join("src/lib.rs"),
+        "cargo_component_bindings::generate!({
+    implementor: MyComponent,
+});
+
+use bindings::Guest;
+
+struct MyComponent;
+
+impl Guest for MyComponent {
+    fn hello_world() -> String {
+        \"Hello, World!\".to_string()
+    }
+}
+",
+    )?;
+
+    project
+        .cargo_component("expand")
+        .assert()
+        .stdout(contains("MyComponent as _GuestImpl"))
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn it_rejects_non_component_packages() -> Result<()> {
+    let project = Project::new("foo")?;
+    fs::write(
+        project.root().join("Cargo.toml"),
+        "[package]
+name = \"foo\"
+version = \"0.1.0\"
+edition = \"2021\"
+",
+    )?;
+
+    project
+        .cargo_component("expand")
+        .assert()
+        .stderr(contains(
+            "package `foo` is not a WebAssembly component package",
+        ))
+        .failure();
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use crate::support::*;
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use predicates::{prelude::PredicateBooleanExt, str::contains};
+use std::fs;
+
+mod support;
+
+#[test]
+fn it_formats_wit_files() -> Result<()> {
+    let project = Project::new("foo")?;
+
+    let path = project.root().join("wit/world.wit");
+    fs::write(
+        &path,
+        "package component:foo
+world   example {
+export    hello-world:   func( ) ->   string
+    }",
+    )?;
+
+    project
+        .cargo_component("fmt --check")
+        .assert()
+        .stderr(contains("is not formatted"))
+        .failure();
+
+    project
+        .cargo_component("fmt")
+        .assert()
+        .stderr(contains("Formatted WIT file"))
+        .success();
+
+    let contents = fs::read_to_string(&path)?;
+    assert!(contents.contains("world example {\n  export hello-world: func() -> string\n}\n"));
+
+    project.cargo_component("fmt --check").assert().success();
+
+    Ok(())
+}
+
+#[test]
+fn it_skips_wit_files_with_comments() -> Result<()> {
+    let project = Project::new("foo")?;
+
+    let path = project.root().join("wit/world.wit");
+    let source = "package component:foo
+// A comment that is not a doc comment
+world   example {
+export    hello-world:   func( ) ->   string
+    }";
+    fs::write(&path, source)?;
+
+    project
+        .cargo_component("fmt")
+        .assert()
+        .stderr(
+            contains("as it would remove comments or reorder items")
+                .and(contains("Formatted WIT file").not()),
+        )
+        .success();
+
+    assert_eq!(fs::read_to_string(&path)?, source);
+
+    project
+        .cargo_component("fmt --check")
+        .assert()
+        .stderr(contains("as it would remove comments or reorder items"))
+        .success();
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use crate::support::*;
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use predicates::str::contains;
+use std::fs;
+
+mod support;
+
+#[test]
+fn it_migrates_snake_case_keys() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    let path = project.root().join("Cargo.toml");
+    let mut manifest = fs::read_to_string(&path)?;
+    manifest.push_str(
+        "
+[package.metadata.component.producers]
+# Keep the processed-by field
+processed_by = true
+",
+    );
+    fs::write(&path, &manifest)?;
+
+    project
+        .cargo_component("migrate --dry-run")
+        .assert()
+        .stderr(contains(
+            "renamed `package.metadata.component.producers.processed_by` to `package.metadata.component.producers.processed-by`",
+        ))
+        .success();
+
+    assert_eq!(fs::read_to_string(&path)?, manifest);
+
+    project
+        .cargo_component("migrate")
+        .assert()
+        .stderr(contains("Migrated renamed"))
+        .success();
+
+    let migrated = fs::read_to_string(&path)?;
+    assert!(migrated.contains("# Keep the processed-by field\nprocessed-by = true\n"));
+    assert!(!migrated.contains("processed_by"));
+
+    project
+        .cargo_component("migrate")
+        .assert()
+        .stderr(contains("component metadata is already up-to-date"))
+        .success();
+
+    assert_eq!(fs::read_to_string(&path)?, migrated);
+
+    project.cargo_component("build").assert().success();
+
+    Ok(())
+}
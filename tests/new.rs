@@ -1,8 +1,9 @@
 use crate::support::*;
 use anyhow::Result;
 use assert_cmd::prelude::*;
-use predicates::{str::contains, Predicate};
+use predicates::{prelude::PredicateBooleanExt, str::contains, Predicate};
 use std::fs;
+use toml_edit::Document;
 
 mod support;
 
@@ -39,6 +40,77 @@ fn it_creates_the_expected_files_for_bin() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn it_creates_a_command_without_a_target() -> Result<()> {
+    let root = create_root()?;
+
+    cargo_component("new --command foo")
+        .current_dir(&root)
+        .assert()
+        .stderr(contains("Updated manifest of package `foo"))
+        .success();
+
+    let manifest = fs::read_to_string(root.join("foo/Cargo.toml"))?;
+    assert!(contains("[package.metadata.component]").eval(&manifest));
+    assert!(!contains("target").eval(&manifest));
+    assert!(!contains("crate-type").eval(&manifest));
+
+    cargo_component("new --command --reactor bar")
+        .current_dir(&root)
+        .assert()
+        .stderr(contains(
+            "the argument '--command' cannot be used with '--reactor'",
+        ))
+        .failure();
+
+    Ok(())
+}
+
+#[test]
+fn it_creates_a_wit_package() -> Result<()> {
+    let root = create_root()?;
+
+    cargo_component("new --wit my:iface foo")
+        .current_dir(&root)
+        .assert()
+        .stderr(contains("Updated manifest of package `foo`"))
+        .success();
+
+    let proj_dir = root.join("foo");
+    let manifest = fs::read_to_string(proj_dir.join("Cargo.toml"))?;
+    assert!(contains("package = \"my:iface\"").eval(&manifest));
+    assert!(!contains("cargo-component-bindings").eval(&manifest));
+    assert!(
+        contains("interface example").eval(&fs::read_to_string(proj_dir.join("wit/world.wit"))?)
+    );
+
+    cargo_component("build")
+        .current_dir(&proj_dir)
+        .assert()
+        .stderr(contains("Creating type component").and(contains("Finished dev")))
+        .success();
+
+    let path = proj_dir.join("target/wasm32-wasi/debug/foo.wasm");
+    validate_component(&path)?;
+
+    match wit_component::decode(&fs::read(path)?)? {
+        wit_component::DecodedWasm::WitPackage(resolve, pkg) => {
+            let package = &resolve.packages[pkg];
+            assert_eq!(package.name.to_string(), "my:iface");
+            assert!(package.interfaces.contains_key("example"));
+        }
+        _ => panic!("expected a WIT package"),
+    }
+
+    cargo_component("new --wit my:iface --reactor bar")
+        .current_dir(&root)
+        .assert()
+        .stderr(contains("cannot be used with"))
+        .failure();
+
+    Ok(())
+}
+
 #[test]
 fn it_creates_the_expected_files() -> Result<()> {
     let root = create_root()?;
@@ -60,6 +132,32 @@ fn it_creates_the_expected_files() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn it_creates_a_bare_package() -> Result<()> {
+    let root = create_root()?;
+
+    cargo_component("new --reactor --bare foo")
+        .current_dir(&root)
+        .assert()
+        .stderr(contains("Updated manifest of package `foo`"))
+        .success();
+
+    let proj_dir = root.join("foo");
+
+    let source = fs::read_to_string(proj_dir.join("src/lib.rs"))?;
+    assert!(!source.contains("impl"));
+
+    let world = fs::read_to_string(proj_dir.join("wit/world.wit"))?;
+    assert!(world.contains("world foo {}"));
+    assert!(!world.contains("hello-world"));
+
+    let manifest = fs::read_to_string(proj_dir.join("Cargo.toml"))?;
+    assert!(manifest.contains("package = \"component:foo\""));
+    assert!(!manifest.contains("[package.metadata.component.dependencies]"));
+
+    Ok(())
+}
+
 #[test]
 fn it_supports_editor_option() -> Result<()> {
     let root = create_root()?;
@@ -181,3 +279,71 @@ async fn it_errors_if_target_does_not_exist() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn it_targets_a_world_without_the_reactor_flag() -> Result<()> {
+    let root = create_root()?;
+    let (_server, config) = spawn_server(&root).await?;
+    config.write_to_file(&root.join("warg-config.json"))?;
+
+    publish_wit(
+        &config,
+        "foo:bar",
+        "1.2.3",
+        r#"package foo:bar@1.2.3
+world foo {
+    export bar: func(name: string) -> string
+}"#,
+        true,
+    )
+    .await?;
+
+    cargo_component("new component --target foo:bar@1.0.0")
+        .current_dir(&root)
+        .assert()
+        .success();
+
+    let dir = root.join("component");
+    let manifest = fs::read_to_string(dir.join("Cargo.toml"))?;
+    assert!(contains(r#"target = "foo:bar@1.2.3""#).eval(&manifest));
+    assert!(contains(r#"crate-type = ["cdylib"]"#).eval(&manifest));
+
+    let source = fs::read_to_string(dir.join("src/lib.rs"))?;
+    assert!(contains("impl Guest for Component")
+        .and(contains("fn bar(name: String) -> String"))
+        .and(contains("unimplemented!()"))
+        .eval(&source));
+
+    let mut doc: Document = manifest.parse()?;
+    redirect_bindings_crate(&mut doc);
+    fs::write(dir.join("Cargo.toml"), doc.to_string())?;
+
+    cargo_component("build")
+        .current_dir(&dir)
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+    validate_component(&dir.join("target/wasm32-wasi/debug/component.wasm"))?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn it_errors_if_the_target_cannot_be_fetched_offline() -> Result<()> {
+    let root = create_root()?;
+    let (_server, config) = spawn_server(&root).await?;
+    config.write_to_file(&root.join("warg-config.json"))?;
+
+    cargo_component("new component --target foo:bar@1.0.0 --offline")
+        .current_dir(&root)
+        .assert()
+        .stderr(
+            contains("failed to fetch the WIT package of target `foo:bar`")
+                .and(contains("network access is disabled")),
+        )
+        .failure();
+
+    assert!(!root.join("component").exists());
+
+    Ok(())
+}
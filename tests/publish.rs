@@ -1,10 +1,10 @@
 use crate::support::*;
 use anyhow::{Context, Result};
 use assert_cmd::prelude::*;
-use predicates::str::contains;
+use predicates::{prelude::PredicateBooleanExt, str::contains};
 use semver::Version;
 use std::fs;
-use toml_edit::{value, Array};
+use toml_edit::{value, Array, Item, Table};
 use warg_client::Client;
 use warg_protocol::registry::PackageId;
 use wasm_metadata::LinkType;
@@ -278,3 +278,161 @@ async fn it_publishes_with_registry_metadata() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn it_reports_the_package_on_a_dry_run() -> Result<()> {
+    let root = create_root()?;
+    let (_server, config) = spawn_server(&root).await?;
+    config.write_to_file(&root.join("warg-config.json"))?;
+
+    let project = Project::with_root(&root, "foo", "--namespace test")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["description"] = value("A test component");
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("publish --init --dry-run")
+        .env("CARGO_COMPONENT_PUBLISH_KEY", test_signing_key())
+        .assert()
+        .stderr(
+            contains("Would publish package `test:foo` v0.1.0")
+                .and(contains("Description A test component"))
+                .and(contains("Producer processed-by: cargo-component"))
+                .and(contains("not publishing component to the registry")),
+        )
+        .success();
+
+    // The dry run should not have initialized the package in the registry
+    project
+        .cargo_component("publish")
+        .env("CARGO_COMPONENT_PUBLISH_KEY", test_signing_key())
+        .assert()
+        .stderr(contains("error: package `test:foo` does not exist"))
+        .failure();
+
+    // A component that does not export its whole target world fails the run
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "package test:foo
+
+interface greet {
+    greet: func() -> string
+}
+
+world example {
+    export greet
+    export hello-world: func() -> string
+}",
+    )?;
+
+    fs::write(
+        project.root().join("src/lib.rs"),
+        r#"cargo_component_bindings::generate!({
+    exclude: ["test:foo/greet"]
+});
+
+use bindings::Guest;
+
+struct Component;
+
+impl Guest for Component {
+    fn hello_world() -> String {
+        "Hello, World!".to_string()
+    }
+}
+"#,
+    )?;
+
+    project
+        .cargo_component("publish --init --dry-run")
+        .env("CARGO_COMPONENT_PUBLISH_KEY", test_signing_key())
+        .assert()
+        .stderr(
+            contains("does not conform to target world `example` of package `foo`")
+                .and(contains("missing export `test:foo/greet`"))
+                .and(contains("Would publish").not()),
+        )
+        .failure();
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn it_warns_about_a_missing_producer_on_a_dry_run() -> Result<()> {
+    let root = create_root()?;
+    let (_server, config) = spawn_server(&root).await?;
+    config.write_to_file(&root.join("warg-config.json"))?;
+
+    let project = Project::with_root(&root, "foo", "--namespace test")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["producers"]["processed-by"] = value(false);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("publish --init --dry-run")
+        .env("CARGO_COMPONENT_PUBLISH_KEY", test_signing_key())
+        .assert()
+        .stderr(
+            contains("Would publish package `test:foo` v0.1.0")
+                .and(contains(
+                    "component has no `processed-by` producer for `cargo-component`",
+                ))
+                .and(contains("not publishing component to the registry")),
+        )
+        .success();
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn it_refuses_to_publish_with_local_deps() -> Result<()> {
+    let root = create_root()?;
+    let (_server, config) = spawn_server(&root).await?;
+    config.write_to_file(&root.join("warg-config.json"))?;
+
+    let project = Project::with_root(&root, "foo", "--namespace test")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        let mut dependencies = Table::new();
+        dependencies["foo:bar"]["path"] = value("wit/deps/foo-bar");
+
+        let target =
+            doc["package"]["metadata"]["component"]["target"].or_insert(Item::Table(Table::new()));
+        target["dependencies"] = Item::Table(dependencies);
+        Ok(doc)
+    })?;
+
+    fs::create_dir_all(project.root().join("wit/deps/foo-bar"))?;
+    fs::write(
+        project.root().join("wit/deps/foo-bar/bar.wit"),
+        "package foo:bar
+
+interface baz {
+    baz: func()
+}",
+    )?;
+
+    project
+        .cargo_component("publish --init --dry-run")
+        .env("CARGO_COMPONENT_PUBLISH_KEY", test_signing_key())
+        .assert()
+        .stderr(
+            contains("package `foo` has dependencies on local paths")
+                .and(contains("`foo:bar` (path `"))
+                .and(contains("use `--allow-local-deps` to publish anyway")),
+        )
+        .failure();
+
+    project
+        .cargo_component("publish --init --dry-run --allow-local-deps")
+        .env("CARGO_COMPONENT_PUBLISH_KEY", test_signing_key())
+        .assert()
+        .stderr(contains("Would publish package `test:foo` v0.1.0"))
+        .success();
+
+    Ok(())
+}
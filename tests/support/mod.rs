@@ -89,14 +89,17 @@ pub fn create_root() -> Result<PathBuf> {
     Ok(root)
 }
 
-pub fn cargo_component(args: &str) -> Command {
+pub fn bin_path(name: &str) -> PathBuf {
     let mut exe = std::env::current_exe().unwrap();
     exe.pop(); // remove test exe name
     exe.pop(); // remove `deps`
-    exe.push("cargo-component");
+    exe.push(name);
     exe.set_extension(std::env::consts::EXE_EXTENSION);
+    exe
+}
 
-    let mut cmd = Command::new(&exe);
+pub fn cargo_component(args: &str) -> Command {
+    let mut cmd = Command::new(bin_path("cargo-component"));
     cmd.arg("component");
     for arg in args.split_whitespace() {
         cmd.arg(arg);
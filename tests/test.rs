@@ -0,0 +1,87 @@
+use crate::support::*;
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use predicates::{prelude::PredicateBooleanExt, str::contains};
+use toml_edit::{value, Array};
+
+mod support;
+
+#[test]
+fn it_passes_through_to_cargo_test() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("test --lib")
+        .assert()
+        .stderr(contains("Running unittests").and(contains("Validated component").not()))
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn it_validates_components_and_runs_host_tests() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("test --components")
+        .assert()
+        .stderr(
+            contains("Validated component")
+                .and(contains("Testing component").not())
+                .and(contains("Running unittests")),
+        )
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn it_runs_the_test_runner() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["test-runner"] =
+            value(Array::from_iter(["sh", "-c", "echo \"testing $0\""]));
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("test --components")
+        .assert()
+        .stdout(contains("testing ").and(contains("foo.wasm")))
+        .stderr(contains("Testing component"))
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn it_fails_when_the_test_runner_fails() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["test-runner"] =
+            value(Array::from_iter(["sh", "-c", "exit 1"]));
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("test --components")
+        .assert()
+        .stderr(
+            contains("test runner `sh -c exit 1` failed for component")
+                .and(contains("Running unittests").not()),
+        )
+        .failure();
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use crate::support::*;
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use predicates::{prelude::PredicateBooleanExt, str::contains};
+use std::fs;
+use toml_edit::{value, Item, Table};
+
+mod support;
+
+#[test]
+fn help() {
+    for arg in ["help tree", "tree -h", "tree --help"] {
+        cargo_component(arg)
+            .assert()
+            .stdout(contains(
+                "Display the dependency tree of component packages",
+            ))
+            .success();
+    }
+}
+
+#[test]
+fn it_prints_target_and_component_dependencies() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        let mut dependencies = Table::new();
+        dependencies["foo:bar"]["path"] = value("wit/deps/foo-bar.wit");
+
+        let target =
+            doc["package"]["metadata"]["component"]["target"].or_insert(Item::Table(Table::new()));
+        target["dependencies"] = Item::Table(dependencies);
+
+        doc["package"]["metadata"]["component"]["dependencies"]["my:comp"]["path"] =
+            value("comp.wasm");
+        Ok(doc)
+    })?;
+
+    fs::create_dir_all(project.root().join("wit/deps"))?;
+    fs::write(
+        project.root().join("wit/deps/foo-bar.wit"),
+        "package foo:bar
+
+interface baz {
+    qux: func() -> string
+}",
+    )?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "package component:foo
+
+world example {
+    export foo:bar/baz
+}",
+    )?;
+
+    fs::write(
+        project.root().join("comp.wasm"),
+        wat::parse_str("(component)")?,
+    )?;
+
+    project
+        .cargo_component("tree")
+        .assert()
+        .stdout(
+            contains("foo v0.1.0\n")
+                .and(contains("├── component:foo (target)\n"))
+                .and(contains(
+                    "│   └── foo:bar (target dependency, path wit/deps/foo-bar.wit)\n",
+                ))
+                .and(contains(
+                    "└── my:comp (component dependency, path comp.wasm)\n",
+                )),
+        )
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn it_rejects_a_non_component_package() -> Result<()> {
+    let project = project()?
+        .file(
+            "Cargo.toml",
+            r#"[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+"#,
+        )?
+        .file("src/lib.rs", "")?
+        .build();
+
+    project
+        .cargo_component("tree")
+        .assert()
+        .stderr(contains("no WebAssembly component packages were selected"))
+        .failure();
+
+    Ok(())
+}
@@ -3,7 +3,7 @@ use anyhow::{Context, Result};
 use assert_cmd::prelude::*;
 use predicates::{prelude::PredicateBooleanExt, str::contains};
 use std::fs;
-use toml_edit::value;
+use toml_edit::{value, Document, Item, Table};
 
 mod support;
 
@@ -320,3 +320,201 @@ async fn update_with_changed_dependencies() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn update_locks_wit_and_component_dependencies_separately() -> Result<()> {
+    let root = create_root()?;
+    let (_server, config) = spawn_server(&root).await?;
+    config.write_to_file(&root.join("warg-config.json"))?;
+
+    publish_wit(
+        &config,
+        "foo:bar",
+        "1.0.0",
+        r#"package foo:bar@1.0.0
+world foo {
+    import foo: func() -> string
+    export bar: func() -> string
+}"#,
+        true,
+    )
+    .await?;
+
+    publish_component(&config, "foo:baz", "1.0.0", "(component)", true).await?;
+
+    let project = Project::with_root(&root, "component", "--target foo:bar@1.0.0")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("add foo:baz")
+        .assert()
+        .stderr(contains("Added dependency `foo:baz` with version `1.0.0`"))
+        .success();
+
+    project
+        .cargo_component("update")
+        .assert()
+        .stderr(
+            contains("Adding dependency `foo:bar` v1.0.0")
+                .and(contains("Adding dependency `foo:baz` v1.0.0")),
+        )
+        .success();
+
+    let path = project.root().join("Cargo-component.lock");
+    let contents = fs::read_to_string(&path)
+        .with_context(|| format!("failed to read lock file `{path}`", path = path.display()))?;
+    let lock: Document = contents.parse()?;
+
+    let ids = |section: &str| -> Vec<String> {
+        lock[section]
+            .as_array_of_tables()
+            .map(|packages| {
+                packages
+                    .iter()
+                    .filter_map(|p| p["id"].as_str().map(ToString::to_string))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    assert_eq!(ids("package"), ["foo:bar"]);
+    assert_eq!(ids("component"), ["foo:baz"]);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn update_only_the_given_packages() -> Result<()> {
+    let root = create_root()?;
+    let (_server, config) = spawn_server(&root).await?;
+    config.write_to_file(&root.join("warg-config.json"))?;
+
+    publish_wit(
+        &config,
+        "foo:bar",
+        "1.0.0",
+        r#"package foo:bar@1.0.0
+world foo {
+    export bar: func() -> string
+}"#,
+        true,
+    )
+    .await?;
+
+    publish_component(&config, "foo:baz", "1.0.0", "(component)", true).await?;
+
+    let project = Project::with_root(&root, "component", "--target foo:bar@1.0.0")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("add foo:baz")
+        .assert()
+        .stderr(contains("Added dependency `foo:baz` with version `1.0.0`"))
+        .success();
+
+    project.cargo_component("update").assert().success();
+
+    publish_wit(
+        &config,
+        "foo:bar",
+        "1.1.0",
+        r#"package foo:bar@1.1.0
+world foo {
+    export bar: func() -> string
+}"#,
+        false,
+    )
+    .await?;
+
+    publish_component(&config, "foo:baz", "1.1.0", "(component)", false).await?;
+
+    project
+        .cargo_component("update foo:baz")
+        .assert()
+        .stderr(contains("`foo:baz` v1.0.0 -> v1.1.0").and(contains("foo:bar").not()))
+        .success();
+
+    project
+        .cargo_component("update")
+        .assert()
+        .stderr(contains("`foo:bar` v1.0.0 -> v1.1.0").and(contains("foo:baz").not()))
+        .success();
+
+    project
+        .cargo_component("update foo:qux")
+        .assert()
+        .stderr(contains(
+            "package `foo:qux` is not a locked registry dependency",
+        ))
+        .failure();
+
+    Ok(())
+}
+
+#[test]
+fn update_without_registry_dependencies_is_a_noop() -> Result<()> {
+    let project = Project::new("foo")?;
+
+    project
+        .cargo_component("update")
+        .assert()
+        .stderr(contains("there are no registry dependencies to update"))
+        .success();
+
+    assert!(!project.root().join("Cargo-component.lock").exists());
+
+    Ok(())
+}
+
+#[test]
+fn update_locks_local_path_dependencies() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        let mut dependencies = Table::new();
+        dependencies["foo:bar"]["path"] = value("wit/deps/foo-bar.wit");
+
+        let target =
+            doc["package"]["metadata"]["component"]["target"].or_insert(Item::Table(Table::new()));
+        target["dependencies"] = Item::Table(dependencies);
+        Ok(doc)
+    })?;
+
+    fs::create_dir_all(project.root().join("wit/deps"))?;
+    fs::write(
+        project.root().join("wit/deps/foo-bar.wit"),
+        "package foo:bar
+
+interface baz {
+    qux: func() -> string
+}",
+    )?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "package component:foo
+
+world example {
+    export foo:bar/baz
+}",
+    )?;
+
+    project
+        .cargo_component("update")
+        .assert()
+        .stderr(contains(
+            "Locking path dependency `foo:bar` at `wit/deps/foo-bar.wit`",
+        ))
+        .success();
+
+    let lock_file = fs::read_to_string(project.root().join("Cargo-component.lock"))?;
+    assert!(lock_file.contains("[[path]]"));
+    assert!(lock_file.contains(r#"id = "foo:bar""#));
+
+    Ok(())
+}
@@ -0,0 +1,153 @@
+use crate::support::*;
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use predicates::{prelude::PredicateBooleanExt, str::contains};
+use std::fs;
+use toml_edit::{value, Document, Item, Table};
+
+mod support;
+
+#[test]
+fn help() {
+    for arg in ["help vendor-wit", "vendor-wit -h", "vendor-wit --help"] {
+        cargo_component(arg)
+            .assert()
+            .stdout(contains(
+                "Vendor the target dependencies of a component package",
+            ))
+            .success();
+    }
+}
+
+#[test]
+fn it_vendors_target_dependencies() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        let mut dependencies = Table::new();
+        dependencies["foo:bar"]["path"] = value("wit-src/foo-bar");
+        dependencies["bar:baz"]["path"] = value("wit-src/bar-baz.wit");
+
+        let target =
+            doc["package"]["metadata"]["component"]["target"].or_insert(Item::Table(Table::new()));
+        target["dependencies"] = Item::Table(dependencies);
+        Ok(doc)
+    })?;
+
+    // The `baz:qux` package is only found in the `deps` tree of `foo:bar`
+    fs::create_dir_all(project.root().join("wit-src/foo-bar/deps/baz-qux"))?;
+    fs::write(
+        project.root().join("wit-src/foo-bar/deps/baz-qux/qux.wit"),
+        "package baz:qux
+
+interface qux {
+    type ty = u32
+}",
+    )?;
+
+    fs::write(
+        project.root().join("wit-src/foo-bar/bar.wit"),
+        "package foo:bar
+
+interface baz {
+    use baz:qux/qux.{ty}
+    baz: func() -> ty
+}",
+    )?;
+
+    fs::write(
+        project.root().join("wit-src/bar-baz.wit"),
+        "package bar:baz
+
+interface qux {
+    qux: func()
+}",
+    )?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "package component:foo
+
+world example {
+    export foo:bar/baz
+    export bar:baz/qux
+}",
+    )?;
+
+    fs::write(
+        project.root().join("src/lib.rs"),
+        "cargo_component_bindings::generate!();
+use bindings::exports::{foo::bar::baz::{Guest as Baz, Ty}, bar::baz::qux::Guest as Qux};
+
+struct Component;
+
+impl Baz for Component {
+    fn baz() -> Ty {
+        todo!()
+    }
+}
+
+impl Qux for Component {
+    fn qux() {
+        todo!()
+    }
+}
+",
+    )?;
+
+    project
+        .cargo_component("vendor-wit")
+        .assert()
+        .stderr(
+            contains("Vendored target dependency `bar:baz` to `wit/deps/bar-baz`").and(contains(
+                "Vendored target dependency `foo:bar` to `wit/deps/foo-bar`",
+            )),
+        )
+        .success();
+
+    let vendored = project.root().join("wit/deps");
+    assert!(vendored.join("bar-baz/package.wit").is_file());
+    assert!(vendored.join("foo-bar/package.wit").is_file());
+    assert!(vendored.join("foo-bar/deps/baz-qux/package.wit").is_file());
+
+    let manifest: Document = fs::read_to_string(project.root().join("Cargo.toml"))?.parse()?;
+    let dependencies = &manifest["package"]["metadata"]["component"]["target"]["dependencies"];
+    assert_eq!(
+        dependencies["foo:bar"]["path"].as_str(),
+        Some("wit/deps/foo-bar")
+    );
+    assert_eq!(
+        dependencies["bar:baz"]["path"].as_str(),
+        Some("wit/deps/bar-baz")
+    );
+
+    // The vendored copy should be all that is needed to build the component
+    fs::remove_dir_all(project.root().join("wit-src"))?;
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    validate_component(&project.debug_wasm("foo"))?;
+
+    Ok(())
+}
+
+#[test]
+fn it_skips_packages_without_target_dependencies() -> Result<()> {
+    let project = Project::new("foo")?;
+
+    project
+        .cargo_component("vendor-wit")
+        .assert()
+        .stderr(contains(
+            "package `foo` has no target dependencies to vendor",
+        ))
+        .success();
+
+    assert!(!project.root().join("wit/deps").exists());
+
+    Ok(())
+}
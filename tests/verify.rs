@@ -0,0 +1,165 @@
+use crate::support::*;
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use predicates::{prelude::PredicateBooleanExt, str::contains};
+use std::fs;
+
+mod support;
+
+/// Encodes a component from WAT with a `producers` section.
+fn component(wat: &str) -> Result<Vec<u8>> {
+    let mut producers = wasm_metadata::Producers::empty();
+    producers.add("processed-by", "test", "1.0.0");
+    producers.add_to_wasm(&wat::parse_str(wat)?)
+}
+
+const HOST_WIT: &str = "package host:env
+
+world host {
+    import get-name: func() -> string
+}";
+
+#[test]
+fn it_verifies_a_component_against_a_host_world() -> Result<()> {
+    let project = project()?.file("host.wit", HOST_WIT)?.build();
+
+    fs::write(
+        project.root().join("component.wasm"),
+        component(r#"(component (import "get-name" (func (result string))))"#)?,
+    )?;
+
+    project
+        .cargo_component("verify --against host.wit component.wasm")
+        .assert()
+        .stderr(contains("Verified component `component.wasm`"))
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn it_reports_unmet_imports() -> Result<()> {
+    let project = project()?.file("host.wit", HOST_WIT)?.build();
+
+    fs::write(
+        project.root().join("component.wasm"),
+        component(
+            r#"(component
+                (import "get-name" (func (result string)))
+                (import "get-age" (func (result u32)))
+            )"#,
+        )?,
+    )?;
+
+    project
+        .cargo_component("verify --against host.wit component.wasm")
+        .assert()
+        .stderr(contains("imports not provided by host world `host`").and(contains("`get-age`")))
+        .failure();
+
+    Ok(())
+}
+
+#[test]
+fn it_reports_imports_with_mismatched_signatures() -> Result<()> {
+    let project = project()?.file("host.wit", HOST_WIT)?.build();
+
+    fs::write(
+        project.root().join("component.wasm"),
+        component(r#"(component (import "get-name" (func (result u32))))"#)?,
+    )?;
+
+    project
+        .cargo_component("verify --against host.wit component.wasm")
+        .assert()
+        .stderr(
+            contains("imports not provided by host world `host`")
+                .and(contains("`get-name` (mismatched signature)")),
+        )
+        .failure();
+
+    Ok(())
+}
+
+#[test]
+fn it_verifies_a_component_without_a_host_world() -> Result<()> {
+    let project = project()?.build();
+
+    fs::write(
+        project.root().join("component.wasm"),
+        component(r#"(component (import "get-name" (func (result string))))"#)?,
+    )?;
+
+    project
+        .cargo_component("verify component.wasm")
+        .assert()
+        .stderr(
+            contains("Verified component `component.wasm`")
+                .and(contains("World"))
+                .and(contains("Producer processed-by: test 1.0.0")),
+        )
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn it_fails_to_verify_a_component_without_producers() -> Result<()> {
+    let project = project()?.build();
+
+    fs::write(
+        project.root().join("component.wasm"),
+        wat::parse_str("(component)")?,
+    )?;
+
+    project
+        .cargo_component("verify component.wasm")
+        .assert()
+        .stderr(contains(
+            "component `component.wasm` has no `producers` section",
+        ))
+        .failure();
+
+    Ok(())
+}
+
+#[test]
+fn it_verifies_a_component_without_producers_against_a_host_world() -> Result<()> {
+    let project = project()?.file("host.wit", HOST_WIT)?.build();
+
+    fs::write(
+        project.root().join("component.wasm"),
+        wat::parse_str(r#"(component (import "get-name" (func (result string))))"#)?,
+    )?;
+
+    project
+        .cargo_component("verify --against host.wit component.wasm")
+        .assert()
+        .stderr(
+            contains("Verified component `component.wasm` against host world `host`")
+                .and(contains("Producer").not()),
+        )
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn it_fails_to_verify_a_module() -> Result<()> {
+    let project = project()?.build();
+
+    fs::write(
+        project.root().join("module.wasm"),
+        wat::parse_str("(module)")?,
+    )?;
+
+    project
+        .cargo_component("verify module.wasm")
+        .assert()
+        .stderr(contains(
+            "`module.wasm` is a WebAssembly module and not a component",
+        ))
+        .failure();
+
+    Ok(())
+}
@@ -0,0 +1,184 @@
+use crate::support::*;
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use predicates::{prelude::PredicateBooleanExt, str::contains};
+use std::fs;
+use toml_edit::{value, Item, Table};
+
+mod support;
+
+#[test]
+fn it_prints_the_target_world() -> Result<()> {
+    let project = Project::new("foo")?;
+
+    project
+        .cargo_component("wit")
+        .assert()
+        .stdout(contains("world example").and(contains("export hello-world: func() -> string")))
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn it_prints_the_wit_of_the_built_component() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("wit --built")
+        .assert()
+        .stderr(contains("package `foo` has no built component in `"))
+        .failure();
+
+    project
+        .cargo_component("build")
+        .assert()
+        .stderr(contains("Finished dev [unoptimized + debuginfo] target(s)"))
+        .success();
+
+    project
+        .cargo_component("wit --built")
+        .assert()
+        .stdout(
+            contains("package root:component")
+                .and(contains("export hello-world: func() -> string")),
+        )
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn it_prints_the_wit_of_a_renamed_component_built_with_a_profile() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        redirect_bindings_crate(&mut doc);
+        doc["package"]["metadata"]["component"]["output-name"] = value("renamed");
+        Ok(doc)
+    })?;
+
+    project
+        .cargo_component("wit --built --release")
+        .assert()
+        .stderr(contains(
+            "; build it with `cargo component build --release`",
+        ))
+        .failure();
+
+    project
+        .cargo_component("build --release")
+        .assert()
+        .stderr(contains("Finished release [optimized] target(s)"))
+        .success();
+
+    project
+        .cargo_component("wit --built --release")
+        .assert()
+        .stdout(contains("export hello-world: func() -> string"))
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn it_prints_the_target_world_as_json() -> Result<()> {
+    let project = Project::new("foo")?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "package component:foo
+
+world example {
+    import iface: interface {
+        resource res {
+            constructor()
+            describe: func() -> string
+        }
+    }
+
+    export hello-world: func() -> string
+    export greet: func(name: string) -> result<string, u32>
+}",
+    )?;
+
+    let output = project.cargo_component("wit --json").output()?;
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(json["package"], "component:foo");
+    assert_eq!(json["world"], "example");
+
+    let import = &json["imports"][0];
+    assert_eq!(import["name"], "iface");
+    assert_eq!(import["types"][0]["name"], "res");
+    assert_eq!(import["types"][0]["kind"], "resource");
+
+    let exports: Vec<_> = json["exports"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(exports, ["hello-world", "greet"]);
+    assert_eq!(
+        json["exports"][1]["results"][0]["type"],
+        "result<string, u32>"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn it_lists_the_source_files_of_the_target_world() -> Result<()> {
+    let project = Project::new("foo")?;
+    project.update_manifest(|mut doc| {
+        let mut dependencies = Table::new();
+        dependencies["foo:bar"]["path"] = value("wit/deps/foo-bar");
+
+        let target =
+            doc["package"]["metadata"]["component"]["target"].or_insert(Item::Table(Table::new()));
+        target["dependencies"] = Item::Table(dependencies);
+        Ok(doc)
+    })?;
+
+    fs::create_dir_all(project.root().join("wit/deps/foo-bar"))?;
+    fs::write(
+        project.root().join("wit/deps/foo-bar/bar.wit"),
+        "package foo:bar
+
+interface bar {
+    bar: func() -> string
+}",
+    )?;
+
+    fs::write(
+        project.root().join("wit/world.wit"),
+        "package component:foo
+
+world example {
+    import foo:bar/bar
+    export hello-world: func() -> string
+}",
+    )?;
+
+    project
+        .cargo_component("wit --list-sources")
+        .assert()
+        .stdout(
+            contains(project.root().join("wit/world.wit").display().to_string()).and(contains(
+                project
+                    .root()
+                    .join("wit/deps/foo-bar/bar.wit")
+                    .display()
+                    .to_string(),
+            )),
+        )
+        .stdout(contains("world example").not())
+        .success();
+
+    Ok(())
+}